@@ -0,0 +1,374 @@
+//! `Accept` and `Accept-Language` headers and quality values.
+//!
+//! See https://httpwg.org/specs/rfc9110.html#field.accept and
+//! https://httpwg.org/specs/rfc9110.html#field.accept-language.
+
+use crate::{De, Ser};
+use mime::Mime;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when an `Accept` or `Accept-Language` header, or a
+/// quality value, is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidAccept {
+    reason: &'static str,
+}
+
+impl InvalidAccept {
+    fn new(reason: &'static str) -> Self {
+        InvalidAccept { reason }
+    }
+}
+
+impl fmt::Display for InvalidAccept {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid Accept header: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidAccept {}
+
+/// An item of a weighted list, such as `text/html;q=0.8`.
+///
+/// This is serialised as an `[item, quality]` tuple.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityItem<T> {
+    /// The item.
+    pub item: T,
+    /// The quality weight of the item, in thousandths, from 0 to 1000.
+    pub quality: u16,
+}
+
+impl<T> QualityItem<T> {
+    /// The maximum quality weight, which is the default one.
+    pub const MAX_QUALITY: u16 = 1000;
+
+    /// Returns an item with the maximum quality weight.
+    pub fn new(item: T) -> Self {
+        QualityItem { item, quality: Self::MAX_QUALITY }
+    }
+}
+
+/// Trims the optional whitespace around `s`.
+fn trim_ows(s: &str) -> &str {
+    s.trim_matches(|c| c == ' ' || c == '\t')
+}
+
+/// Parses a quality value such as `0.8`, returning it in thousandths.
+fn parse_quality(s: &str) -> Result<u16, InvalidAccept> {
+    let invalid = || InvalidAccept::new("invalid quality value");
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if frac.len() > 3 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let frac = frac.bytes()
+        .chain("000".bytes())
+        .take(3)
+        .fold(0, |v, b| v * 10 + (b - b'0') as u16);
+    match int {
+        "0" => Ok(frac),
+        "1" if frac == 0 => Ok(1000),
+        _ => Err(invalid()),
+    }
+}
+
+/// Splits a weighted list element into its item and quality weight, where
+/// the item is what precedes the `q` parameter. Extensions following the
+/// `q` parameter are ignored.
+fn split_quality(s: &str) -> Result<(&str, u16), InvalidAccept> {
+    for (i, _) in s.match_indices(';') {
+        let param = s[i + 1..].split(';').next().unwrap();
+        if let Some((name, value)) = param.split_once('=') {
+            if trim_ows(name).eq_ignore_ascii_case("q") {
+                return Ok((trim_ows(&s[..i]), parse_quality(trim_ows(value))?));
+            }
+        }
+    }
+    Ok((s, QualityItem::<()>::MAX_QUALITY))
+}
+
+/// Writes `quality` as a `q` parameter, unless it is the maximum one.
+fn write_quality(formatter: &mut fmt::Formatter, quality: u16) -> fmt::Result {
+    match quality {
+        1000 => Ok(()),
+        0 => formatter.write_str(";q=0"),
+        _ => {
+            let frac = format!("{:03}", quality);
+            write!(formatter, ";q=0.{}", frac.trim_end_matches('0'))
+        },
+    }
+}
+
+/// A parsed `Accept` header, listing the media ranges a client accepts with
+/// their quality weights, in the order of the header.
+///
+/// This is serialised as a sequence of `[media_range, quality]` tuples,
+/// where `quality` is in thousandths, e.g. `[["text/html", 1000],
+/// ["*/*", 800]]`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Accept {
+    items: Vec<QualityItem<Mime>>,
+}
+
+impl Accept {
+    /// Returns the number of media ranges.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether there are no media ranges.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns an iterator over the media ranges, in the order of the header.
+    pub fn iter(&self) -> impl Iterator<Item = &QualityItem<Mime>> {
+        self.items.iter()
+    }
+
+    /// Returns the media ranges by decreasing quality weight, keeping the
+    /// order of the header for equal weights.
+    pub fn by_preference(&self) -> Vec<&QualityItem<Mime>> {
+        let mut items = self.items.iter().collect::<Vec<_>>();
+        items.sort_by_key(|item| cmp::Reverse(item.quality));
+        items
+    }
+
+    /// Adds a media range at the end of the list.
+    pub fn push(&mut self, item: QualityItem<Mime>) -> Result<(), InvalidAccept> {
+        self.items.push(validate(item)?);
+        Ok(())
+    }
+}
+
+fn validate<T>(item: QualityItem<T>) -> Result<QualityItem<T>, InvalidAccept> {
+    if item.quality > QualityItem::<T>::MAX_QUALITY {
+        return Err(InvalidAccept::new("quality value out of range"));
+    }
+    Ok(item)
+}
+
+impl FromStr for Accept {
+    type Err = InvalidAccept;
+
+    /// Parses a comma-separated list of media ranges, ignoring empty
+    /// elements as required for lists.
+    fn from_str(s: &str) -> Result<Self, InvalidAccept> {
+        let mut accept = Accept::default();
+        for element in s.split(',') {
+            let element = trim_ows(element);
+            if element.is_empty() {
+                continue;
+            }
+            let (media_range, quality) = split_quality(element)?;
+            let media_range = media_range.parse::<Mime>()
+                .map_err(|_| InvalidAccept::new("invalid media range"))?;
+            accept.push(QualityItem { item: media_range, quality })?;
+        }
+        Ok(accept)
+    }
+}
+
+impl fmt::Display for Accept {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            write!(formatter, "{}", item.item)?;
+            write_quality(formatter, item.quality)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for QualityItem<T>
+    where De<T>: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (item, quality) = <(De<T>, u16)>::deserialize(deserializer)?;
+        validate(QualityItem { item: item.into_inner(), quality }).map_err(de::Error::custom)
+    }
+}
+
+impl<T> Serialize for QualityItem<T>
+    where for<'a> Ser<'a, T>: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (Ser::new(&self.item), self.quality).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Accept {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct AcceptVisitor;
+
+        impl<'de> Visitor<'de> for AcceptVisitor {
+            type Value = Accept;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of weighted media ranges")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Accept, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut items = Vec::with_capacity(capacity);
+                while let Some(item) = visitor.next_element()? {
+                    items.push(item);
+                }
+                Ok(Accept { items })
+            }
+        }
+
+        deserializer.deserialize_seq(AcceptVisitor)
+    }
+}
+
+impl Serialize for Accept {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_seq(&self.items)
+    }
+}
+
+/// A parsed `Accept-Language` header, listing the language ranges a client
+/// prefers with their quality weights, in the order of the header.
+///
+/// Language ranges are kept as in the header, e.g. `en-GB` or `*`, as they
+/// are matched case-insensitively.
+///
+/// This is serialised as a sequence of `[language_range, quality]` tuples,
+/// where `quality` is in thousandths, e.g. `[["fr-CH", 1000], ["fr", 900]]`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AcceptLanguage {
+    items: Vec<QualityItem<String>>,
+}
+
+impl AcceptLanguage {
+    /// Returns the number of language ranges.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether there are no language ranges.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns an iterator over the language ranges, in the order of the
+    /// header.
+    pub fn iter(&self) -> impl Iterator<Item = &QualityItem<String>> {
+        self.items.iter()
+    }
+
+    /// Returns the language ranges by decreasing quality weight, keeping the
+    /// order of the header for equal weights.
+    pub fn by_preference(&self) -> Vec<&QualityItem<String>> {
+        let mut items = self.items.iter().collect::<Vec<_>>();
+        items.sort_by_key(|item| cmp::Reverse(item.quality));
+        items
+    }
+
+    /// Adds a language range at the end of the list.
+    pub fn push(&mut self, item: QualityItem<String>) -> Result<(), InvalidAccept> {
+        if !is_language_range(&item.item) {
+            return Err(InvalidAccept::new("invalid language range"));
+        }
+        self.items.push(validate(item)?);
+        Ok(())
+    }
+}
+
+/// Returns whether `s` is a basic language range as defined by RFC 4647,
+/// such as `de-CH` or `*`.
+fn is_language_range(s: &str) -> bool {
+    let is_subtag = |subtag: &str, is_char: fn(&u8) -> bool| {
+        (1..=8).contains(&subtag.len()) && subtag.as_bytes().iter().all(is_char)
+    };
+    let mut subtags = s.split('-');
+    s == "*" || (is_subtag(subtags.next().unwrap(), u8::is_ascii_alphabetic) &&
+                 subtags.all(|subtag| is_subtag(subtag, u8::is_ascii_alphanumeric)))
+}
+
+impl FromStr for AcceptLanguage {
+    type Err = InvalidAccept;
+
+    /// Parses a comma-separated list of language ranges, ignoring empty
+    /// elements as required for lists.
+    fn from_str(s: &str) -> Result<Self, InvalidAccept> {
+        let mut accept = AcceptLanguage::default();
+        for element in s.split(',') {
+            let element = trim_ows(element);
+            if element.is_empty() {
+                continue;
+            }
+            let (language_range, quality) = split_quality(element)?;
+            accept.push(QualityItem { item: language_range.to_owned(), quality })?;
+        }
+        Ok(accept)
+    }
+}
+
+impl fmt::Display for AcceptLanguage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            formatter.write_str(&item.item)?;
+            write_quality(formatter, item.quality)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for AcceptLanguage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct AcceptLanguageVisitor;
+
+        impl<'de> Visitor<'de> for AcceptLanguageVisitor {
+            type Value = AcceptLanguage;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of weighted language ranges")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<AcceptLanguage, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let mut accept = AcceptLanguage::default();
+                while let Some((item, quality)) = visitor.next_element::<(String, u16)>()? {
+                    accept.push(QualityItem { item, quality }).map_err(de::Error::custom)?;
+                }
+                Ok(accept)
+            }
+        }
+
+        deserializer.deserialize_seq(AcceptLanguageVisitor)
+    }
+}
+
+impl Serialize for AcceptLanguage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_seq(self.items.iter().map(|item| (&item.item, item.quality)))
+    }
+}
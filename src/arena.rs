@@ -0,0 +1,253 @@
+//! Deserialisation of headers into caller-supplied arenas.
+
+use crate::names::parse_header_name;
+use http::HeaderMap;
+use hyper::header::HeaderValue;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_bytes::ByteBuf;
+use std::cmp;
+use std::fmt;
+
+/// An allocator in which deserialised header names and values get copied.
+///
+/// This is meant to be implemented by bump allocators, so that short-lived
+/// deserialised headers do not go through the global allocator. With the
+/// `bumpalo` feature, it is implemented for `bumpalo::Bump`.
+pub trait Arena {
+    /// Copies `bytes` in the arena.
+    fn alloc_bytes(&self, bytes: &[u8]) -> &[u8];
+}
+
+#[cfg(feature = "bumpalo")]
+impl Arena for bumpalo::Bump {
+    fn alloc_bytes(&self, bytes: &[u8]) -> &[u8] {
+        self.alloc_slice_copy(bytes)
+    }
+}
+
+/// Headers whose names and values live in an `Arena`.
+///
+/// Values of this type are obtained through the `InArena` seed, which
+/// accepts the same representation as `De<HeaderMap>`. Like `RawHeaders`,
+/// names and values are not validated until `to_header_map` is called.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ArenaHeaders<'a> {
+    entries: Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> ArenaHeaders<'a> {
+    /// Returns the number of header values.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no header values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the header names and values, yielding each
+    /// value of a header separately.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + '_ {
+        self.entries.iter().cloned()
+    }
+
+    /// Returns the first value of the header named `name`, which is matched
+    /// case-insensitively.
+    pub fn get(&self, name: &str) -> Option<&'a [u8]> {
+        self.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name.as_bytes()))
+            .map(|(_, v)| v)
+    }
+
+    /// Validates the headers, returning them as a `HeaderMap`.
+    pub fn to_header_map(&self) -> Result<HeaderMap, http::Error> {
+        let mut headers = HeaderMap::with_capacity(self.entries.len());
+        for &(name, value) in &self.entries {
+            headers.append(parse_header_name(name)?,
+                           HeaderValue::from_bytes(value)?);
+        }
+        Ok(headers)
+    }
+}
+
+/// A seed to deserialize headers into an `Arena`, as `ArenaHeaders`.
+///
+/// Names and values given as strings or bytes are copied directly in the
+/// arena, while the ones given as sequences of integers are collected
+/// beforehand.
+pub struct InArena<'a, A: 'a> {
+    arena: &'a A,
+}
+
+impl<'a, A> InArena<'a, A>
+    where A: Arena,
+{
+    /// Returns a new seed allocating in `arena`.
+    #[inline(always)]
+    pub fn new(arena: &'a A) -> Self {
+        InArena { arena }
+    }
+}
+
+impl<'a, A> fmt::Debug for InArena<'a, A> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("InArena")
+    }
+}
+
+impl<'de, 'a, A> DeserializeSeed<'de> for InArena<'a, A>
+    where A: Arena,
+{
+    type Value = ArenaHeaders<'a>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct HeadersVisitor<'a, A: 'a>(&'a A);
+
+        impl<'de, 'a, A> Visitor<'de> for HeadersVisitor<'a, A>
+            where A: Arena,
+        {
+            type Value = ArenaHeaders<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a map from header names to header values")
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Ok(ArenaHeaders::default())
+            }
+
+            fn visit_map<V>(self,
+                            mut visitor: V)
+                            -> Result<Self::Value, V::Error>
+                where V: MapAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut entries = Vec::with_capacity(capacity);
+                while let Some(name) = visitor.next_key_seed(BytesIn(self.0))? {
+                    visitor.next_value_seed(ValuesIn {
+                        arena: self.0,
+                        name,
+                        entries: &mut entries,
+                    })?;
+                }
+                Ok(ArenaHeaders { entries })
+            }
+        }
+
+        deserializer.deserialize_map(HeadersVisitor(self.arena))
+    }
+}
+
+/// Deserialises the values of a header, appending them to `entries`.
+struct ValuesIn<'a, 'e, A: 'a> {
+    arena: &'a A,
+    name: &'a [u8],
+    entries: &'e mut Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl<'de, 'a, 'e, A> DeserializeSeed<'de> for ValuesIn<'a, 'e, A>
+    where A: Arena,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+        where D: Deserializer<'de>,
+    {
+        // Like `RawValues`, human-readable formats may also hold a single
+        // value instead of a sequence.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_seq(self)
+        }
+    }
+}
+
+impl<'de, 'a, 'e, A> Visitor<'de> for ValuesIn<'a, 'e, A>
+    where A: Arena,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string, bytes, or an array of strings and sequences of bytes")
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E>
+        where E: de::Error,
+    {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<(), E>
+        where E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<(), E>
+        where E: de::Error,
+    {
+        self.entries.push((self.name, self.arena.alloc_bytes(v)));
+        Ok(())
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<(), V::Error>
+        where V: SeqAccess<'de>,
+    {
+        while let Some(value) = visitor.next_element_seed(BytesIn(self.arena))? {
+            self.entries.push((self.name, value));
+        }
+        Ok(())
+    }
+}
+
+/// Deserialises a string or a sequence of bytes into an arena.
+struct BytesIn<'a, A: 'a>(&'a A);
+
+impl<'de, 'a, A> DeserializeSeed<'de> for BytesIn<'a, A>
+    where A: Arena,
+{
+    type Value = &'a [u8];
+
+    fn deserialize<D>(self, deserializer: D) -> Result<&'a [u8], D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(self)
+    }
+}
+
+impl<'de, 'a, A> Visitor<'de> for BytesIn<'a, A>
+    where A: Arena,
+{
+    type Value = &'a [u8];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string or a sequence of bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where E: de::Error,
+    {
+        Ok(self.0.alloc_bytes(v.as_bytes()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where E: de::Error,
+    {
+        Ok(self.0.alloc_bytes(v))
+    }
+
+    fn visit_seq<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+        where V: SeqAccess<'de>,
+    {
+        let bytes = ByteBuf::deserialize(de::value::SeqAccessDeserializer::new(visitor))?;
+        Ok(self.0.alloc_bytes(&bytes))
+    }
+}
@@ -0,0 +1,106 @@
+//! Validation of header field values.
+
+use http::HeaderValue;
+use serde::de;
+use std::fmt;
+
+/// The number of bytes validated at once.
+///
+/// Chunk validation does not short-circuit, so that it gets vectorised.
+const CHUNK: usize = 16;
+
+/// Returns whether `b` is visible ASCII, a space or a tab.
+#[inline(always)]
+fn is_visible_ascii(b: u8) -> bool {
+    b == b'\t' || (b' '..=b'~').contains(&b)
+}
+
+/// Returns whether `b` is allowed in a header field value.
+///
+/// This matches what `HeaderValue::from_bytes` accepts, i.e. visible ASCII,
+/// spaces, tabs and `obs-text` bytes.
+#[inline(always)]
+fn is_field_byte(b: u8) -> bool {
+    b == b'\t' || (b >= b' ' && b != 0x7f)
+}
+
+/// Returns the index of the first byte of `bytes` not allowed in a header
+/// field value.
+///
+/// Most values are plain visible ASCII, so they are checked a chunk at a
+/// time, and only the chunks failing that check are looked at byte by byte.
+pub(crate) fn invalid_field_byte(bytes: &[u8]) -> Option<usize> {
+    let mut chunks = bytes.chunks_exact(CHUNK);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        if !chunk.iter().fold(true, |ok, &b| ok & is_visible_ascii(b)) {
+            if let Some(i) = chunk.iter().position(|&b| !is_field_byte(b)) {
+                return Some(offset + i);
+            }
+        }
+        offset += CHUNK;
+    }
+    chunks.remainder()
+        .iter()
+        .position(|&b| !is_field_byte(b))
+        .map(|i| offset + i)
+}
+
+/// Checks that `bytes` can be used as a header field value, failing with an
+/// error pointing at the first invalid byte otherwise.
+pub(crate) fn check_field_value<W, E>(what: W, bytes: &[u8]) -> Result<(), E>
+    where W: fmt::Display,
+          E: de::Error,
+{
+    match invalid_field_byte(bytes) {
+        None => Ok(()),
+        Some(i) => Err(E::custom(format_args!(
+            "invalid byte {:#04x} at index {} of {}", bytes[i], i, what))),
+    }
+}
+
+/// Returns `bytes` as a header value, failing like `check_field_value`
+/// otherwise.
+///
+/// `HeaderValue::from_bytes` already validates its input, so `bytes` are
+/// only scanned again to locate the invalid byte when it fails.
+pub(crate) fn parse_field_value<W, E>(what: W, bytes: &[u8]) -> Result<HeaderValue, E>
+    where W: fmt::Display,
+          E: de::Error,
+{
+    HeaderValue::from_bytes(bytes).or_else(|error| {
+        check_field_value(what, bytes)?;
+        Err(E::custom(error))
+    })
+}
+
+/// Parses a quoted string at the start of `s`, returning its unescaped
+/// content and the rest of `s`, or none if it is not terminated.
+pub(crate) fn parse_quoted_string(s: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            _ if escaped => {
+                value.push(c);
+                escaped = false;
+            },
+            '\\' => escaped = true,
+            '"' => return Some((value, &s[i + 1..])),
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+/// Writes `s` as a quoted string, escaping its quotes and backslashes.
+pub(crate) fn write_quoted_string(formatter: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    formatter.write_str("\"")?;
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            formatter.write_str("\\")?;
+        }
+        write!(formatter, "{}", c)?;
+    }
+    formatter.write_str("\"")
+}
@@ -0,0 +1,296 @@
+//! W3C Baggage headers.
+//!
+//! See https://www.w3.org/TR/baggage/.
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::error;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// An error returned when a `baggage` header is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidBaggage {
+    reason: &'static str,
+}
+
+impl InvalidBaggage {
+    fn new(reason: &'static str) -> Self {
+        InvalidBaggage { reason }
+    }
+}
+
+impl fmt::Display for InvalidBaggage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid baggage: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidBaggage {}
+
+/// A parsed `baggage` header.
+///
+/// This is serialised as a sequence of list members, each of them being
+/// serialised as a `[key, value, properties]` tuple, where `properties` is
+/// a sequence of `[key, value]` pairs whose value may be null. Values are
+/// given decoded, without percent-encoding.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Baggage {
+    members: Vec<BaggageMember>,
+    /// The length of the serialised header, kept to check `MAX_LEN` without
+    /// serialising the header on every push.
+    len: usize,
+}
+
+/// A list member of a `baggage` header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BaggageMember {
+    /// The key of the list member.
+    pub key: String,
+    /// The decoded value of the list member.
+    pub value: String,
+    /// The properties of the list member, with their decoded value if any.
+    pub properties: Vec<(String, Option<String>)>,
+}
+
+impl BaggageMember {
+    /// Returns a list member without properties.
+    pub fn new(key: String, value: String) -> Self {
+        BaggageMember {
+            key,
+            value,
+            properties: vec![],
+        }
+    }
+
+    fn validate(self) -> Result<Self, InvalidBaggage> {
+        if !is_token(&self.key) {
+            return Err(InvalidBaggage::new("invalid list member key"));
+        }
+        if !self.properties.iter().all(|(key, _)| is_token(key)) {
+            return Err(InvalidBaggage::new("invalid property key"));
+        }
+        Ok(self)
+    }
+
+    /// Returns the length of the serialised list member.
+    fn encoded_len(&self) -> usize {
+        let properties = self.properties.iter().map(|(key, value)| {
+            1 + key.len() + value.as_ref().map_or(0, |value| 1 + encoded_len(value))
+        });
+        self.key.len() + 1 + encoded_len(&self.value) + properties.sum::<usize>()
+    }
+}
+
+impl Baggage {
+    /// The maximum number of list members.
+    pub const MAX_MEMBERS: usize = 64;
+
+    /// The maximum length of the serialised header, in bytes.
+    pub const MAX_LEN: usize = 8192;
+
+    /// Returns the number of list members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns whether there are no list members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns the list member with the given key.
+    pub fn get(&self, key: &str) -> Option<&BaggageMember> {
+        self.members.iter().find(|member| member.key == key)
+    }
+
+    /// Returns an iterator over the list members.
+    pub fn iter(&self) -> impl Iterator<Item = &BaggageMember> {
+        self.members.iter()
+    }
+
+    /// Adds a list member at the end of the list.
+    ///
+    /// This fails if the key is invalid or if the list would exceed the
+    /// limits of the specification.
+    pub fn push(&mut self, member: BaggageMember) -> Result<(), InvalidBaggage> {
+        let member = member.validate()?;
+        if self.members.len() == Self::MAX_MEMBERS {
+            return Err(InvalidBaggage::new("too many list members"));
+        }
+        // Members after the first one are preceded by a comma.
+        let len = self.len + !self.members.is_empty() as usize + member.encoded_len();
+        if len > Self::MAX_LEN {
+            return Err(InvalidBaggage::new("header is too long"));
+        }
+        self.members.push(member);
+        self.len = len;
+        Ok(())
+    }
+}
+
+/// Returns whether `s` is a `token` as defined by RFC 7230.
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+    })
+}
+
+/// Trims the optional whitespace around `s`.
+fn trim_ows(s: &str) -> &str {
+    s.trim_matches(|c| c == ' ' || c == '\t')
+}
+
+/// Returns whether `b` can be used in a value without being encoded.
+fn is_baggage_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x2b | 0x2d..=0x3a | 0x3c..=0x5b | 0x5d..=0x7e) &&
+        b != b'%'
+}
+
+fn decode_value(value: &str) -> Result<String, InvalidBaggage> {
+    let invalid = || InvalidBaggage::new("invalid value");
+    let digit = |d: Option<&u8>| (*d? as char).to_digit(16);
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.as_bytes().iter();
+    while let Some(&b) = iter.next() {
+        if b == b'%' {
+            let high = digit(iter.next()).ok_or_else(invalid)?;
+            let low = digit(iter.next()).ok_or_else(invalid)?;
+            bytes.push((high << 4 | low) as u8);
+        } else if is_baggage_octet(b) {
+            bytes.push(b);
+        } else {
+            return Err(invalid());
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Returns the length of `value` once percent-encoded.
+fn encoded_len(value: &str) -> usize {
+    value.bytes().map(|b| if is_baggage_octet(b) { 1 } else { 3 }).sum()
+}
+
+struct Encoded<'a>(&'a str);
+
+impl<'a> fmt::Display for Encoded<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.0.bytes() {
+            if is_baggage_octet(b) {
+                formatter.write_char(b as char)?;
+            } else {
+                write!(formatter, "%{:02X}", b)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Baggage {
+    type Err = InvalidBaggage;
+
+    fn from_str(s: &str) -> Result<Self, InvalidBaggage> {
+        if s.len() > Self::MAX_LEN {
+            return Err(InvalidBaggage::new("header is too long"));
+        }
+        let mut baggage = Baggage::default();
+        for member in s.split(',') {
+            let mut parts = member.split(';');
+            let (key, value) = parts
+                .next()
+                .and_then(|pair| pair.split_once('='))
+                .ok_or(InvalidBaggage::new("list member without a value"))?;
+            let mut member = BaggageMember::new(trim_ows(key).to_owned(),
+                                                decode_value(trim_ows(value))?);
+            for property in parts {
+                member.properties.push(match property.split_once('=') {
+                    None => (trim_ows(property).to_owned(), None),
+                    Some((key, value)) => {
+                        (trim_ows(key).to_owned(), Some(decode_value(trim_ows(value))?))
+                    },
+                });
+            }
+            baggage.push(member)?;
+        }
+        Ok(baggage)
+    }
+}
+
+impl fmt::Display for Baggage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, member) in self.members.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(",")?;
+            }
+            write!(formatter, "{}={}", member.key, Encoded(&member.value))?;
+            for (key, value) in &member.properties {
+                write!(formatter, ";{}", key)?;
+                if let Some(ref value) = *value {
+                    write!(formatter, "={}", Encoded(value))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Baggage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct BaggageVisitor;
+
+        impl<'de> Visitor<'de> for BaggageVisitor {
+            type Value = Baggage;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of baggage list members")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Baggage, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0),
+                                        Baggage::MAX_MEMBERS);
+                let mut baggage = Baggage {
+                    members: Vec::with_capacity(capacity),
+                    len: 0,
+                };
+                while let Some(member) = visitor.next_element()? {
+                    baggage.push(member).map_err(de::Error::custom)?;
+                }
+                Ok(baggage)
+            }
+        }
+
+        deserializer.deserialize_seq(BaggageVisitor)
+    }
+}
+
+impl Serialize for Baggage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_seq(&self.members)
+    }
+}
+
+impl<'de> Deserialize<'de> for BaggageMember {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (key, value, properties) = Deserialize::deserialize(deserializer)?;
+        BaggageMember { key, value, properties }
+            .validate()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for BaggageMember {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (&self.key, &self.value, &self.properties).serialize(serializer)
+    }
+}
@@ -0,0 +1,77 @@
+//! The native traits of bincode 2.
+//!
+//! These encode values exactly as the serde implementations do in compact
+//! formats, so data written through `bincode::serde` or bincode 1 can be
+//! read with these traits and vice versa, as long as the same bincode
+//! configuration is used.
+
+use crate::{De, Ser, Serde};
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::serde::Compat;
+use bincode::{BorrowDecode, Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+impl<'a, T> Encode for Ser<'a, T>
+    where Ser<'a, T>: Serialize,
+{
+    fn encode<E>(&self, encoder: &mut E) -> Result<(), EncodeError>
+        where E: Encoder,
+    {
+        Compat(self).encode(encoder)
+    }
+}
+
+impl<T, Context> Decode<Context> for De<T>
+    where for<'de> De<T>: Deserialize<'de>,
+{
+    fn decode<D>(decoder: &mut D) -> Result<Self, DecodeError>
+        where D: Decoder<Context = Context>,
+    {
+        Compat::<De<T>>::decode(decoder).map(|compat| compat.0)
+    }
+}
+
+impl<'de, T, Context> BorrowDecode<'de, Context> for De<T>
+    where for<'a> De<T>: Deserialize<'a>,
+{
+    fn borrow_decode<D>(decoder: &mut D) -> Result<Self, DecodeError>
+        where D: BorrowDecoder<'de, Context = Context>,
+    {
+        Compat::<De<T>>::borrow_decode(decoder).map(|compat| compat.0)
+    }
+}
+
+impl<T> Encode for Serde<T>
+    where for<'de> De<T>: Deserialize<'de>,
+          for<'a> Ser<'a, T>: Serialize,
+{
+    fn encode<E>(&self, encoder: &mut E) -> Result<(), EncodeError>
+        where E: Encoder,
+    {
+        Ser::new(&self.0).encode(encoder)
+    }
+}
+
+impl<T, Context> Decode<Context> for Serde<T>
+    where for<'de> De<T>: Deserialize<'de>,
+          for<'a> Ser<'a, T>: Serialize,
+{
+    fn decode<D>(decoder: &mut D) -> Result<Self, DecodeError>
+        where D: Decoder<Context = Context>,
+    {
+        De::<T>::decode(decoder).map(|de| Serde(de.into_inner()))
+    }
+}
+
+impl<'de, T, Context> BorrowDecode<'de, Context> for Serde<T>
+    where for<'a> De<T>: Deserialize<'a>,
+          for<'a> Ser<'a, T>: Serialize,
+{
+    fn borrow_decode<D>(decoder: &mut D) -> Result<Self, DecodeError>
+        where D: BorrowDecoder<'de, Context = Context>,
+    {
+        De::<T>::borrow_decode(decoder).map(|de| Serde(de.into_inner()))
+    }
+}
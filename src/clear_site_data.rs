@@ -0,0 +1,154 @@
+//! `Clear-Site-Data` headers.
+//!
+//! See https://w3c.github.io/webappsec-clear-site-data/.
+
+use crate::sf::{self, BareItem, Item, Member};
+use hyper::header::HeaderName;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `Clear-Site-Data` header name.
+pub static CLEAR_SITE_DATA: HeaderName = HeaderName::from_static("clear-site-data");
+
+const TYPES: &[&str] = &["cache", "cookies", "storage", "executionContexts", "*"];
+
+/// An error returned when a `Clear-Site-Data` header is invalid.
+#[derive(Clone, Copy, Debug)]
+pub struct InvalidClearSiteData(());
+
+impl fmt::Display for InvalidClearSiteData {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("invalid Clear-Site-Data header")
+    }
+}
+
+impl error::Error for InvalidClearSiteData {}
+
+/// The data types a response asks to clear.
+///
+/// This is serialised as a sequence of the types as named in the header,
+/// e.g. `["cache", "cookies"]`, which is `["*"]` if all of them are set.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ClearSiteData {
+    /// Whether to clear the HTTP cache, `"cache"`.
+    pub cache: bool,
+    /// Whether to clear cookies, `"cookies"`.
+    pub cookies: bool,
+    /// Whether to clear DOM storage, `"storage"`.
+    pub storage: bool,
+    /// Whether to reload the browsing contexts of the origin,
+    /// `"executionContexts"`.
+    pub execution_contexts: bool,
+}
+
+impl ClearSiteData {
+    /// Returns a value clearing all data types, as `"*"` does.
+    pub fn all() -> Self {
+        ClearSiteData {
+            cache: true,
+            cookies: true,
+            storage: true,
+            execution_contexts: true,
+        }
+    }
+
+    /// Returns whether no data type is to be cleared.
+    pub fn is_empty(&self) -> bool {
+        *self == ClearSiteData::default()
+    }
+
+    fn set(&mut self, ty: &str) -> bool {
+        match ty {
+            "cache" => self.cache = true,
+            "cookies" => self.cookies = true,
+            "storage" => self.storage = true,
+            "executionContexts" => self.execution_contexts = true,
+            "*" => *self = ClearSiteData::all(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn types(&self) -> Vec<&'static str> {
+        if *self == ClearSiteData::all() {
+            return vec!["*"];
+        }
+        let flags = [self.cache, self.cookies, self.storage, self.execution_contexts];
+        TYPES.iter().zip(&flags).filter(|&(_, &set)| set).map(|(&ty, _)| ty).collect()
+    }
+}
+
+impl FromStr for ClearSiteData {
+    type Err = InvalidClearSiteData;
+
+    /// Parses a list of quoted types, ignoring the unknown ones as required
+    /// by the specification.
+    fn from_str(s: &str) -> Result<Self, InvalidClearSiteData> {
+        let list = sf::parse_list(s).map_err(|_| InvalidClearSiteData(()))?;
+        let mut data = ClearSiteData::default();
+        for member in &list {
+            if let Member::Item(item) = member {
+                if let Some(ty) = item.bare.as_str() {
+                    data.set(ty);
+                }
+            }
+        }
+        Ok(data)
+    }
+}
+
+impl fmt::Display for ClearSiteData {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let list = self.types().into_iter().map(|ty| {
+            Member::Item(Item::new(BareItem::String(ty.to_owned())))
+        }).collect::<Vec<_>>();
+        sf::List(&list).fmt(formatter)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClearSiteData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct TypesVisitor;
+
+        impl<'de> Visitor<'de> for TypesVisitor {
+            type Value = ClearSiteData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of Clear-Site-Data types")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<ClearSiteData, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let mut data = ClearSiteData::default();
+                while let Some(ty) = visitor.next_element::<String>()? {
+                    if !data.set(&ty) {
+                        return Err(de::Error::unknown_variant(&ty, TYPES));
+                    }
+                }
+                Ok(data)
+            }
+        }
+
+        deserializer.deserialize_seq(TypesVisitor)
+    }
+}
+
+impl Serialize for ClearSiteData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let types = self.types();
+        let mut serializer = serializer.serialize_seq(Some(types.len()))?;
+        for ty in types {
+            serializer.serialize_element(ty)?;
+        }
+        serializer.end()
+    }
+}
@@ -0,0 +1,240 @@
+//! User-Agent Client Hints.
+//!
+//! See https://wicg.github.io/ua-client-hints/.
+
+use crate::sf::{self, BareItem, Item, Member};
+use http::HeaderMap;
+use hyper::header::{HeaderName, HeaderValue};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+
+/// The `Sec-CH-UA` header name.
+pub static SEC_CH_UA: HeaderName = HeaderName::from_static("sec-ch-ua");
+
+/// The `Sec-CH-UA-Full-Version-List` header name.
+pub static SEC_CH_UA_FULL_VERSION_LIST: HeaderName =
+    HeaderName::from_static("sec-ch-ua-full-version-list");
+
+/// The `Sec-CH-UA-Mobile` header name.
+pub static SEC_CH_UA_MOBILE: HeaderName = HeaderName::from_static("sec-ch-ua-mobile");
+
+/// The `Sec-CH-UA-Platform` header name.
+pub static SEC_CH_UA_PLATFORM: HeaderName = HeaderName::from_static("sec-ch-ua-platform");
+
+/// The `Sec-CH-UA-Platform-Version` header name.
+pub static SEC_CH_UA_PLATFORM_VERSION: HeaderName =
+    HeaderName::from_static("sec-ch-ua-platform-version");
+
+/// An error returned when a client hint is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidClientHint {
+    name: &'static HeaderName,
+}
+
+impl InvalidClientHint {
+    /// Returns the name of the invalid client hint.
+    pub fn name(&self) -> &HeaderName {
+        self.name
+    }
+}
+
+impl fmt::Display for InvalidClientHint {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid {} client hint", self.name)
+    }
+}
+
+impl error::Error for InvalidClientHint {}
+
+/// A brand and its version, as listed by `Sec-CH-UA`.
+///
+/// This is serialised as a `[brand, version]` tuple.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Brand {
+    /// The name of the brand, e.g. `Servo`.
+    pub brand: String,
+    /// The version of the brand, which is only the significant version in
+    /// `Sec-CH-UA` and the full version in `Sec-CH-UA-Full-Version-List`.
+    pub version: String,
+}
+
+/// The User-Agent client hints sent by a user agent.
+///
+/// This is serialised as a `[brands, full_version_list, mobile, platform,
+/// platform_version]` tuple, where all but `brands` may be null.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UserAgentHints {
+    /// The brands of `Sec-CH-UA`.
+    pub brands: Vec<Brand>,
+    /// The brands of `Sec-CH-UA-Full-Version-List`.
+    pub full_version_list: Option<Vec<Brand>>,
+    /// Whether the user agent prefers a mobile experience, from
+    /// `Sec-CH-UA-Mobile`.
+    pub mobile: Option<bool>,
+    /// The platform, from `Sec-CH-UA-Platform`.
+    pub platform: Option<String>,
+    /// The platform version, from `Sec-CH-UA-Platform-Version`.
+    pub platform_version: Option<String>,
+}
+
+impl UserAgentHints {
+    /// Returns the client hints found in `headers`.
+    ///
+    /// Missing hints are left empty.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, InvalidClientHint> {
+        Ok(UserAgentHints {
+            brands: parse(headers, &SEC_CH_UA, brands)?.unwrap_or_default(),
+            full_version_list: parse(headers, &SEC_CH_UA_FULL_VERSION_LIST, brands)?,
+            mobile: parse(headers, &SEC_CH_UA_MOBILE, |s| {
+                sf::parse_item(s).ok()?.bare.as_bool()
+            })?,
+            platform: parse(headers, &SEC_CH_UA_PLATFORM, string)?,
+            platform_version: parse(headers, &SEC_CH_UA_PLATFORM_VERSION, string)?,
+        })
+    }
+
+    /// Inserts the client hints in `headers`.
+    ///
+    /// `Sec-CH-UA` is always inserted, other hints only if present. This
+    /// fails if a brand or a string cannot be represented in a header.
+    pub fn insert_into(&self, headers: &mut HeaderMap) -> Result<(), InvalidClientHint> {
+        let mut insert = |name: &'static HeaderName, value: Option<String>| {
+            let value = value.ok_or(InvalidClientHint { name })?;
+            let value = HeaderValue::from_str(&value)
+                .expect("structured fields are visible ASCII");
+            headers.insert(name.clone(), value);
+            Ok(())
+        };
+        insert(&SEC_CH_UA, brands_to_string(&self.brands))?;
+        if let Some(ref list) = self.full_version_list {
+            insert(&SEC_CH_UA_FULL_VERSION_LIST, brands_to_string(list))?;
+        }
+        if let Some(mobile) = self.mobile {
+            insert(&SEC_CH_UA_MOBILE, Some(Item::new(BareItem::Boolean(mobile)).to_string()))?;
+        }
+        if let Some(ref platform) = self.platform {
+            insert(&SEC_CH_UA_PLATFORM, string_to_string(platform))?;
+        }
+        if let Some(ref version) = self.platform_version {
+            insert(&SEC_CH_UA_PLATFORM_VERSION, string_to_string(version))?;
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), InvalidClientHint> {
+        let valid_brands = |brands: &[Brand]| {
+            brands.iter().all(|b| sf::is_string(&b.brand) && sf::is_string(&b.version))
+        };
+        if !valid_brands(&self.brands) {
+            return Err(InvalidClientHint { name: &SEC_CH_UA });
+        }
+        if self.full_version_list.as_deref().is_some_and(|brands| !valid_brands(brands)) {
+            return Err(InvalidClientHint { name: &SEC_CH_UA_FULL_VERSION_LIST });
+        }
+        if self.platform.as_deref().is_some_and(|p| !sf::is_string(p)) {
+            return Err(InvalidClientHint { name: &SEC_CH_UA_PLATFORM });
+        }
+        if self.platform_version.as_deref().is_some_and(|v| !sf::is_string(v)) {
+            return Err(InvalidClientHint { name: &SEC_CH_UA_PLATFORM_VERSION });
+        }
+        Ok(())
+    }
+}
+
+fn parse<T>(headers: &HeaderMap,
+            name: &'static HeaderName,
+            f: impl FnOnce(&str) -> Option<T>)
+            -> Result<Option<T>, InvalidClientHint>
+{
+    headers.get(name).map(|value| {
+        value.to_str().ok().and_then(f).ok_or(InvalidClientHint { name })
+    }).transpose()
+}
+
+fn brands(s: &str) -> Option<Vec<Brand>> {
+    sf::parse_list(s).ok()?.into_iter().map(|member| {
+        let item = match member {
+            Member::Item(item) => item,
+            Member::InnerList(..) => return None,
+        };
+        let version = item.params.iter().find(|(key, _)| key == "v")?.1.as_str()?;
+        Some(Brand {
+            brand: item.bare.as_str()?.to_owned(),
+            version: version.to_owned(),
+        })
+    }).collect()
+}
+
+fn string(s: &str) -> Option<String> {
+    sf::parse_item(s).ok()?.bare.as_str().map(str::to_owned)
+}
+
+fn brands_to_string(brands: &[Brand]) -> Option<String> {
+    let list = brands.iter().map(|b| {
+        if !sf::is_string(&b.brand) || !sf::is_string(&b.version) {
+            return None;
+        }
+        Some(Member::Item(Item {
+            bare: BareItem::String(b.brand.clone()),
+            params: vec![("v".to_owned(), BareItem::String(b.version.clone()))],
+        }))
+    }).collect::<Option<Vec<_>>>()?;
+    Some(sf::List(&list).to_string())
+}
+
+fn string_to_string(s: &str) -> Option<String> {
+    if sf::is_string(s) {
+        Some(BareItem::String(s.to_owned()).to_string())
+    } else {
+        None
+    }
+}
+
+impl<'de> Deserialize<'de> for Brand {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (brand, version) = Deserialize::deserialize(deserializer)?;
+        Ok(Brand { brand, version })
+    }
+}
+
+impl Serialize for Brand {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (&self.brand, &self.version).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UserAgentHints {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (brands, full_version_list, mobile, platform, platform_version) =
+            Deserialize::deserialize(deserializer)?;
+        let hints = UserAgentHints {
+            brands,
+            full_version_list,
+            mobile,
+            platform,
+            platform_version,
+        };
+        hints.validate().map_err(D::Error::custom)?;
+        Ok(hints)
+    }
+}
+
+impl Serialize for UserAgentHints {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (&self.brands,
+         &self.full_version_list,
+         self.mobile,
+         &self.platform,
+         &self.platform_version).serialize(serializer)
+    }
+}
@@ -0,0 +1,103 @@
+//! Cache revalidation requests.
+
+use crate::{De, Ser};
+use headers::{ETag, HeaderMapExt, IfModifiedSince, IfNoneMatch, LastModified};
+use http::HeaderMap;
+use hyper::header;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::SystemTime;
+
+/// The validators of a cached response, along with the conditional headers
+/// sent to revalidate it.
+///
+/// This is serialised like a `HeaderMap` with the `ETag`, `Last-Modified`,
+/// `If-None-Match` and `If-Modified-Since` headers, so that a cache can
+/// persist both what it knows about a response and exactly what it intends
+/// to send.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConditionalRequest {
+    /// The entity tag of the cached response.
+    pub etag: Option<ETag>,
+    /// The modification date of the cached response.
+    pub last_modified: Option<LastModified>,
+    /// The `If-None-Match` header to send.
+    pub if_none_match: Option<IfNoneMatch>,
+    /// The `If-Modified-Since` header to send.
+    pub if_modified_since: Option<IfModifiedSince>,
+}
+
+impl ConditionalRequest {
+    /// Returns the conditional request revalidating a response with the
+    /// given headers.
+    ///
+    /// `If-None-Match` and `If-Modified-Since` are derived from the `ETag`
+    /// and `Last-Modified` headers of the response, if any.
+    pub fn from_response(headers: &HeaderMap) -> Self {
+        let etag = headers.typed_get::<ETag>();
+        let last_modified = headers.typed_get::<LastModified>();
+        ConditionalRequest {
+            if_none_match: etag.clone().map(IfNoneMatch::from),
+            if_modified_since: last_modified
+                .map(|date| IfModifiedSince::from(SystemTime::from(date))),
+            etag,
+            last_modified,
+        }
+    }
+
+    /// Inserts the `If-None-Match` and `If-Modified-Since` headers to send in
+    /// `headers`.
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        if let Some(ref if_none_match) = self.if_none_match {
+            headers.typed_insert(if_none_match.clone());
+        }
+        if let Some(if_modified_since) = self.if_modified_since {
+            headers.typed_insert(if_modified_since);
+        }
+    }
+
+    fn to_header_map(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(ref etag) = self.etag {
+            headers.typed_insert(etag.clone());
+        }
+        if let Some(last_modified) = self.last_modified {
+            headers.typed_insert(last_modified);
+        }
+        self.apply(&mut headers);
+        headers
+    }
+}
+
+impl<'de> Deserialize<'de> for ConditionalRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let headers = De::<HeaderMap>::deserialize(deserializer)?.into_inner();
+        if let Some(name) = headers.keys().find(|&name| {
+            name != header::ETAG && name != header::LAST_MODIFIED &&
+                name != header::IF_NONE_MATCH && name != header::IF_MODIFIED_SINCE
+        }) {
+            return Err(D::Error::custom(format_args!(
+                "unexpected header {:?} in a conditional request", name)));
+        }
+        let invalid = |name| D::Error::custom(format_args!("invalid {:?} header", name));
+        Ok(ConditionalRequest {
+            etag: headers.typed_try_get().map_err(|_| invalid(header::ETAG))?,
+            last_modified: headers.typed_try_get()
+                .map_err(|_| invalid(header::LAST_MODIFIED))?,
+            if_none_match: headers.typed_try_get()
+                .map_err(|_| invalid(header::IF_NONE_MATCH))?,
+            if_modified_since: headers.typed_try_get()
+                .map_err(|_| invalid(header::IF_MODIFIED_SINCE))?,
+        })
+    }
+}
+
+impl Serialize for ConditionalRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        Ser::new(&self.to_header_map()).serialize(serializer)
+    }
+}
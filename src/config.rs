@@ -0,0 +1,496 @@
+//! Configuration of the serialisation and deserialisation of Hyper types.
+
+use crate::extensions::ExtensionRegistry;
+use hyper::header::HeaderName;
+use serde::de;
+use std::cell::Cell;
+use std::fmt;
+use std::sync::Arc;
+
+/// A loss of fidelity noticed while serialising or deserialising a value.
+///
+/// Issues are only reported to the callbacks registered with
+/// `SerConfig::audit` and `DeConfig::audit`, and never make the operation
+/// fail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Issue {
+    /// A header name was lowercased while deserialising a `HeaderMap`, its
+    /// original casing is lost.
+    CasingNormalized(String),
+    /// A header whose values cannot be combined into one, such as
+    /// `Set-Cookie`, had multiple values serialised as a single entry of a
+    /// map, their order relative to other headers is lost.
+    ValuesGrouped(HeaderName),
+}
+
+/// The layout of a serialised `HeaderMap`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum HeaderMapFormat {
+    /// A map from header names to sequences of values.
+    ///
+    /// This is the default layout, and the one expected when deserialising
+    /// unless `DeConfig::header_format` says otherwise. In human-readable
+    /// formats, a single value may be deserialised instead of a sequence,
+    /// e.g. `{"host": "example.org"}`.
+    #[default]
+    Multimap,
+    /// A map from header names to combined values, following the "sort and
+    /// combine" algorithm of the Fetch standard.
+    ///
+    /// Header names are sorted, and the values of each header get joined
+    /// with `", "`. `Set-Cookie` is special-cased: it is repeated once per
+    /// value instead, as its values cannot be combined.
+    ///
+    /// This layout cannot be deserialised.
+    Fetch,
+    /// A sequence of `[name, value]` pairs, one per header value.
+    ///
+    /// Unlike the map layouts, headers with the same name are not merged
+    /// under a single key, and `SerIter` keeps the order of its items even
+    /// when they interleave names, e.g. to replay `Set-Cookie` headers or
+    /// requests going through a proxy. Header names are never sorted.
+    Ordered,
+}
+
+type Audit = Arc<dyn Fn(&Issue) + Send + Sync>;
+
+/// Options controlling how some types get serialised.
+///
+/// Use it through `serialize_with_config` or `Ser::with_config`.
+#[derive(Clone, Default)]
+pub struct SerConfig {
+    pub(crate) pretty: bool,
+    pub(crate) sort_keys: bool,
+    pub(crate) redact: bool,
+    pub(crate) header_format: HeaderMapFormat,
+    pub(crate) base64_fallback: bool,
+    pub(crate) grpc: bool,
+    pub(crate) uri_components: bool,
+    pub(crate) jar_state: bool,
+    pub(crate) directive_maps: bool,
+    pub(crate) extensions: Option<Arc<ExtensionRegistry>>,
+    audit: Option<Audit>,
+}
+
+impl SerConfig {
+    pub(crate) const DEFAULT: SerConfig = SerConfig {
+        pretty: false,
+        sort_keys: false,
+        redact: false,
+        header_format: HeaderMapFormat::Multimap,
+        base64_fallback: false,
+        grpc: false,
+        uri_components: false,
+        jar_state: false,
+        directive_maps: false,
+        extensions: None,
+        audit: None,
+    };
+
+    pub(crate) const PRETTY: SerConfig = SerConfig {
+        pretty: true,
+        ..SerConfig::DEFAULT
+    };
+
+    pub(crate) const ORDERED: SerConfig = SerConfig {
+        header_format: HeaderMapFormat::Ordered,
+        ..SerConfig::DEFAULT
+    };
+
+    /// Returns the default configuration.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether values are serialised in a pretty way.
+    ///
+    /// See `serialize_pretty`.
+    #[inline(always)]
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Sets whether header names are sorted when serialising a `HeaderMap`.
+    ///
+    /// This makes the output deterministic regardless of the insertion order
+    /// of the headers, e.g. to hash or deduplicate serialised header maps.
+    #[inline(always)]
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Sets the layout used when serialising a `HeaderMap`.
+    #[inline(always)]
+    pub fn header_format(mut self, format: HeaderMapFormat) -> Self {
+        self.header_format = format;
+        self
+    }
+
+    /// Sets whether header values which are not UTF-8 text are serialised
+    /// as tagged base64 strings in pretty mode, such as `"base64:6Q=="`,
+    /// instead of sequences of bytes.
+    ///
+    /// Values which start with the `base64:` tag are encoded too, so they
+    /// can be told apart when deserialising with `DeConfig::base64_fallback`.
+    /// This only applies to the values of a `HeaderMap`, in the
+    /// `HeaderMapFormat::Multimap` and `HeaderMapFormat::Ordered` layouts.
+    #[inline(always)]
+    pub fn base64_fallback(mut self, base64_fallback: bool) -> Self {
+        self.base64_fallback = base64_fallback;
+        self
+    }
+
+    /// Sets whether header maps follow the conventions of gRPC metadata.
+    ///
+    /// When enabled, the base64-encoded values of headers whose name ends
+    /// with `-bin` are decoded and serialised as bytes, and the values of the
+    /// other headers are serialised as strings, as if in pretty mode. This
+    /// only applies to the `HeaderMapFormat::Multimap` layout.
+    ///
+    /// Serialisation fails if the value of a `-bin` header is not valid
+    /// base64.
+    #[inline(always)]
+    pub fn grpc_metadata(mut self, grpc: bool) -> Self {
+        self.grpc = grpc;
+        self
+    }
+
+    /// Sets whether a `Uri` is serialised as a map of its components.
+    ///
+    /// When enabled, a `Uri` is serialised as a map with the `scheme`,
+    /// `authority`, `path` and `query` keys, where absent components are
+    /// omitted, instead of as a string. This only applies to human-readable
+    /// formats, and the `De<Uri>` wrapper accepts both forms.
+    #[inline(always)]
+    pub fn uri_components(mut self, uri_components: bool) -> Self {
+        self.uri_components = uri_components;
+        self
+    }
+
+    /// Sets whether a `CookieJar` is serialised with its delta.
+    ///
+    /// When enabled, a `CookieJar` is serialised as an `[original, added,
+    /// removed]` tuple of cookie sequences instead of as the sequence of its
+    /// cookies, so the deserialising side must use `DeConfig::jar_state` too.
+    /// Original cookies replaced or removed by the delta are lost.
+    #[inline(always)]
+    pub fn jar_state(mut self, jar_state: bool) -> Self {
+        self.jar_state = jar_state;
+        self
+    }
+
+    /// Sets whether typed headers made of directives are serialised as maps.
+    ///
+    /// When enabled, `CacheControl` and `StrictTransportSecurity` headers are
+    /// serialised as maps from their directive names to `true` or to their
+    /// values, e.g. `{"max-age": 60, "public": true}`, instead of as
+    /// strings. This only applies to human-readable formats, and the `De`
+    /// wrappers accept both forms.
+    #[inline(always)]
+    pub fn directive_maps(mut self, directive_maps: bool) -> Self {
+        self.directive_maps = directive_maps;
+        self
+    }
+
+    /// Sets the registry of the extensions serialised along requests and
+    /// responses.
+    ///
+    /// When set, `Request`, `Response` and their parts get serialised with
+    /// their recorded extensions as an additional trailing element, so the
+    /// deserialising side must use a `DeConfig` with a registry too.
+    #[inline(always)]
+    pub fn extensions(mut self, registry: Arc<ExtensionRegistry>) -> Self {
+        self.extensions = Some(registry);
+        self
+    }
+
+    /// Sets whether sensitive header values are replaced by `[redacted]`.
+    ///
+    /// Header values are considered sensitive if they are marked as such
+    /// with `HeaderValue::set_sensitive`, or if they are the value of an
+    /// `Authorization`, `Cookie`, `Proxy-Authorization` or `Set-Cookie`
+    /// header.
+    #[inline(always)]
+    pub fn redact(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Registers a callback to be notified of the fidelity issues noticed
+    /// during serialisation.
+    pub fn audit<F>(mut self, audit: F) -> Self
+        where F: Fn(&Issue) + Send + Sync + 'static,
+    {
+        self.audit = Some(Arc::new(audit));
+        self
+    }
+
+    pub(crate) fn report(&self, issue: Issue) {
+        if let Some(ref audit) = self.audit {
+            audit(&issue);
+        }
+    }
+}
+
+impl fmt::Debug for SerConfig {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("SerConfig")
+            .field("pretty", &self.pretty)
+            .field("sort_keys", &self.sort_keys)
+            .field("redact", &self.redact)
+            .field("header_format", &self.header_format)
+            .field("base64_fallback", &self.base64_fallback)
+            .field("grpc_metadata", &self.grpc)
+            .field("uri_components", &self.uri_components)
+            .field("jar_state", &self.jar_state)
+            .field("directive_maps", &self.directive_maps)
+            .field("extensions", &self.extensions)
+            .field("audit", &self.audit.is_some())
+            .finish()
+    }
+}
+
+/// Options controlling how some types get deserialised.
+///
+/// Use it through `deserialize_with_config` or `DeConfig::seed`.
+#[derive(Clone, Default)]
+pub struct DeConfig {
+    pub(crate) header_policy: HeaderPolicy,
+    pub(crate) header_format: HeaderMapFormat,
+    pub(crate) base64_fallback: bool,
+    pub(crate) grpc: bool,
+    pub(crate) jar_state: bool,
+    pub(crate) extensions: Option<Arc<ExtensionRegistry>>,
+    byte_budget: Option<usize>,
+    audit: Option<Audit>,
+}
+
+impl DeConfig {
+    /// Returns the default configuration.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the policy applied to header names when deserialising a
+    /// `HeaderMap`.
+    #[inline(always)]
+    pub fn header_policy(mut self, policy: HeaderPolicy) -> Self {
+        self.header_policy = policy;
+        self
+    }
+
+    /// Sets the layout expected when deserialising a `HeaderMap`.
+    ///
+    /// This is the counterpart of `SerConfig::header_format`, the
+    /// `HeaderMapFormat::Fetch` layout is not supported and makes
+    /// deserialisation fail.
+    #[inline(always)]
+    pub fn header_format(mut self, format: HeaderMapFormat) -> Self {
+        self.header_format = format;
+        self
+    }
+
+    /// Sets whether header values starting with `base64:` are decoded from
+    /// base64 when deserialising a `HeaderMap`.
+    ///
+    /// This is the counterpart of `SerConfig::base64_fallback`.
+    #[inline(always)]
+    pub fn base64_fallback(mut self, base64_fallback: bool) -> Self {
+        self.base64_fallback = base64_fallback;
+        self
+    }
+
+    /// Sets whether header maps follow the conventions of gRPC metadata.
+    ///
+    /// When enabled, the values of headers whose name ends with `-bin` are
+    /// deserialised as binary values, which get base64-encoded. This is the
+    /// counterpart of `SerConfig::grpc_metadata`.
+    #[inline(always)]
+    pub fn grpc_metadata(mut self, grpc: bool) -> Self {
+        self.grpc = grpc;
+        self
+    }
+
+    /// Sets whether a `CookieJar` is deserialised with its delta.
+    ///
+    /// This is the counterpart of `SerConfig::jar_state`, added cookies are
+    /// added with `CookieJar::add` and removal cookies are recorded as if
+    /// their original cookie was removed with `CookieJar::remove`.
+    #[inline(always)]
+    pub fn jar_state(mut self, jar_state: bool) -> Self {
+        self.jar_state = jar_state;
+        self
+    }
+
+    /// Sets the registry of the extensions deserialised along requests and
+    /// responses.
+    ///
+    /// This is the counterpart of `SerConfig::extensions`, recorded values
+    /// are restored in the `Extensions` of the requests, responses or parts.
+    #[inline(always)]
+    pub fn extensions(mut self, registry: Arc<ExtensionRegistry>) -> Self {
+        self.extensions = Some(registry);
+        self
+    }
+
+    /// Sets the maximum number of bytes of header names and values decoded
+    /// when deserialising a `HeaderMap`.
+    ///
+    /// Deserialisation fails as soon as the budget is exceeded, without
+    /// decoding the rest of the input.
+    #[inline(always)]
+    pub fn byte_budget(mut self, limit: usize) -> Self {
+        self.byte_budget = Some(limit);
+        self
+    }
+
+    pub(crate) fn budget(&self) -> Budget {
+        Budget {
+            limit: self.byte_budget,
+            left: Cell::new(self.byte_budget.unwrap_or(usize::MAX)),
+        }
+    }
+
+    /// Registers a callback to be notified of the fidelity issues noticed
+    /// during deserialisation.
+    pub fn audit<F>(mut self, audit: F) -> Self
+        where F: Fn(&Issue) + Send + Sync + 'static,
+    {
+        self.audit = Some(Arc::new(audit));
+        self
+    }
+
+    pub(crate) fn report(&self, issue: Issue) {
+        if let Some(ref audit) = self.audit {
+            audit(&issue);
+        }
+    }
+}
+
+impl fmt::Debug for DeConfig {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("DeConfig")
+            .field("header_policy", &self.header_policy)
+            .field("header_format", &self.header_format)
+            .field("base64_fallback", &self.base64_fallback)
+            .field("grpc_metadata", &self.grpc)
+            .field("jar_state", &self.jar_state)
+            .field("extensions", &self.extensions)
+            .field("byte_budget", &self.byte_budget)
+            .field("audit", &self.audit.is_some())
+            .finish()
+    }
+}
+
+/// The number of bytes left to decode during a deserialisation.
+pub(crate) struct Budget {
+    limit: Option<usize>,
+    left: Cell<usize>,
+}
+
+impl Budget {
+    pub(crate) fn unlimited() -> Self {
+        DeConfig::default().budget()
+    }
+
+    pub(crate) fn spend<E>(&self, len: usize) -> Result<(), E>
+        where E: de::Error,
+    {
+        match self.left.get().checked_sub(len) {
+            Some(left) => {
+                self.left.set(left);
+                Ok(())
+            },
+            None => Err(E::custom(format_args!(
+                "exceeded the byte budget of {} bytes",
+                self.limit.unwrap_or(usize::MAX)))),
+        }
+    }
+}
+
+/// What to do with a header when deserialising a `HeaderMap`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HeaderAction {
+    /// The header is kept.
+    Keep,
+    /// The header is silently dropped.
+    Drop,
+    /// Deserialisation fails with an error.
+    Reject,
+}
+
+/// A set of rules deciding which headers are accepted when deserialising
+/// a `HeaderMap`.
+///
+/// Patterns are matched case-insensitively against header names, and the
+/// first matching rule wins. A pattern ending with `*` matches all the names
+/// starting with what precedes it, e.g. `Proxy-*` matches `proxy-connection`.
+/// Names not matching any rule get the fallback action, which is
+/// `HeaderAction::Keep` unless changed with `otherwise`.
+///
+/// ```
+/// let policy = HeaderPolicy::new()
+///     .rule("Proxy-*", HeaderAction::Drop)
+///     .rule("X-Servo-*", HeaderAction::Reject);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HeaderPolicy {
+    rules: Vec<(String, HeaderAction)>,
+    fallback: HeaderAction,
+}
+
+impl Default for HeaderPolicy {
+    fn default() -> Self {
+        HeaderPolicy {
+            rules: vec![],
+            fallback: HeaderAction::Keep,
+        }
+    }
+}
+
+impl HeaderPolicy {
+    /// Returns a policy keeping all headers.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule applying `action` to the headers matching `pattern`.
+    pub fn rule(mut self, pattern: &str, action: HeaderAction) -> Self {
+        self.rules.push((pattern.to_ascii_lowercase(), action));
+        self
+    }
+
+    /// Sets the action applied to the headers not matching any rule.
+    ///
+    /// For example, `otherwise(HeaderAction::Drop)` turns the rules with
+    /// `HeaderAction::Keep` into an allow list.
+    pub fn otherwise(mut self, action: HeaderAction) -> Self {
+        self.fallback = action;
+        self
+    }
+
+    /// Returns the action applied to the header named `name`.
+    pub fn action(&self, name: &str) -> HeaderAction {
+        let name = name.as_bytes();
+        self.rules
+            .iter()
+            .find(|(pattern, _)| {
+                let pattern = pattern.as_bytes();
+                match pattern.split_last() {
+                    Some((b'*', prefix)) => {
+                        name.len() >= prefix.len() &&
+                            name[..prefix.len()].eq_ignore_ascii_case(prefix)
+                    },
+                    _ => name.eq_ignore_ascii_case(pattern),
+                }
+            })
+            .map_or(self.fallback, |&(_, action)| action)
+    }
+}
@@ -0,0 +1,72 @@
+//! Metadata about the connection a response was received on.
+
+use serde::de;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::net::SocketAddr;
+
+/// The connection a response was received on, to report which address
+/// actually served it.
+///
+/// Its addresses are the ones given by the `remote_addr` and `local_addr`
+/// methods of the `hyper::client::connect::HttpInfo` extension of a response.
+///
+/// This is serialised as a `[remote_addr, local_addr, alpn, is_tls]` tuple,
+/// where `alpn` may be null.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ConnectionInfo {
+    /// The address of the peer.
+    pub remote_addr: SocketAddr,
+    /// The local address of the connection.
+    pub local_addr: SocketAddr,
+    /// The protocol negotiated through ALPN, e.g. `h2` or `http/1.1`.
+    pub alpn: Option<String>,
+    /// Whether the connection is secured with TLS.
+    pub is_tls: bool,
+}
+
+impl ConnectionInfo {
+    /// Returns the metadata of a plain connection between the given
+    /// addresses, without ALPN.
+    pub fn new(remote_addr: SocketAddr, local_addr: SocketAddr) -> Self {
+        ConnectionInfo { remote_addr, local_addr, alpn: None, is_tls: false }
+    }
+}
+
+/// Returns the metadata of the connection described by the `HttpInfo`
+/// extension of a response received by a hyper client.
+///
+/// That extension only has the addresses of the connection, so `alpn` is
+/// `None` and `is_tls` is false, to be set from the TLS connector if any.
+#[cfg(feature = "http-info")]
+impl<'a> From<&'a hyper::client::connect::HttpInfo> for ConnectionInfo {
+    fn from(info: &'a hyper::client::connect::HttpInfo) -> Self {
+        ConnectionInfo::new(info.remote_addr(), info.local_addr())
+    }
+}
+
+/// Returns whether `s` is a valid ALPN protocol identifier, which is limited
+/// to 255 bytes.
+fn is_alpn_protocol(s: &str) -> bool {
+    !s.is_empty() && s.len() <= 255
+}
+
+impl<'de> Deserialize<'de> for ConnectionInfo {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (remote_addr, local_addr, alpn, is_tls) =
+            <(SocketAddr, SocketAddr, Option<String>, bool)>::deserialize(deserializer)?;
+        if alpn.as_deref().is_some_and(|alpn| !is_alpn_protocol(alpn)) {
+            return Err(de::Error::custom("invalid ALPN protocol"));
+        }
+        Ok(ConnectionInfo { remote_addr, local_addr, alpn, is_tls })
+    }
+}
+
+impl Serialize for ConnectionInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (self.remote_addr, self.local_addr, &self.alpn, self.is_tls).serialize(serializer)
+    }
+}
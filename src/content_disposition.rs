@@ -0,0 +1,205 @@
+//! `Content-Disposition` headers.
+//!
+//! See https://httpwg.org/specs/rfc6266.html and, for the encoding of the
+//! `filename*` parameter, https://httpwg.org/specs/rfc5987.html.
+
+use crate::ascii::{invalid_field_byte, parse_quoted_string, write_quoted_string};
+use serde::de;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a `Content-Disposition` header is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidContentDisposition {
+    reason: &'static str,
+}
+
+impl InvalidContentDisposition {
+    fn new(reason: &'static str) -> Self {
+        InvalidContentDisposition { reason }
+    }
+}
+
+impl fmt::Display for InvalidContentDisposition {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid Content-Disposition header: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidContentDisposition {}
+
+/// A parsed `Content-Disposition` header, such as
+/// `attachment; filename="EURO rates"; filename*=UTF-8''%e2%82%ac%20rates`.
+///
+/// Parameters other than `filename` and `filename*` are ignored.
+///
+/// This is serialised as a `[disposition, filename, filename_ext]` tuple,
+/// where `filename` and `filename_ext` may be null.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentDisposition {
+    /// The disposition type, e.g. `inline` or `attachment`, in lowercase.
+    pub disposition: String,
+    /// The `filename` parameter, without its quotes.
+    pub filename: Option<String>,
+    /// The `filename*` parameter, decoded from its charset and percent
+    /// encoding.
+    pub filename_ext: Option<String>,
+}
+
+impl ContentDisposition {
+    /// Returns the file name to use, which is `filename_ext` if set, as
+    /// recipients are required to prefer it, and `filename` otherwise.
+    pub fn file_name(&self) -> Option<&str> {
+        self.filename_ext.as_deref().or(self.filename.as_deref())
+    }
+
+    fn validate(self) -> Result<Self, InvalidContentDisposition> {
+        if !is_token(&self.disposition) {
+            return Err(InvalidContentDisposition::new("invalid disposition type"));
+        }
+        if self.disposition.bytes().any(|b| b.is_ascii_uppercase()) {
+            return Err(InvalidContentDisposition::new("disposition type not in lowercase"));
+        }
+        if self.filename.as_ref().is_some_and(|v| invalid_field_byte(v.as_bytes()).is_some()) {
+            return Err(InvalidContentDisposition::new("invalid filename"));
+        }
+        if self.filename_ext.as_ref().is_some_and(|v| v.chars().any(char::is_control)) {
+            return Err(InvalidContentDisposition::new("invalid filename*"));
+        }
+        Ok(self)
+    }
+}
+
+/// Returns whether `s` is a `token` as defined by RFC 9110.
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(is_tchar)
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// Returns whether `b` may appear unencoded in an extended parameter value.
+fn is_attr_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b)
+}
+
+/// Decodes an extended parameter value, `charset'language'value`, where
+/// `value` is percent-encoded.
+fn decode_ext_value(s: &str) -> Result<String, InvalidContentDisposition> {
+    let invalid = || InvalidContentDisposition::new("invalid filename*");
+    let mut parts = s.splitn(3, '\'');
+    let (charset, value) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(charset), Some(_), Some(value)) => (charset, value),
+        _ => return Err(invalid()),
+    };
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok());
+            let decoded = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            bytes.push(decoded.ok_or_else(invalid)?);
+            rest = &tail[2..];
+        } else if is_attr_char(b) {
+            bytes.push(b);
+            rest = tail;
+        } else {
+            return Err(invalid());
+        }
+    }
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).map_err(|_| invalid())
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Ok(bytes.into_iter().map(char::from).collect())
+    } else {
+        Err(InvalidContentDisposition::new("unsupported filename* charset"))
+    }
+}
+
+impl FromStr for ContentDisposition {
+    type Err = InvalidContentDisposition;
+
+    fn from_str(s: &str) -> Result<Self, InvalidContentDisposition> {
+        let is_ows = |c| c == ' ' || c == '\t';
+        let end = s.find(';').unwrap_or(s.len());
+        let mut disposition = ContentDisposition {
+            disposition: s[..end].trim_matches(is_ows).to_ascii_lowercase(),
+            filename: None,
+            filename_ext: None,
+        };
+        let mut rest = &s[end..];
+        while let Some(param) = rest.strip_prefix(';') {
+            let param = param.trim_start_matches(is_ows);
+            if param.is_empty() {
+                rest = param;
+                break;
+            }
+            let (name, param) = param.split_once('=')
+                .ok_or(InvalidContentDisposition::new("expected a parameter value"))?;
+            let param = param.trim_start_matches(is_ows);
+            let value = if param.starts_with('"') {
+                let (value, tail) = parse_quoted_string(param)
+                    .ok_or(InvalidContentDisposition::new("unterminated quoted string"))?;
+                rest = tail;
+                value
+            } else {
+                let end = param.find(';').unwrap_or(param.len());
+                rest = &param[end..];
+                param[..end].trim_end_matches(is_ows).to_owned()
+            };
+            rest = rest.trim_start_matches(is_ows);
+            match name.trim_end_matches(is_ows).to_ascii_lowercase().as_str() {
+                "filename" => disposition.filename = Some(value),
+                "filename*" => disposition.filename_ext = Some(decode_ext_value(&value)?),
+                _ => {},
+            }
+        }
+        if !rest.is_empty() {
+            return Err(InvalidContentDisposition::new("expected a semicolon"));
+        }
+        disposition.validate()
+    }
+}
+
+impl fmt::Display for ContentDisposition {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.disposition)?;
+        if let Some(ref filename) = self.filename {
+            formatter.write_str("; filename=")?;
+            write_quoted_string(formatter, filename)?;
+        }
+        if let Some(ref filename) = self.filename_ext {
+            formatter.write_str("; filename*=UTF-8''")?;
+            for &b in filename.as_bytes() {
+                if is_attr_char(b) {
+                    write!(formatter, "{}", b as char)?;
+                } else {
+                    write!(formatter, "%{:02X}", b)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentDisposition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (disposition, filename, filename_ext) = Deserialize::deserialize(deserializer)?;
+        ContentDisposition { disposition, filename, filename_ext }
+            .validate()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ContentDisposition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (&self.disposition, &self.filename, &self.filename_ext).serialize(serializer)
+    }
+}
@@ -0,0 +1,228 @@
+//! Cross-origin isolation headers.
+//!
+//! See https://html.spec.whatwg.org/multipage/browsers.html#cross-origin-opener-policies
+//! and https://fetch.spec.whatwg.org/#cross-origin-resource-policy-header.
+
+use crate::sf;
+use http::HeaderMap;
+use hyper::header::{HeaderName, HeaderValue};
+use serde::de;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `Cross-Origin-Opener-Policy` header name.
+pub static CROSS_ORIGIN_OPENER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-opener-policy");
+
+/// The `Cross-Origin-Embedder-Policy` header name.
+pub static CROSS_ORIGIN_EMBEDDER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-embedder-policy");
+
+/// The `Cross-Origin-Resource-Policy` header name.
+pub static CROSS_ORIGIN_RESOURCE_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-resource-policy");
+
+/// An error returned when a cross-origin policy is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidCrossOriginPolicy {
+    name: &'static HeaderName,
+}
+
+impl InvalidCrossOriginPolicy {
+    /// Returns the name of the header holding the invalid policy.
+    pub fn name(&self) -> &HeaderName {
+        self.name
+    }
+}
+
+impl fmt::Display for InvalidCrossOriginPolicy {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid {} header", self.name)
+    }
+}
+
+impl error::Error for InvalidCrossOriginPolicy {}
+
+macro_rules! policy {
+    ($(#[$attr:meta])* $name:ident, $header:ident, {
+        $($(#[$vattr:meta])* $variant:ident => $value:expr,)*
+    }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum $name {
+            $($(#[$vattr])* $variant,)*
+        }
+
+        impl $name {
+            const VALUES: &'static [&'static str] = &[$($value),*];
+
+            /// Returns the value of this policy in a header.
+            pub fn as_str(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => $value,)*
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = InvalidCrossOriginPolicy;
+
+            fn from_str(s: &str) -> Result<Self, InvalidCrossOriginPolicy> {
+                match s {
+                    $($value => Ok($name::$variant),)*
+                    _ => Err(InvalidCrossOriginPolicy { name: &$header }),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                value.parse().map_err(|_| {
+                    de::Error::unknown_variant(&value, $name::VALUES)
+                })
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+    };
+}
+
+policy! {
+    /// A `Cross-Origin-Opener-Policy` value.
+    ///
+    /// This is serialised as its value in a header, e.g. `"same-origin"`.
+    OpenerPolicy, CROSS_ORIGIN_OPENER_POLICY, {
+        /// `unsafe-none`.
+        UnsafeNone => "unsafe-none",
+        /// `same-origin-allow-popups`.
+        SameOriginAllowPopups => "same-origin-allow-popups",
+        /// `same-origin`.
+        SameOrigin => "same-origin",
+        /// `noopener-allow-popups`.
+        NoopenerAllowPopups => "noopener-allow-popups",
+    }
+}
+
+policy! {
+    /// A `Cross-Origin-Embedder-Policy` value.
+    ///
+    /// This is serialised as its value in a header, e.g. `"require-corp"`.
+    EmbedderPolicy, CROSS_ORIGIN_EMBEDDER_POLICY, {
+        /// `unsafe-none`.
+        UnsafeNone => "unsafe-none",
+        /// `require-corp`.
+        RequireCorp => "require-corp",
+        /// `credentialless`.
+        Credentialless => "credentialless",
+    }
+}
+
+policy! {
+    /// A `Cross-Origin-Resource-Policy` value.
+    ///
+    /// This is serialised as its value in a header, e.g. `"same-site"`.
+    ResourcePolicy, CROSS_ORIGIN_RESOURCE_POLICY, {
+        /// `same-site`.
+        SameSite => "same-site",
+        /// `same-origin`.
+        SameOrigin => "same-origin",
+        /// `cross-origin`.
+        CrossOrigin => "cross-origin",
+    }
+}
+
+/// The cross-origin isolation policies of a response.
+///
+/// This is serialised as an `[opener, embedder, resource]` tuple, where
+/// each policy may be null.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CrossOriginIsolation {
+    /// The policy of `Cross-Origin-Opener-Policy`.
+    pub opener: Option<OpenerPolicy>,
+    /// The policy of `Cross-Origin-Embedder-Policy`.
+    pub embedder: Option<EmbedderPolicy>,
+    /// The policy of `Cross-Origin-Resource-Policy`.
+    pub resource: Option<ResourcePolicy>,
+}
+
+impl CrossOriginIsolation {
+    /// Returns the policies found in `headers`.
+    ///
+    /// `Cross-Origin-Opener-Policy` and `Cross-Origin-Embedder-Policy` are
+    /// structured fields whose parameters, such as `report-to`, are ignored.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, InvalidCrossOriginPolicy> {
+        Ok(CrossOriginIsolation {
+            opener: parse_token(headers, &CROSS_ORIGIN_OPENER_POLICY)?,
+            embedder: parse_token(headers, &CROSS_ORIGIN_EMBEDDER_POLICY)?,
+            resource: headers.get(&CROSS_ORIGIN_RESOURCE_POLICY).map(|value| {
+                value.to_str()
+                    .map_err(|_| InvalidCrossOriginPolicy { name: &CROSS_ORIGIN_RESOURCE_POLICY })?
+                    .trim()
+                    .parse()
+            }).transpose()?,
+        })
+    }
+
+    /// Inserts the present policies in `headers`.
+    pub fn insert_into(&self, headers: &mut HeaderMap) {
+        let mut insert = |name: &HeaderName, value: &'static str| {
+            headers.insert(name.clone(), HeaderValue::from_static(value));
+        };
+        if let Some(opener) = self.opener {
+            insert(&CROSS_ORIGIN_OPENER_POLICY, opener.as_str());
+        }
+        if let Some(embedder) = self.embedder {
+            insert(&CROSS_ORIGIN_EMBEDDER_POLICY, embedder.as_str());
+        }
+        if let Some(resource) = self.resource {
+            insert(&CROSS_ORIGIN_RESOURCE_POLICY, resource.as_str());
+        }
+    }
+}
+
+fn parse_token<T>(headers: &HeaderMap,
+                  name: &'static HeaderName)
+                  -> Result<Option<T>, InvalidCrossOriginPolicy>
+    where T: FromStr<Err = InvalidCrossOriginPolicy>,
+{
+    headers.get(name).map(|value| {
+        let invalid = InvalidCrossOriginPolicy { name };
+        let item = sf::parse_item(value.to_str().map_err(|_| invalid.clone())?)
+            .map_err(|_| invalid.clone())?;
+        item.bare.as_token().ok_or(invalid)?.parse()
+    }).transpose()
+}
+
+impl<'de> Deserialize<'de> for CrossOriginIsolation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (opener, embedder, resource) = Deserialize::deserialize(deserializer)?;
+        Ok(CrossOriginIsolation { opener, embedder, resource })
+    }
+}
+
+impl Serialize for CrossOriginIsolation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (self.opener, self.embedder, self.resource).serialize(serializer)
+    }
+}
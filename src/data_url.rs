@@ -0,0 +1,164 @@
+//! `data:` URLs.
+//!
+//! See https://fetch.spec.whatwg.org/#data-urls.
+
+use crate::{De, Ser};
+use base64::Engine;
+use base64::alphabet::STANDARD;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use mime::Mime;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_bytes::{ByteBuf, Bytes};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Data URLs are encoded with padding, but may be decoded without.
+const ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent));
+
+/// The MIME type of data URLs without a valid one.
+const DEFAULT_MIME: &str = "text/plain;charset=US-ASCII";
+
+/// An error returned when a data URL is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidDataUrl {
+    reason: &'static str,
+}
+
+impl InvalidDataUrl {
+    fn new(reason: &'static str) -> Self {
+        InvalidDataUrl { reason }
+    }
+}
+
+impl fmt::Display for InvalidDataUrl {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid data URL: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidDataUrl {}
+
+/// A parsed `data:` URL, such as `data:text/plain;charset=utf-8,caf%C3%A9`.
+///
+/// In human-readable formats, this is serialised as a `data:` URL with a
+/// base64-encoded body. Otherwise, it is serialised as a `[mime, body]`
+/// tuple.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataUrl {
+    /// The MIME type, `text/plain;charset=US-ASCII` if the URL had none.
+    pub mime: Mime,
+    /// The decoded body.
+    pub body: Vec<u8>,
+}
+
+impl DataUrl {
+    /// Returns the value of the `charset` parameter of the MIME type.
+    pub fn charset(&self) -> Option<&str> {
+        self.mime.get_param(mime::CHARSET).map(|charset| charset.as_str())
+    }
+}
+
+/// Percent-decodes `s`, leaving invalid escapes as is.
+fn percent_decode(s: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        // `from_str_radix` accepts a leading sign, so the digits are checked
+        // first to keep e.g. `%+f` as is.
+        let hex = tail.get(..2)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok());
+        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(decoded) if b == b'%' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            },
+            _ => {
+                bytes.push(b);
+                rest = tail;
+            },
+        }
+    }
+    bytes
+}
+
+impl FromStr for DataUrl {
+    type Err = InvalidDataUrl;
+
+    /// Parses a data URL, ignoring its fragment.
+    fn from_str(s: &str) -> Result<Self, InvalidDataUrl> {
+        let s = s.trim_matches(|c: char| c.is_ascii_whitespace());
+        let s = s.split('#').next().unwrap_or(s);
+        let url = s.get(..5)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+            .map(|_| &s[5..])
+            .ok_or(InvalidDataUrl::new("expected a data: scheme"))?;
+        let (mime, body) = url.split_once(',')
+            .ok_or(InvalidDataUrl::new("expected a comma"))?;
+        let mut mime = mime.trim_matches(|c: char| c.is_ascii_whitespace());
+        let mut body = percent_decode(body);
+        let base64 = mime.rsplit_once(';')
+            .filter(|(_, param)| param.trim_start_matches(' ').eq_ignore_ascii_case("base64"));
+        if let Some((essence, _)) = base64 {
+            mime = essence.trim_end_matches(' ');
+            body.retain(|b| !b.is_ascii_whitespace());
+            body = ENGINE.decode(body).map_err(|_| InvalidDataUrl::new("invalid base64 body"))?;
+        }
+        let mime = if mime.starts_with(';') {
+            format!("text/plain{}", mime).parse()
+        } else {
+            mime.parse()
+        };
+        let mime = mime.unwrap_or_else(|_| DEFAULT_MIME.parse().unwrap());
+        Ok(DataUrl { mime, body })
+    }
+}
+
+impl fmt::Display for DataUrl {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "data:{};base64,{}", self.mime, ENGINE.encode(&self.body))
+    }
+}
+
+impl<'de> Deserialize<'de> for DataUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct DataUrlVisitor;
+
+        impl<'de> Visitor<'de> for DataUrlVisitor {
+            type Value = DataUrl;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a data URL")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<DataUrl, E>
+                where E: de::Error,
+            {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(DataUrlVisitor);
+        }
+        let (mime, body) = <(De<Mime>, ByteBuf)>::deserialize(deserializer)?;
+        Ok(DataUrl { mime: mime.into_inner(), body: body.into_vec() })
+    }
+}
+
+impl Serialize for DataUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        (Ser::new(&self.mime), Bytes::new(&self.body)).serialize(serializer)
+    }
+}
@@ -0,0 +1,114 @@
+//! Dates and times of version 0.3 of the `time` crate.
+
+use crate::{De, Ser};
+use serde::de::{self, Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use time03::format_description::well_known::Rfc3339;
+use time03::format_description::{self, FormatItem};
+use time03::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// The format of a `PrimitiveDateTime` in human-readable formats, where the
+/// subsecond is only formatted when not zero.
+const PRIMITIVE: &str = "[year]-[month]-[day]T[hour]:[minute]:[second]";
+const PRIMITIVE_SUBSECOND: &str = "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond]";
+
+fn format_items(format: &str) -> Vec<FormatItem<'_>> {
+    format_description::parse_borrowed::<1>(format).expect("invalid format description")
+}
+
+/// Returns the date and time `secs` seconds and `nanos` nanoseconds after
+/// the Unix epoch, in UTC.
+fn from_unix<E>(secs: i64, nanos: u32) -> Result<OffsetDateTime, E>
+    where E: de::Error,
+{
+    OffsetDateTime::from_unix_timestamp(secs)
+        .and_then(|v| v.replace_nanosecond(nanos))
+        .map_err(E::custom)
+}
+
+struct DateTimeVisitor(&'static str);
+
+impl<'de> Visitor<'de> for DateTimeVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<String, E>
+        where E: de::Error,
+    {
+        Ok(v.to_owned())
+    }
+}
+
+/// In human-readable formats, an `OffsetDateTime` is serialised according to
+/// RFC 3339. Otherwise, it is serialised as a `[timestamp, nanosecond,
+/// offset]` tuple, where `timestamp` is a Unix timestamp and `offset` is the
+/// UTC offset in seconds.
+impl<'de> Deserialize<'de> for De<OffsetDateTime> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let visitor = DateTimeVisitor("a date and time according to RFC 3339");
+            let v = deserializer.deserialize_str(visitor)?;
+            return OffsetDateTime::parse(&v, &Rfc3339).map(De::new).map_err(D::Error::custom);
+        }
+        let (secs, nanos, offset) = <(i64, u32, i32)>::deserialize(deserializer)?;
+        let offset = UtcOffset::from_whole_seconds(offset).map_err(D::Error::custom)?;
+        from_unix(secs, nanos)?
+            .checked_to_offset(offset)
+            .map(De::new)
+            .ok_or_else(|| D::Error::custom("date and time out of range"))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, OffsetDateTime> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let v = self.v.format(&Rfc3339).map_err(serde::ser::Error::custom)?;
+            return serializer.serialize_str(&v);
+        }
+        (self.v.unix_timestamp(), self.v.nanosecond(), self.v.offset().whole_seconds())
+            .serialize(serializer)
+    }
+}
+
+/// In human-readable formats, a `PrimitiveDateTime` is serialised like an
+/// RFC 3339 date and time without offset, e.g. `2017-10-21T07:28:00`.
+/// Otherwise, it is serialised as a `[timestamp, nanosecond]` tuple, where
+/// `timestamp` is the Unix timestamp of the date and time taken as UTC.
+impl<'de> Deserialize<'de> for De<PrimitiveDateTime> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let visitor = DateTimeVisitor("a date and time without offset");
+            let v = deserializer.deserialize_str(visitor)?;
+            let format = if v.contains('.') { PRIMITIVE_SUBSECOND } else { PRIMITIVE };
+            return PrimitiveDateTime::parse(&v, &format_items(format))
+                .map(De::new)
+                .map_err(D::Error::custom);
+        }
+        let (secs, nanos) = <(i64, u32)>::deserialize(deserializer)?;
+        let v = from_unix(secs, nanos)?;
+        Ok(De::new(PrimitiveDateTime::new(v.date(), v.time())))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, PrimitiveDateTime> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let format = if self.v.nanosecond() == 0 { PRIMITIVE } else { PRIMITIVE_SUBSECOND };
+            let v = self.v.format(&format_items(format)).map_err(serde::ser::Error::custom)?;
+            return serializer.serialize_str(&v);
+        }
+        (self.v.assume_utc().unix_timestamp(), self.v.nanosecond()).serialize(serializer)
+    }
+}
@@ -0,0 +1,242 @@
+//! Recording of the known values of `http::Extensions`.
+
+use http::Extensions;
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_bytes::{ByteBuf, Bytes};
+use std::cmp;
+use std::collections::HashSet;
+use std::error;
+use std::fmt;
+
+type Encode = Box<dyn Fn(&Extensions) -> Option<Vec<u8>> + Send + Sync>;
+type Decode = Box<dyn Fn(&[u8], &mut Extensions) -> bool + Send + Sync>;
+
+struct Codec {
+    name: &'static str,
+    encode: Encode,
+    decode: Decode,
+}
+
+/// A set of extension types which can be recorded from `http::Extensions`.
+///
+/// `Extensions` is a type map which cannot be serialised as is, so each
+/// extension type needs to be registered with a unique name and a pair of
+/// functions encoding it to and decoding it from bytes, e.g. with bincode.
+///
+/// ```
+/// let registry = ExtensionRegistry::new()
+///     .register::<RequestId, _, _>(
+///         "request-id",
+///         |id| bincode::serialize(id).unwrap(),
+///         |bytes| bincode::deserialize(bytes).ok());
+/// let recorded = registry.extract(request.extensions());
+/// ```
+#[derive(Default)]
+pub struct ExtensionRegistry {
+    codecs: Vec<Codec>,
+    preserve_unknown: bool,
+}
+
+impl ExtensionRegistry {
+    /// Returns an empty registry.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the extension type `T` under `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another extension type was already registered under `name`.
+    pub fn register<T, E, D>(mut self, name: &'static str, encode: E, decode: D) -> Self
+        where T: Send + Sync + 'static,
+              E: Fn(&T) -> Vec<u8> + Send + Sync + 'static,
+              D: Fn(&[u8]) -> Option<T> + Send + Sync + 'static,
+    {
+        assert!(self.codec(name).is_none(),
+                "extension {:?} is already registered", name);
+        self.codecs.push(Codec {
+            name,
+            encode: Box::new(move |extensions| extensions.get::<T>().map(&encode)),
+            decode: Box::new(move |bytes, extensions| {
+                decode(bytes).map(|value| extensions.insert(value)).is_some()
+            }),
+        });
+        self
+    }
+
+    /// Sets whether recorded values whose name is not registered are
+    /// preserved when restoring them.
+    ///
+    /// When enabled, such values are inserted in `Extensions` as a
+    /// `RecordedExtensions` value, which gets recorded again by `extract`.
+    /// This lets a process forward extensions it does not know about.
+    #[inline(always)]
+    pub fn preserve_unknown(mut self, preserve: bool) -> Self {
+        self.preserve_unknown = preserve;
+        self
+    }
+
+    /// Returns whether an extension type was registered under `name`.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.codec(name).is_some()
+    }
+
+    /// Records the values of the registered extension types found in
+    /// `extensions`.
+    ///
+    /// Unknown values preserved by `restore` are recorded as well.
+    pub fn extract(&self, extensions: &Extensions) -> RecordedExtensions {
+        let mut entries = self.codecs
+            .iter()
+            .filter_map(|codec| {
+                (codec.encode)(extensions).map(|bytes| (codec.name.to_owned(), bytes))
+            })
+            .collect::<Vec<_>>();
+        if let Some(preserved) = extensions.get::<RecordedExtensions>() {
+            entries.extend(preserved.entries.iter().filter(|(name, _)| {
+                !self.is_registered(name)
+            }).cloned());
+        }
+        RecordedExtensions { entries }
+    }
+
+    /// Inserts the recorded values of the registered extension types in
+    /// `extensions`.
+    ///
+    /// Recorded values whose name is not registered are ignored, unless
+    /// `preserve_unknown` is enabled.
+    pub fn restore(&self,
+                   recorded: &RecordedExtensions,
+                   extensions: &mut Extensions)
+                   -> Result<(), InvalidExtension>
+    {
+        let mut unknown = vec![];
+        for (name, bytes) in &recorded.entries {
+            match self.codec(name) {
+                Some(codec) if !(codec.decode)(bytes, extensions) => {
+                    return Err(InvalidExtension { name: name.clone() });
+                },
+                Some(_) => {},
+                None if self.preserve_unknown => unknown.push((name.clone(), bytes.clone())),
+                None => {},
+            }
+        }
+        if !unknown.is_empty() {
+            extensions.insert(RecordedExtensions { entries: unknown });
+        }
+        Ok(())
+    }
+
+    fn codec(&self, name: &str) -> Option<&Codec> {
+        self.codecs.iter().find(|codec| codec.name == name)
+    }
+}
+
+impl fmt::Debug for ExtensionRegistry {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_set()
+            .entries(self.codecs.iter().map(|codec| codec.name))
+            .finish()
+    }
+}
+
+/// The encoded values of some extensions, as recorded by an
+/// `ExtensionRegistry`.
+///
+/// This is serialised as a map from extension names to bytes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RecordedExtensions {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl RecordedExtensions {
+    /// Returns the number of recorded extensions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no extension was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the names and encoded values of the recorded
+    /// extensions.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.entries.iter().map(|(name, bytes)| (&**name, &**bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for RecordedExtensions {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct RecordedVisitor;
+
+        impl<'de> Visitor<'de> for RecordedVisitor {
+            type Value = RecordedExtensions;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a map from extension names to bytes")
+            }
+
+            fn visit_map<V>(self,
+                            mut visitor: V)
+                            -> Result<Self::Value, V::Error>
+                where V: MapAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut entries = Vec::<(String, Vec<u8>)>::with_capacity(capacity);
+                let mut names = HashSet::with_capacity(capacity);
+                while let Some((name, bytes)) = visitor.next_entry::<String, ByteBuf>()? {
+                    if !names.insert(name.clone()) {
+                        return Err(de::Error::custom(format_args!(
+                            "duplicate extension {:?}", name)));
+                    }
+                    entries.push((name, bytes.into_vec()));
+                }
+                Ok(RecordedExtensions { entries })
+            }
+        }
+
+        deserializer.deserialize_map(RecordedVisitor)
+    }
+}
+
+impl Serialize for RecordedExtensions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut serializer = serializer.serialize_map(Some(self.entries.len()))?;
+        for (name, bytes) in &self.entries {
+            serializer.serialize_entry(name, Bytes::new(bytes))?;
+        }
+        serializer.end()
+    }
+}
+
+/// An error returned when a recorded extension value cannot be decoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidExtension {
+    name: String,
+}
+
+impl InvalidExtension {
+    /// Returns the name of the extension which could not be decoded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for InvalidExtension {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid value for extension {:?}", self.name)
+    }
+}
+
+impl error::Error for InvalidExtension {}
@@ -0,0 +1,294 @@
+//! `Forwarded` and `X-Forwarded-For` headers.
+//!
+//! See https://httpwg.org/specs/rfc7239.html.
+
+use crate::ascii::{invalid_field_byte, parse_quoted_string, write_quoted_string};
+use hyper::header::HeaderName;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `X-Forwarded-For` header name.
+pub static X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// An error returned when a `Forwarded` or `X-Forwarded-For` header is
+/// invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidForwarded {
+    reason: &'static str,
+}
+
+impl InvalidForwarded {
+    fn new(reason: &'static str) -> Self {
+        InvalidForwarded { reason }
+    }
+}
+
+impl fmt::Display for InvalidForwarded {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid Forwarded header: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidForwarded {}
+
+/// A parsed `Forwarded` header, listing the proxies a request went through.
+///
+/// Parameters other than `for`, `by`, `host` and `proto` are ignored.
+///
+/// This is serialised as a sequence of elements, each of them being
+/// serialised as a `[for, by, host, proto]` tuple whose members may be null.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Forwarded {
+    elements: Vec<ForwardedElement>,
+}
+
+/// An element of a `Forwarded` header, describing a single proxy hop.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ForwardedElement {
+    /// The node making the request to the proxy, the `for` parameter, e.g.
+    /// `192.0.2.60` or `"[2001:db8:cafe::17]:4711"` without the quotes.
+    pub forwarded_for: Option<String>,
+    /// The interface where the request came into the proxy, the `by`
+    /// parameter.
+    pub by: Option<String>,
+    /// The `Host` header of the request as received by the proxy.
+    pub host: Option<String>,
+    /// The protocol used to make the request, e.g. `https`.
+    pub proto: Option<String>,
+}
+
+impl ForwardedElement {
+    fn params(&self) -> [(&'static str, &Option<String>); 4] {
+        [
+            ("for", &self.forwarded_for),
+            ("by", &self.by),
+            ("host", &self.host),
+            ("proto", &self.proto),
+        ]
+    }
+
+    fn validate(self) -> Result<Self, InvalidForwarded> {
+        if self.params().iter().all(|(_, value)| value.is_none()) {
+            return Err(InvalidForwarded::new("empty element"));
+        }
+        let is_value = |v: &String| !v.is_empty() && invalid_field_byte(v.as_bytes()).is_none();
+        if self.params().iter().any(|(_, value)| value.as_ref().is_some_and(|v| !is_value(v))) {
+            return Err(InvalidForwarded::new("invalid parameter value"));
+        }
+        Ok(self)
+    }
+}
+
+impl Forwarded {
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns an iterator over the elements, from the first proxy the
+    /// request went through to the last one.
+    pub fn iter(&self) -> impl Iterator<Item = &ForwardedElement> {
+        self.elements.iter()
+    }
+
+    /// Adds an element at the end of the list.
+    pub fn push(&mut self, element: ForwardedElement) -> Result<(), InvalidForwarded> {
+        self.elements.push(element.validate()?);
+        Ok(())
+    }
+
+    /// Parses an `X-Forwarded-For` header, such as `192.0.2.43, 2001:db8::17`,
+    /// as elements with only a `for` parameter.
+    ///
+    /// IPv6 addresses are put in brackets, as required in `Forwarded`.
+    pub fn from_x_forwarded_for(s: &str) -> Result<Self, InvalidForwarded> {
+        let mut forwarded = Forwarded::default();
+        for node in s.split(',').map(trim_ows).filter(|node| !node.is_empty()) {
+            let node = if node.contains(':') && !node.starts_with('[') {
+                format!("[{}]", node)
+            } else {
+                node.to_owned()
+            };
+            forwarded.push(ForwardedElement {
+                forwarded_for: Some(node),
+                ..ForwardedElement::default()
+            })?;
+        }
+        Ok(forwarded)
+    }
+
+    /// Returns the `X-Forwarded-For` header listing the `for` parameters of
+    /// the elements, skipping the elements without one.
+    ///
+    /// Brackets are removed from IPv6 addresses without a port.
+    pub fn to_x_forwarded_for(&self) -> String {
+        let nodes = self.elements.iter().filter_map(|element| {
+            let node = element.forwarded_for.as_deref()?;
+            Some(node.strip_prefix('[').and_then(|n| n.strip_suffix(']')).unwrap_or(node))
+        });
+        nodes.collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Returns whether `s` is a `token` as defined by RFC 9110.
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+    })
+}
+
+/// Trims the optional whitespace around `s`.
+fn trim_ows(s: &str) -> &str {
+    s.trim_matches(|c| c == ' ' || c == '\t')
+}
+
+impl FromStr for Forwarded {
+    type Err = InvalidForwarded;
+
+    /// Parses a list of elements, ignoring empty elements and unknown
+    /// parameters.
+    fn from_str(s: &str) -> Result<Self, InvalidForwarded> {
+        let is_ows = |c| c == ' ' || c == '\t';
+        let mut forwarded = Forwarded::default();
+        let mut element = ForwardedElement::default();
+        let mut rest = s.trim_start_matches(is_ows);
+        while !rest.is_empty() {
+            if let Some(tail) = rest.strip_prefix(',') {
+                if element != ForwardedElement::default() {
+                    forwarded.push(element)?;
+                }
+                element = ForwardedElement::default();
+                rest = tail.trim_start_matches(is_ows);
+                continue;
+            }
+            if let Some(tail) = rest.strip_prefix(';') {
+                rest = tail.trim_start_matches(is_ows);
+                continue;
+            }
+            let (name, tail) = rest.split_once('=')
+                .ok_or(InvalidForwarded::new("expected a parameter value"))?;
+            let value = if tail.starts_with('"') {
+                let (value, tail) = parse_quoted_string(tail)
+                    .ok_or(InvalidForwarded::new("unterminated quoted string"))?;
+                rest = tail;
+                value
+            } else {
+                let end = tail.find(|c| c == ';' || c == ',' || is_ows(c)).unwrap_or(tail.len());
+                if !is_token(&tail[..end]) {
+                    return Err(InvalidForwarded::new("invalid parameter value"));
+                }
+                rest = &tail[end..];
+                tail[..end].to_owned()
+            };
+            let param = match name.to_ascii_lowercase().as_str() {
+                "for" => Some(&mut element.forwarded_for),
+                "by" => Some(&mut element.by),
+                "host" => Some(&mut element.host),
+                "proto" => Some(&mut element.proto),
+                _ if is_token(name) => None,
+                _ => return Err(InvalidForwarded::new("invalid parameter name")),
+            };
+            if param.is_some_and(|param| param.replace(value).is_some()) {
+                return Err(InvalidForwarded::new("duplicate parameter"));
+            }
+            rest = rest.trim_start_matches(is_ows);
+            if !rest.is_empty() && !rest.starts_with([';', ',']) {
+                return Err(InvalidForwarded::new("expected a semicolon or a comma"));
+            }
+        }
+        if element != ForwardedElement::default() {
+            forwarded.push(element)?;
+        }
+        Ok(forwarded)
+    }
+}
+
+impl fmt::Display for Forwarded {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, element) in self.elements.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            let params = element.params();
+            let params = params.iter().filter_map(|&(name, value)| Some((name, value.as_ref()?)));
+            for (j, (name, value)) in params.enumerate() {
+                if j > 0 {
+                    formatter.write_str(";")?;
+                }
+                write!(formatter, "{}=", name)?;
+                if is_token(value) {
+                    formatter.write_str(value)?;
+                } else {
+                    write_quoted_string(formatter, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Forwarded {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct ForwardedVisitor;
+
+        impl<'de> Visitor<'de> for ForwardedVisitor {
+            type Value = Forwarded;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of Forwarded elements")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Forwarded, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut elements = Vec::with_capacity(capacity);
+                while let Some(element) = visitor.next_element()? {
+                    elements.push(element);
+                }
+                Ok(Forwarded { elements })
+            }
+        }
+
+        deserializer.deserialize_seq(ForwardedVisitor)
+    }
+}
+
+impl Serialize for Forwarded {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_seq(&self.elements)
+    }
+}
+
+impl<'de> Deserialize<'de> for ForwardedElement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (forwarded_for, by, host, proto) = Deserialize::deserialize(deserializer)?;
+        ForwardedElement { forwarded_for, by, host, proto }
+            .validate()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ForwardedElement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (&self.forwarded_for, &self.by, &self.host, &self.proto).serialize(serializer)
+    }
+}
@@ -0,0 +1,31 @@
+//! gRPC metadata conventions.
+//!
+//! gRPC metadata is carried in HTTP/2 headers, where the values of keys
+//! ending with `-bin` are base64-encoded binary values, and the values of
+//! other keys are ASCII strings.
+
+use base64::Engine;
+use base64::alphabet::STANDARD;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+
+/// gRPC implementations emit unpadded base64, but must accept padded values.
+const ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent));
+
+/// Returns whether the values of the metadata key `name` are binary.
+pub(crate) fn is_binary_key(name: &str) -> bool {
+    name.len() > 4 && name.as_bytes()[name.len() - 4..].eq_ignore_ascii_case(b"-bin")
+}
+
+/// Decodes the value of a binary metadata key.
+pub(crate) fn decode(value: &[u8]) -> Option<Vec<u8>> {
+    ENGINE.decode(value).ok()
+}
+
+/// Encodes the value of a binary metadata key.
+pub(crate) fn encode(value: &[u8]) -> String {
+    ENGINE.encode(value)
+}
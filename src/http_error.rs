@@ -0,0 +1,305 @@
+//! Errors of `hyper` and `http`.
+
+use crate::{De, Ser};
+use http::header::{InvalidHeaderName, InvalidHeaderValue};
+use http::method::InvalidMethod;
+use http::status::InvalidStatusCode;
+use http::uri::{InvalidUri, InvalidUriParts};
+use hyper::Uri;
+use serde::de;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+
+macro_rules! kind {
+    ($(#[$attr:meta])* $name:ident {
+        $($(#[$vattr:meta])* $variant:ident => $value:expr,)*
+    }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum $name {
+            $($(#[$vattr])* $variant,)*
+        }
+
+        impl $name {
+            const VALUES: &'static [&'static str] = &[$($value),*];
+
+            /// Returns the name of this kind, as serialised.
+            pub fn as_str(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => $value,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                match &*value {
+                    $($value => Ok($name::$variant),)*
+                    _ => Err(de::Error::unknown_variant(&value, $name::VALUES)),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+    };
+}
+
+kind! {
+    /// The kind of a `hyper::Error`.
+    ///
+    /// This is serialised as a string, e.g. `"incomplete-message"`.
+    HyperErrorKind {
+        /// An invalid message was received, see `hyper::Error::is_parse`.
+        Parse => "parse",
+        /// The user misused `hyper`, see `hyper::Error::is_user`.
+        User => "user",
+        /// A pending request was canceled.
+        Canceled => "canceled",
+        /// A sender or receiver was dropped.
+        Closed => "closed",
+        /// A connection could not be established.
+        Connect => "connect",
+        /// The connection closed before a message could complete.
+        IncompleteMessage => "incomplete-message",
+        /// Writing a body was aborted.
+        BodyWriteAborted => "body-write-aborted",
+        /// A timeout elapsed.
+        Timeout => "timeout",
+        /// Any other error.
+        Other => "other",
+    }
+}
+
+kind! {
+    /// The kind of an `http::Error`, named after the invalid part.
+    ///
+    /// This is serialised as a string, e.g. `"header-value"`.
+    HttpErrorKind {
+        /// An invalid `StatusCode`.
+        StatusCode => "status-code",
+        /// An invalid `Method`.
+        Method => "method",
+        /// An invalid `Uri`.
+        Uri => "uri",
+        /// Invalid `uri::Parts`.
+        UriParts => "uri-parts",
+        /// An invalid `HeaderName`.
+        HeaderName => "header-name",
+        /// An invalid `HeaderValue`.
+        HeaderValue => "header-value",
+        /// Any other error.
+        Other => "other",
+    }
+}
+
+/// An error of `hyper` or `http` that can be sent to another process.
+///
+/// Its message is the `Display` output of the original error, and its URI,
+/// not provided by the original errors, may be set with `with_uri`.
+///
+/// This is serialised as a `[source, kind, message, uri]` tuple, where
+/// `source` is either `"hyper"` or `"http"` and `uri` may be null.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SerializableHttpError {
+    /// An error returned by `hyper`.
+    Hyper {
+        /// The kind of the error.
+        kind: HyperErrorKind,
+        /// The message of the error.
+        message: String,
+        /// The URI of the request that failed, if known.
+        uri: Option<Uri>,
+    },
+    /// An error returned by `http`.
+    Http {
+        /// The kind of the error.
+        kind: HttpErrorKind,
+        /// The message of the error.
+        message: String,
+        /// The URI of the request that failed, if known.
+        uri: Option<Uri>,
+    },
+}
+
+impl SerializableHttpError {
+    /// Returns the message of the error.
+    pub fn message(&self) -> &str {
+        match *self {
+            SerializableHttpError::Hyper { ref message, .. } |
+            SerializableHttpError::Http { ref message, .. } => message,
+        }
+    }
+
+    /// Returns the URI of the request that failed, if known.
+    pub fn uri(&self) -> Option<&Uri> {
+        match *self {
+            SerializableHttpError::Hyper { ref uri, .. } |
+            SerializableHttpError::Http { ref uri, .. } => uri.as_ref(),
+        }
+    }
+
+    /// Sets the URI of the request that failed.
+    pub fn with_uri(mut self, new_uri: Uri) -> Self {
+        match self {
+            SerializableHttpError::Hyper { ref mut uri, .. } |
+            SerializableHttpError::Http { ref mut uri, .. } => *uri = Some(new_uri),
+        }
+        self
+    }
+}
+
+impl fmt::Display for SerializableHttpError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.message())?;
+        if let Some(uri) = self.uri() {
+            write!(formatter, " ({})", uri)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for SerializableHttpError {}
+
+impl From<hyper::Error> for SerializableHttpError {
+    fn from(error: hyper::Error) -> Self {
+        // Checked from the most specific kind to the least specific one, as a
+        // body write abortion is also a user error.
+        let kind = if error.is_body_write_aborted() {
+            HyperErrorKind::BodyWriteAborted
+        } else if error.is_timeout() {
+            HyperErrorKind::Timeout
+        } else if error.is_parse() {
+            HyperErrorKind::Parse
+        } else if error.is_user() {
+            HyperErrorKind::User
+        } else if error.is_canceled() {
+            HyperErrorKind::Canceled
+        } else if error.is_closed() {
+            HyperErrorKind::Closed
+        } else if error.is_connect() {
+            HyperErrorKind::Connect
+        } else if error.is_incomplete_message() {
+            HyperErrorKind::IncompleteMessage
+        } else {
+            HyperErrorKind::Other
+        };
+        SerializableHttpError::Hyper { kind, message: error.to_string(), uri: None }
+    }
+}
+
+impl From<http::Error> for SerializableHttpError {
+    fn from(error: http::Error) -> Self {
+        let kind = if error.is::<InvalidStatusCode>() {
+            HttpErrorKind::StatusCode
+        } else if error.is::<InvalidMethod>() {
+            HttpErrorKind::Method
+        } else if error.is::<InvalidUri>() {
+            HttpErrorKind::Uri
+        } else if error.is::<InvalidUriParts>() {
+            HttpErrorKind::UriParts
+        } else if error.is::<InvalidHeaderName>() {
+            HttpErrorKind::HeaderName
+        } else if error.is::<InvalidHeaderValue>() {
+            HttpErrorKind::HeaderValue
+        } else {
+            HttpErrorKind::Other
+        };
+        SerializableHttpError::Http { kind, message: error.to_string(), uri: None }
+    }
+}
+
+macro_rules! from_http_errors {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for SerializableHttpError {
+                fn from(error: $error) -> Self {
+                    http::Error::from(error).into()
+                }
+            }
+        )*
+    };
+}
+
+from_http_errors!(
+    InvalidStatusCode,
+    InvalidMethod,
+    InvalidUri,
+    InvalidUriParts,
+    InvalidHeaderName,
+    InvalidHeaderValue
+);
+
+impl<'de> Deserialize<'de> for SerializableHttpError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct ErrorVisitor;
+
+        impl<'de> de::Visitor<'de> for ErrorVisitor {
+            type Value = SerializableHttpError;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a [source, kind, message, uri] tuple")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: de::SeqAccess<'de>,
+            {
+                let missing = |i| de::Error::invalid_length(i, &self);
+                let source = visitor.next_element::<String>()?.ok_or_else(|| missing(0))?;
+                let error = match &*source {
+                    "hyper" => SerializableHttpError::Hyper {
+                        kind: visitor.next_element()?.ok_or_else(|| missing(1))?,
+                        message: visitor.next_element()?.ok_or_else(|| missing(2))?,
+                        uri: None,
+                    },
+                    "http" => SerializableHttpError::Http {
+                        kind: visitor.next_element()?.ok_or_else(|| missing(1))?,
+                        message: visitor.next_element()?.ok_or_else(|| missing(2))?,
+                        uri: None,
+                    },
+                    _ => return Err(de::Error::unknown_variant(&source, &["hyper", "http"])),
+                };
+                let uri = visitor.next_element::<Option<De<Uri>>>()?.ok_or_else(|| missing(3))?;
+                Ok(match uri {
+                    Some(uri) => error.with_uri(uri.into_inner()),
+                    None => error,
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple(4, ErrorVisitor)
+    }
+}
+
+impl Serialize for SerializableHttpError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let uri = self.uri().map(Ser::new);
+        match *self {
+            SerializableHttpError::Hyper { kind, ref message, .. } => {
+                ("hyper", kind, message, uri).serialize(serializer)
+            },
+            SerializableHttpError::Http { kind, ref message, .. } => {
+                ("http", kind, message, uri).serialize(serializer)
+            },
+        }
+    }
+}
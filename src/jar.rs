@@ -0,0 +1,94 @@
+//! Changes made to a `CookieJar`.
+
+use crate::{De, Ser};
+use cookie::{Cookie, CookieJar};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Splits the cookies of `jar` into its original cookies, its added cookies
+/// and its removal cookies.
+///
+/// Original cookies replaced or removed by the delta are not accessible
+/// anymore, so they are not returned.
+pub(crate) fn jar_state(jar: &CookieJar) -> [Vec<&Cookie<'static>>; 3] {
+    let (added, removed) = jar.delta().partition::<Vec<_>, _>(|c| jar.get(c.name()).is_some());
+    let original = jar.iter()
+        .filter(|c| !added.iter().any(|a| a.name() == c.name()))
+        .collect();
+    [original, added, removed]
+}
+
+/// The delta of a `CookieJar`, i.e. the cookies added and removed since its
+/// original cookies were set.
+///
+/// This is serialised as an `[added, removed]` tuple of cookie sequences,
+/// where removed cookies are removal cookies, so that only the changes made
+/// to a jar need to be sent to another process.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JarDelta {
+    added: Vec<Cookie<'static>>,
+    removed: Vec<Cookie<'static>>,
+}
+
+impl JarDelta {
+    /// Returns the delta of `jar`.
+    pub fn new(jar: &CookieJar) -> Self {
+        let [_, added, removed] = jar_state(jar);
+        JarDelta {
+            added: added.into_iter().cloned().collect(),
+            removed: removed.into_iter().cloned().collect(),
+        }
+    }
+
+    /// Returns whether no cookie was added nor removed.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns the added cookies.
+    #[inline(always)]
+    pub fn added(&self) -> &[Cookie<'static>] {
+        &self.added
+    }
+
+    /// Returns the removal cookies of the removed cookies.
+    #[inline(always)]
+    pub fn removed(&self) -> &[Cookie<'static>] {
+        &self.removed
+    }
+
+    /// Applies this delta to `jar`, through `CookieJar::add` and
+    /// `CookieJar::remove`.
+    pub fn apply(self, jar: &mut CookieJar) {
+        for cookie in self.added {
+            jar.add(cookie);
+        }
+        for cookie in self.removed {
+            jar.remove(cookie);
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JarDelta {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        type Cookies = Vec<De<Cookie<'static>>>;
+
+        let (added, removed) = <(Cookies, Cookies)>::deserialize(deserializer)?;
+        Ok(JarDelta {
+            added: added.into_iter().map(De::into_inner).collect(),
+            removed: removed.into_iter().map(De::into_inner).collect(),
+        })
+    }
+}
+
+impl Serialize for JarDelta {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let added = self.added.iter().map(Ser::new).collect::<Vec<_>>();
+        let removed = self.removed.iter().map(Ser::new).collect::<Vec<_>>();
+        (added, removed).serialize(serializer)
+    }
+}
@@ -0,0 +1,88 @@
+//! Conversions between Hyper types and `serde_json::Value`.
+
+use crate::{De, Ser};
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde::Serialize;
+use serde_json::{Error, Map, Value};
+
+/// Deserialises a `T` value from a `serde_json::Value`.
+///
+/// This reads the value directly, without going through its JSON text.
+/// A top-level `Value::Null` is read as an empty map, so that a missing
+/// `HeaderMap` can be represented by `null`.
+pub fn from_value<T>(value: Value) -> Result<T, Error>
+    where for<'de> De<T>: Deserialize<'de>,
+{
+    De::deserialize(NullAsEmpty(value)).map(De::into_inner)
+}
+
+/// Serialises `value` as a `serde_json::Value`.
+///
+/// This builds the value directly, without going through its JSON text.
+pub fn to_value<T>(value: &T) -> Result<Value, Error>
+    where for<'a> Ser<'a, T>: Serialize,
+{
+    serde_json::to_value(Ser::new(value))
+}
+
+struct NullAsEmpty(Value);
+
+macro_rules! forward_to_value {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Error>
+                where V: Visitor<'de>,
+            {
+                self.0.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for NullAsEmpty {
+    type Error = Error;
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+        where V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Null => Value::Object(Map::new()).deserialize_map(visitor),
+            value => value.deserialize_map(visitor),
+        }
+    }
+
+    forward_to_value! {
+        deserialize_any()
+        deserialize_bool()
+        deserialize_i8()
+        deserialize_i16()
+        deserialize_i32()
+        deserialize_i64()
+        deserialize_u8()
+        deserialize_u16()
+        deserialize_u32()
+        deserialize_u64()
+        deserialize_f32()
+        deserialize_f64()
+        deserialize_char()
+        deserialize_str()
+        deserialize_string()
+        deserialize_bytes()
+        deserialize_byte_buf()
+        deserialize_option()
+        deserialize_unit()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_newtype_struct(name: &'static str)
+        deserialize_seq()
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_struct(name: &'static str, fields: &'static [&'static str])
+        deserialize_enum(name: &'static str, variants: &'static [&'static str])
+        deserialize_identifier()
+    }
+
+    forward_to_deserialize_any! {
+        ignored_any
+    }
+}
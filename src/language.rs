@@ -0,0 +1,57 @@
+//! Language tags of the `language-tags` crate.
+//!
+//! See https://www.rfc-editor.org/rfc/rfc5646.
+
+use crate::{AcceptLanguage, De, QualityItem, Ser};
+use language_tags::LanguageTag;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+impl AcceptLanguage {
+    /// Returns the language ranges which are well-formed language tags, with
+    /// their quality weights, in the order of the header.
+    ///
+    /// The wildcard range `*` is skipped, as well as the ranges which are
+    /// not valid tags, such as `en-a`.
+    pub fn language_tags(&self) -> Vec<QualityItem<LanguageTag>> {
+        self.iter()
+            .filter_map(|item| {
+                let tag = LanguageTag::parse(&item.item).ok()?;
+                Some(QualityItem { item: tag, quality: item.quality })
+            })
+            .collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for De<LanguageTag> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct LanguageTagVisitor;
+
+        impl<'de> Visitor<'de> for LanguageTagVisitor {
+            type Value = De<LanguageTag>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a language tag")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                LanguageTag::parse(v).map(De::new).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(LanguageTagVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, LanguageTag> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
@@ -3,14 +3,86 @@
 //!
 //! The supported types are:
 //!
+//! * `bytes::Bytes`
 //! * `cookie::Cookie`
+//! * `cookie::CookieJar`
+//! * `cookie::Expiration`
+//! * `cookie::SameSite`
+//! * `headers::AcceptRanges`
+//! * `headers::AccessControlAllowCredentials`
+//! * `headers::AccessControlAllowHeaders`
+//! * `headers::AccessControlAllowMethods`
+//! * `headers::AccessControlAllowOrigin`
+//! * `headers::AccessControlExposeHeaders`
+//! * `headers::AccessControlMaxAge`
+//! * `headers::AccessControlRequestHeaders`
+//! * `headers::AccessControlRequestMethod`
+//! * `headers::Age`
+//! * `headers::Allow`
+//! * `headers::Authorization<Basic>`
+//! * `headers::Authorization<Bearer>`
+//! * `headers::CacheControl`
+//! * `headers::Connection`
+//! * `headers::ContentEncoding`
+//! * `headers::ContentLength`
+//! * `headers::ContentLocation`
+//! * `headers::ContentRange`
+//! * `headers::Cookie`
+//! * `headers::Date`
+//! * `headers::ETag`
+//! * `headers::Expect`
+//! * `headers::Expires`
+//! * `headers::Host`
+//! * `headers::IfMatch`
+//! * `headers::IfModifiedSince`
+//! * `headers::IfNoneMatch`
+//! * `headers::IfUnmodifiedSince`
+//! * `headers::LastModified`
+//! * `headers::Location`
+//! * `headers::Origin`
+//! * `headers::Pragma`
+//! * `headers::ProxyAuthorization<Basic>`
+//! * `headers::ProxyAuthorization<Bearer>`
+//! * `headers::Range`
+//! * `headers::Referer`
+//! * `headers::RetryAfter`
+//! * `headers::SecWebsocketAccept`
+//! * `headers::SecWebsocketKey`
+//! * `headers::SecWebsocketVersion`
+//! * `headers::Server`
+//! * `headers::SetCookie`
+//! * `headers::StrictTransportSecurity`
+//! * `headers::Te`
+//! * `headers::TransferEncoding`
+//! * `headers::Upgrade`
+//! * `headers::UserAgent`
+//! * `headers::Vary`
 //! * `headers_ext::ContentType`
+//! * `hyper::header::HeaderName`
+//! * `hyper::header::HeaderValue`
 //! * `hyper::header::Headers`
 //! * `hyper::StatusCode`
 //! * `hyper::Method`
+//! * `hyper::http::Request<B>`, for serialisable bodies
+//! * `hyper::http::request::Parts`
+//! * `hyper::http::Response<B>`, for serialisable bodies
+//! * `hyper::http::response::Parts`
 //! * `hyper::Uri`
+//! * `hyper::http::uri::Authority`
+//! * `hyper::http::uri::Parts`
+//! * `hyper::http::uri::PathAndQuery`
+//! * `hyper::http::uri::Scheme`
+//! * `hyper::ext::ReasonPhrase`, with the `http1` feature
+//! * `hyper::Version`
+//! * `language_tags::LanguageTag`, with the `language-tags` feature
 //! * `mime::Mime`
+//! * `mime::Name`, for serialisation only
+//! * `std::time::SystemTime`
 //! * `time::Tm`
+//! * `time::Duration` of `time` 0.3, as re-exported by `cookie`
+//! * `time::OffsetDateTime` and `time::PrimitiveDateTime` of `time` 0.3, with
+//!   the `time03` feature
+//! * `url::Url`, with the `url` feature
 //!
 //! # How do I use a data type with a `HeaderMap` member with Serde?
 //!
@@ -49,29 +121,191 @@
 //! ipc::channel::<Serde<Cookie>>()
 //! ```
 //!
+//! # How do I serialise a typed header without its own implementation?
+//!
+//! Use the `TypedHeaderSerde` wrapper, which works with any type implementing
+//! `headers::Header`, such as `ReferrerPolicy` or third-party headers.
+//!
+//! ```
+//! serde_json::to_string(&TypedHeaderSerde(ReferrerPolicy::NO_REFERRER))
+//! ```
+//!
+//! # How do I record traffic made with reqwest?
+//!
+//! reqwest 0.11 re-exports the `http` types this crate supports, so its
+//! `HeaderMap`, `StatusCode` and `Method` values can be used with `Ser` and
+//! `De` directly. With the `reqwest` feature, `reqwest_response_parts` and
+//! `reqwest_response_cookies` take the metadata and cookies of its responses
+//! apart, and `into_reqwest_response` and `add_to_reqwest_jar` feed them
+//! back to reqwest.
+//!
+//! ```
+//! let response = reqwest::get("https://servo.org").await?;
+//! let parts = hyper_serde::reqwest_response_parts(&response);
+//! let cookies = hyper_serde::reqwest_response_cookies(&response);
+//! serde_json::to_string(&Ser::new(&parts))
+//! ```
+//!
+//! # How do I convert between `url::Url` and `Uri` values?
+//!
+//! Enable the `url` feature, with which `Url` values can be used with `Ser`
+//! and `De`, and use `uri_from_url` and `url_from_uri`. These go through
+//! `UriWithFragment`, as `Uri` values do not keep the fragment of a `Url`.
+//! Converting a `Uri` fails if it is not absolute.
+//!
+//! ```
+//! let uri = hyper_serde::uri_from_url(&url)?.into_uri();
+//! let url = hyper_serde::url_from_uri(&UriWithFragment::from(uri))?;
+//! ```
+//!
+//! # How do I dump a `HeaderMap` value in a bug report?
+//!
+//! Enable the `serde_json` feature and use `pretty_json`, which redacts
+//! credentials and cookies.
+//!
+//! ```
+//! println!("{}", hyper_serde::pretty_json(&headers)?)
+//! ```
+//!
+//! # How do I encode values with bincode 2?
+//!
+//! Enable the `bincode` feature, with which `Ser` implements `Encode`, and
+//! `De` and `Serde` implement `Decode` and `BorrowDecode`. These produce the
+//! same bytes as bincode's serde compatibility layer, so with the legacy
+//! configuration, data written by bincode 1 can be read by bincode 2 and
+//! vice versa.
+//!
+//! ```
+//! let config = bincode::config::legacy();
+//! let bytes = bincode::encode_to_vec(Ser::new(&headers), config)?;
+//! let (headers, _) = bincode::decode_from_slice::<De<HeaderMap>, _>(&bytes, config)?;
+//! ```
+//!
 //!
 
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
-use cookie::Cookie;
+mod accept;
+mod arena;
+mod ascii;
+mod baggage;
+#[cfg(feature = "bincode")]
+mod bincode2;
+mod clear_site_data;
+mod client_hints;
+mod conditional;
+mod config;
+mod connection;
+mod content_disposition;
+mod cross_origin;
+mod data_url;
+#[cfg(feature = "time03")]
+mod datetime;
+mod extensions;
+mod forwarded;
+mod grpc;
+mod http_error;
+mod jar;
+#[cfg(feature = "language-tags")]
+mod language;
+#[cfg(feature = "serde_json")]
+mod json;
+mod names;
+mod parts;
+mod permissions_policy;
+mod push;
+mod ratelimit;
+mod raw;
+#[cfg(feature = "reqwest")]
+mod recording;
+mod sf;
+mod text;
+mod trace;
+mod typed;
+mod uri_fragment;
+#[cfg(feature = "url")]
+mod url_interop;
+mod via;
+mod websocket;
+
+pub use crate::accept::{Accept, AcceptLanguage, InvalidAccept, QualityItem};
+pub use crate::arena::{Arena, ArenaHeaders, InArena};
+pub use crate::baggage::{Baggage, BaggageMember, InvalidBaggage};
+pub use crate::clear_site_data::{CLEAR_SITE_DATA, ClearSiteData, InvalidClearSiteData};
+pub use crate::client_hints::{Brand, InvalidClientHint, UserAgentHints};
+pub use crate::client_hints::{SEC_CH_UA, SEC_CH_UA_FULL_VERSION_LIST, SEC_CH_UA_MOBILE};
+pub use crate::client_hints::{SEC_CH_UA_PLATFORM, SEC_CH_UA_PLATFORM_VERSION};
+pub use crate::conditional::ConditionalRequest;
+pub use crate::config::{DeConfig, HeaderAction, HeaderMapFormat, HeaderPolicy};
+pub use crate::config::{Issue, SerConfig};
+pub use crate::connection::ConnectionInfo;
+pub use crate::content_disposition::{ContentDisposition, InvalidContentDisposition};
+pub use crate::cross_origin::{CrossOriginIsolation, EmbedderPolicy, InvalidCrossOriginPolicy};
+pub use crate::cross_origin::{OpenerPolicy, ResourcePolicy};
+pub use crate::cross_origin::{CROSS_ORIGIN_EMBEDDER_POLICY, CROSS_ORIGIN_OPENER_POLICY};
+pub use crate::cross_origin::CROSS_ORIGIN_RESOURCE_POLICY;
+pub use crate::data_url::{DataUrl, InvalidDataUrl};
+pub use crate::extensions::{ExtensionRegistry, InvalidExtension, RecordedExtensions};
+pub use crate::forwarded::{Forwarded, ForwardedElement, InvalidForwarded, X_FORWARDED_FOR};
+pub use crate::http_error::{HttpErrorKind, HyperErrorKind, SerializableHttpError};
+pub use crate::jar::JarDelta;
+#[cfg(feature = "serde_json")]
+pub use crate::json::{from_value, to_value};
+pub use crate::names::{STANDARD_HEADERS, standard_header_name};
+pub use crate::permissions_policy::{AllowlistEntry, InvalidPermissionsPolicy};
+pub use crate::permissions_policy::PermissionsPolicy;
+pub use crate::push::{PushPromise, PushedResponse};
+pub use crate::ratelimit::{InvalidRateLimit, ParseRateLimitError, RateLimit};
+pub use crate::ratelimit::{RATELIMIT, RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET};
+pub use crate::raw::RawHeaders;
+#[cfg(feature = "reqwest")]
+pub use crate::recording::{add_to_reqwest_jar, cookie_from_reqwest, into_reqwest_response};
+#[cfg(feature = "reqwest")]
+pub use crate::recording::{reqwest_response_cookies, reqwest_response_parts};
+pub use crate::text::{InvalidStartLine, RequestLine, StatusLine};
+pub use crate::trace::{InvalidTraceContext, TraceParent, TraceState};
+pub use crate::typed::TypedHeaderSerde;
+pub use crate::uri_fragment::{InvalidUriWithFragment, UriWithFragment};
+#[cfg(feature = "url")]
+pub use crate::url_interop::{uri_from_url, url_from_uri};
+pub use crate::via::{InvalidVia, Via, ViaEntry};
+pub use crate::websocket::{InvalidSecWebsocketProtocol, SecWebsocketProtocol};
+
+use crate::ascii::{check_field_value, parse_field_value};
+use crate::config::Budget;
+use crate::jar::jar_state;
+use crate::names::parse_header_name;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use cookie::{Cookie, CookieJar, Expiration, SameSite};
+use cookie::time::{Duration as TimeDuration, OffsetDateTime};
+use cookie::time::format_description::well_known::Rfc3339;
 use headers::ContentType;
 use hyper::StatusCode;
-use hyper::header::{HeaderName, HeaderValue};
+use hyper::header::{self, HeaderName, HeaderValue};
 use http::HeaderMap;
+use http::header::GetAll;
 use hyper::Method;
 use mime::Mime;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_bytes::{ByteBuf, Bytes};
-use serde::de::{self, MapAccess, SeqAccess, Visitor, Error};
+use smallvec::SmallVec;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor, Error};
 use serde::ser::{SerializeMap, SerializeSeq};
+use std::borrow::{Borrow, Cow};
+use std::cell::RefCell;
 use std::cmp;
+use std::convert::TryFrom;
 use std::fmt;
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use std::str;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use time::{Tm, strptime};
-use hyper::Uri;
+use hyper::{Uri, Version};
+use http::uri::{self, Authority, PathAndQuery, Scheme};
 
 /// Deserialises a `T` value with a given deserializer.
 ///
@@ -102,9 +336,11 @@ pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
 /// This does the same job as `serialize` but with a prettier format
 /// for some combinations of types and serialisers.
 ///
-/// For now, the only change from `serialize` is when serialising `Headers`,
+/// For now, the changes from `serialize` are when serialising `Headers`,
 /// where the items in the header values get serialised as strings instead
-/// of sequences of bytes, if they represent UTF-8 text.
+/// of sequences of bytes, if they represent UTF-8 text, and when serialising
+/// `StatusCode` with a human-readable serialiser, where it gets serialised
+/// as a string with its canonical reason phrase, such as `"200 OK"`.
 #[inline(always)]
 pub fn serialize_pretty<T, S>(value: &T,
                               serializer: S)
@@ -115,6 +351,39 @@ pub fn serialize_pretty<T, S>(value: &T,
     Ser::new_pretty(value).serialize(serializer)
 }
 
+/// Serialises `value` with a given serializer and configuration.
+///
+/// This is the configurable counterpart of `serialize` and
+/// `serialize_pretty`, see `SerConfig` for the available options.
+#[inline(always)]
+pub fn serialize_with_config<T, S>(value: &T,
+                                   serializer: S,
+                                   config: &SerConfig)
+                                   -> Result<S::Ok, S::Error>
+    where S: Serializer,
+          for<'a> Ser<'a, T>: Serialize,
+{
+    Ser::with_config(value, config).serialize(serializer)
+}
+
+/// Serialises `value` as pretty-printed JSON, for human consumption.
+///
+/// This uses pretty mode, sorts header names, and redacts the values of
+/// sensitive headers such as `Authorization` and `Cookie`, so that the output
+/// can be pasted in bug reports.
+///
+/// # Errors
+///
+/// Fails if `value` cannot be serialised, e.g. a `SystemTime` before the
+/// Unix epoch.
+#[cfg(feature = "serde_json")]
+pub fn pretty_json<T>(value: &T) -> serde_json::Result<String>
+    where for<'a> Ser<'a, T>: Serialize,
+{
+    let config = SerConfig::new().pretty(true).sort_keys(true).redact(true);
+    serde_json::to_string_pretty(&Ser::with_config(value, &config))
+}
+
 /// A wrapper to deserialize Hyper types.
 ///
 /// This is useful with functions such as `serde_json::from_str`.
@@ -130,7 +399,7 @@ impl<T> De<T> {
     /// Returns a new `De` wrapper
     #[inline(always)]
     pub fn new(v: T) -> Self {
-        De { v: v }
+        De { v }
     }
 }
 
@@ -152,7 +421,7 @@ impl<'de, T> De<T>
 #[derive(Debug)]
 pub struct Ser<'a, T: 'a> {
     v: &'a T,
-    pretty: bool,
+    config: &'a SerConfig,
 }
 
 impl<'a, T> Ser<'a, T>
@@ -161,10 +430,7 @@ impl<'a, T> Ser<'a, T>
     /// Returns a new `Ser` wrapper.
     #[inline(always)]
     pub fn new(value: &'a T) -> Self {
-        Ser {
-            v: value,
-            pretty: false,
-        }
+        Ser::with_config(value, &SerConfig::DEFAULT)
     }
 
     /// Returns a new `Ser` wrapper, in pretty mode.
@@ -172,9 +438,66 @@ impl<'a, T> Ser<'a, T>
     /// See `serialize_pretty`.
     #[inline(always)]
     pub fn new_pretty(value: &'a T) -> Self {
+        Ser::with_config(value, &SerConfig::PRETTY)
+    }
+
+    /// Returns a new `Ser` wrapper, serialising header maps as ordered
+    /// sequences of names and values.
+    ///
+    /// See `HeaderMapFormat::Ordered`.
+    #[inline(always)]
+    pub fn new_ordered(value: &'a T) -> Self {
+        Ser::with_config(value, &SerConfig::ORDERED)
+    }
+
+    /// Returns a new `Ser` wrapper, with the given configuration.
+    ///
+    /// See `serialize_with_config`.
+    #[inline(always)]
+    pub fn with_config(value: &'a T, config: &'a SerConfig) -> Self {
         Ser {
             v: value,
-            pretty: true,
+            config,
+        }
+    }
+}
+
+/// Deserialises a `T` value with a given deserializer and configuration.
+///
+/// This is the configurable counterpart of `deserialize`, for the types
+/// whose deserialisation can be tweaked through `DeConfig`.
+#[inline(always)]
+pub fn deserialize_with_config<'de, T, D>(deserializer: D,
+                                          config: &DeConfig)
+                                          -> Result<T, D::Error>
+    where D: Deserializer<'de>,
+          for<'c> DeWith<'c, T>: DeserializeSeed<'de, Value = T>,
+{
+    config.seed().deserialize(deserializer)
+}
+
+/// A seed to deserialize Hyper types according to a `DeConfig`.
+///
+/// Values of this type are obtained through `DeConfig::seed` and can only
+/// be passed to the `serde::de::DeserializeSeed` trait.
+pub struct DeWith<'c, T> {
+    config: &'c DeConfig,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'c, T> fmt::Debug for DeWith<'c, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("DeWith").field("config", self.config).finish()
+    }
+}
+
+impl DeConfig {
+    /// Returns a seed deserialising `T` values with this configuration.
+    #[inline(always)]
+    pub fn seed<T>(&self) -> DeWith<'_, T> {
+        DeWith {
+            config: self,
+            marker: PhantomData,
         }
     }
 }
@@ -258,6 +581,76 @@ impl<T> Serialize for Serde<T>
     }
 }
 
+/// A convenience wrapper for types converting to and from bytes, such as
+/// custom binary header values.
+///
+/// In human-readable formats, the bytes are serialised as a string if they
+/// represent UTF-8 text, and as a sequence of bytes otherwise. Other formats
+/// always get a sequence of bytes. Both forms are accepted when deserialising.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BytesSerde<T>(pub T);
+
+impl<T> BytesSerde<T> {
+    /// Consumes this wrapper, returning the inner value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for BytesSerde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for BytesSerde<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for BytesSerde<T>
+    where T: TryFrom<Vec<u8>>,
+          T::Error: fmt::Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let bytes = ByteBuf::deserialize(deserializer)?.into_vec();
+        T::try_from(bytes).map(BytesSerde).map_err(D::Error::custom)
+    }
+}
+
+impl<T> Serialize for BytesSerde<T>
+    where T: AsRef<[u8]>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        StrOrBytes(self.0.as_ref()).serialize(serializer)
+    }
+}
+
+/// Serialises bytes as a string in human-readable formats if they represent
+/// UTF-8 text, and as a sequence of bytes otherwise.
+struct StrOrBytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for StrOrBytes<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            if let Ok(v) = str::from_utf8(self.0) {
+                return serializer.serialize_str(v);
+            }
+        }
+        serializer.serialize_bytes(self.0)
+    }
+}
+
 impl<'de> Deserialize<'de> for De<ContentType> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>,
@@ -270,7 +663,52 @@ impl<'a> Serialize for Ser<'a, ContentType> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        serialize(&mime::Mime::from(self.v.clone()), serializer)
+        serializer.collect_str(self.v)
+    }
+}
+
+/// `Bytes` are serialised as base64 strings in human-readable formats, and
+/// as bytes otherwise.
+impl<'de> Deserialize<'de> for De<bytes::Bytes> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct Base64Visitor;
+
+        impl<'de> Visitor<'de> for Base64Visitor {
+            type Value = De<bytes::Bytes>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a base64 string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                BASE64_STANDARD.decode(v)
+                    .map(|bytes| De::new(bytes.into()))
+                    .map_err(|e| E::custom(format_args!("invalid base64: {}", e)))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Base64Visitor)
+        } else {
+            let bytes = ByteBuf::deserialize(deserializer)?;
+            Ok(De::new(bytes.into_vec().into()))
+        }
+    }
+}
+
+impl<'a> Serialize for Ser<'a, bytes::Bytes> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&BASE64_STANDARD.encode(self.v))
+        } else {
+            serializer.serialize_bytes(self.v)
+        }
     }
 }
 
@@ -290,6 +728,7 @@ impl<'de> Deserialize<'de> for De<Cookie<'static>> {
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
                 where E: de::Error,
             {
+                check_field_value("a cookie", v.as_bytes())?;
                 Cookie::parse(v)
                     .map(Cookie::into_owned)
                     .map(De::new)
@@ -305,229 +744,1518 @@ impl<'a, 'cookie> Serialize for Ser<'a, Cookie<'cookie>> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        serializer.serialize_str(&self.v.to_string())
+        serializer.collect_str(self.v)
     }
 }
 
+const SAME_SITE_VARIANTS: &[&str] = &["Strict", "Lax", "None"];
 
-impl<'de> Deserialize<'de> for De<HeaderMap> {
+/// `SameSite` values are serialised as `Strict`, `Lax` or `None`, and they
+/// are deserialised case-insensitively, like the attribute of a cookie.
+impl<'de> Deserialize<'de> for De<SameSite> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>,
     {
-        struct HeadersVisitor;
+        struct SameSiteVisitor;
 
-        impl<'de> Visitor<'de> for HeadersVisitor {
-            type Value = De<HeaderMap>;
+        impl<'de> Visitor<'de> for SameSiteVisitor {
+            type Value = De<SameSite>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(formatter, "a map from header names to header values")
+                write!(formatter, "a SameSite cookie attribute")
             }
 
-            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
                 where E: de::Error,
             {
-                Ok(De::new(HeaderMap::new()))
-            }
-
-            fn visit_map<V>(self,
-                            mut visitor: V)
-                            -> Result<Self::Value, V::Error>
-                where V: MapAccess<'de>,
-            {
-                let mut headers = HeaderMap::new();
-                while let Some((k, values)) = visitor.next_entry::<String, Value>()? {
-                    for v in values.0.iter() {
-                        headers.append(HeaderName::from_str(&k).map_err(V::Error::custom)?, HeaderValue::from_bytes(&v).map_err(V::Error::custom)?);
-                    }
-                }
-                Ok(De::new(headers))
+                let same_site = if v.eq_ignore_ascii_case("strict") {
+                    SameSite::Strict
+                } else if v.eq_ignore_ascii_case("lax") {
+                    SameSite::Lax
+                } else if v.eq_ignore_ascii_case("none") {
+                    SameSite::None
+                } else {
+                    return Err(E::unknown_variant(v, SAME_SITE_VARIANTS));
+                };
+                Ok(De::new(same_site))
             }
         }
 
-        struct Value(Vec<Vec<u8>>);
+        deserializer.deserialize_str(SameSiteVisitor)
+    }
+}
 
-        impl<'de> Deserialize<'de> for Value {
-            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-                where D: Deserializer<'de>,
-            {
-                deserializer.deserialize_seq(ValueVisitor)
-            }
-        }
+impl<'a> Serialize for Ser<'a, SameSite> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_str(self.v)
+    }
+}
 
-        struct ValueVisitor;
+/// `Expiration` values are serialised as an option, which is none for
+/// session cookies. In human-readable formats, the date and time is
+/// serialised according to RFC 3339, otherwise it is serialised as a Unix
+/// timestamp, truncated to the second.
+impl<'de> Deserialize<'de> for De<Expiration> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct ExpirationVisitor;
 
-        impl<'de> Visitor<'de> for ValueVisitor {
-            type Value = Value;
+        impl<'de> Visitor<'de> for ExpirationVisitor {
+            type Value = De<Expiration>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(formatter, "an array of strings and sequences of bytes")
+                write!(formatter, "a cookie expiration")
             }
 
-            fn visit_unit<E>(self) -> Result<Value, E>
+            fn visit_none<E>(self) -> Result<Self::Value, E>
                 where E: de::Error,
             {
-                Ok(Value(vec![]))
+                Ok(De::new(Expiration::Session))
             }
 
-            fn visit_seq<V>(self, mut visitor: V) -> Result<Value, V::Error>
-                where V: SeqAccess<'de>,
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+                where E: de::Error,
             {
-                // Clamp to not OOM on rogue values.
-                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
-                let mut values = Vec::with_capacity(capacity);
-                while let Some(v) = visitor.next_element::<ByteBuf>()? {
-                    values.push(v.into_vec());
-                }
-                Ok(Value(values))
+                Ok(De::new(Expiration::Session))
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                where D: Deserializer<'de>,
+            {
+                let datetime = if deserializer.is_human_readable() {
+                    let v = String::deserialize(deserializer)?;
+                    OffsetDateTime::parse(&v, &Rfc3339).map_err(D::Error::custom)?
+                } else {
+                    let v = i64::deserialize(deserializer)?;
+                    OffsetDateTime::from_unix_timestamp(v).map_err(D::Error::custom)?
+                };
+                Ok(De::new(Expiration::DateTime(datetime)))
             }
         }
 
-        deserializer.deserialize_map(HeadersVisitor)
+        deserializer.deserialize_option(ExpirationVisitor)
     }
 }
 
-impl<'a> Serialize for Ser<'a, HeaderMap> {
+impl<'a> Serialize for Ser<'a, Expiration> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        struct Value<'headers>(&'headers [Vec<u8>], bool);
-
-        impl<'headers> Serialize for Value<'headers> {
-            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-                where S: Serializer,
-            {
-                let mut serializer =
-                    serializer.serialize_seq(Some(self.0.len()))?;
-                for v in self.0 {
-                    if self.1 {
-                        if let Ok(v) = str::from_utf8(v) {
-                            serializer.serialize_element(v)?;
-                            continue;
-                        }
-                    }
-                    serializer.serialize_element(&Bytes::new(v))?;
-                }
-                serializer.end()
-            }
-        }
-
-        let mut serializer = serializer.serialize_map(Some(self.v.keys_len()))?;
-        for name in self.v.keys() {
-            let values = self.v.get_all(name);
-            serializer.serialize_entry(name.as_str(), &Value(&values.iter().map(|v| v.as_bytes().iter().cloned().collect()).collect::<Vec<Vec<u8>>>(), self.pretty))?;
+        let datetime = match *self.v {
+            Expiration::Session => return serializer.serialize_none(),
+            Expiration::DateTime(datetime) => datetime,
+        };
+        if serializer.is_human_readable() {
+            let v = datetime.format(&Rfc3339).map_err(serde::ser::Error::custom)?;
+            serializer.serialize_some(&v)
+        } else {
+            serializer.serialize_some(&datetime.unix_timestamp())
         }
-        serializer.end()
     }
 }
 
-impl<'de> Deserialize<'de> for De<Method> {
+/// `time::Duration` values, such as the `Max-Age` of a cookie, are
+/// serialised as a number of seconds.
+///
+/// The fractional part of the second is dropped, rounding towards zero.
+/// Negative durations are serialised as negative numbers, like a `Max-Age`
+/// attribute which expires the cookie immediately, and numbers which do not
+/// fit in an `i64` are rejected when deserialising.
+impl<'de> Deserialize<'de> for De<TimeDuration> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>,
     {
-        struct MethodVisitor;
+        struct DurationVisitor;
 
-        impl<'de> Visitor<'de> for MethodVisitor {
-            type Value = De<Method>;
+        impl<'de> Visitor<'de> for DurationVisitor {
+            type Value = De<TimeDuration>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(formatter, "an HTTP method")
+                write!(formatter, "a number of seconds")
             }
 
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
                 where E: de::Error,
             {
-                v.parse::<Method>().map(De::new).map_err(E::custom)
+                Ok(De::new(TimeDuration::seconds(v)))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                let v = i64::try_from(v).map_err(|_| {
+                    E::custom(format_args!("duration of {} seconds out of range", v))
+                })?;
+                self.visit_i64(v)
             }
         }
 
-        deserializer.deserialize_string(MethodVisitor)
+        deserializer.deserialize_i64(DurationVisitor)
     }
 }
 
-impl<'a> Serialize for Ser<'a, Method> {
+impl<'a> Serialize for Ser<'a, TimeDuration> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        Serialize::serialize(self.v.as_ref(), serializer)
+        serializer.serialize_i64(self.v.whole_seconds())
     }
 }
 
-impl<'de> Deserialize<'de> for De<Mime> {
+/// A jar is serialised as the sequence of its cookies, which all become
+/// original cookies when deserialised. With `SerConfig::jar_state`, it is
+/// serialised as an `[original, added, removed]` tuple of cookie sequences
+/// instead, so that its delta survives a round trip.
+impl<'de> Deserialize<'de> for De<CookieJar> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>,
     {
-        struct MimeVisitor;
+        DeConfig::default().seed::<CookieJar>().deserialize(deserializer).map(De::new)
+    }
+}
 
-        impl<'de> Visitor<'de> for MimeVisitor {
-            type Value = De<Mime>;
+impl<'de, 'c> DeserializeSeed<'de> for DeWith<'c, CookieJar> {
+    type Value = CookieJar;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(formatter, "a mime type")
-            }
+    fn deserialize<D>(self, deserializer: D) -> Result<CookieJar, D::Error>
+        where D: Deserializer<'de>,
+    {
+        type Cookies = Vec<Serde<Cookie<'static>>>;
 
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-                where E: de::Error,
-            {
-                v.parse::<Mime>().map(De::new).map_err(|_| {
-                    E::custom("could not parse mime type")
-                })
+        let mut jar = CookieJar::new();
+        if !self.config.jar_state {
+            for cookie in Cookies::deserialize(deserializer)? {
+                jar.add_original(cookie.into_inner());
             }
+            return Ok(jar);
         }
-
-        deserializer.deserialize_string(MimeVisitor)
+        let (original, added, removed) = <(Cookies, Cookies, Cookies)>::deserialize(deserializer)?;
+        for cookie in original {
+            jar.add_original(cookie.into_inner());
+        }
+        for cookie in added {
+            jar.add(cookie.into_inner());
+        }
+        for cookie in removed {
+            // A removal cookie is only recorded for an original cookie.
+            let cookie = cookie.into_inner();
+            jar.add_original(Cookie::new(cookie.name().to_owned(), ""));
+            jar.remove(cookie);
+        }
+        Ok(jar)
     }
 }
 
-impl<'a> Serialize for Ser<'a, Mime> {
+impl<'a> Serialize for Ser<'a, CookieJar> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        serializer.serialize_str(&self.v.to_string())
+        if !self.config.jar_state {
+            return serializer.collect_seq(self.v.iter().map(Ser::new));
+        }
+        let [original, added, removed] = jar_state(self.v);
+        let cookies = |cookies: Vec<&'a Cookie<'static>>| {
+            cookies.into_iter().map(Ser::new).collect::<Vec<_>>()
+        };
+        (cookies(original), cookies(added), cookies(removed)).serialize(serializer)
     }
 }
 
-impl<'de> Deserialize<'de> for De<StatusCode> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where D: Deserializer<'de>,
-    {
-        let code = Deserialize::deserialize(deserializer)?;
-        Ok(De::new(StatusCode::from_u16(code).map_err(D::Error::custom)?))
-    }
+/// How deserialised cookies get merged into an existing `CookieJar`.
+///
+/// See `MergeInto`.
+#[derive(Clone, Copy)]
+pub enum JarMerge<'f> {
+    /// The cookies of the jar not present in the deserialised ones are
+    /// removed, and the deserialised cookies are added.
+    ReplaceAll,
+    /// The deserialised cookies are added to the jar, replacing the cookies
+    /// with the same names.
+    Union,
+    /// Like `Union`, except that a cookie of the jar is kept if it was created
+    /// after the deserialised cookie with the same name.
+    ///
+    /// Creation times are not tracked by `Cookie`, so they are given by
+    /// this function. Cookies without a creation time are older than the
+    /// ones with a creation time.
+    KeepNewer(&'f dyn Fn(&Cookie<'static>) -> Option<Tm>),
 }
 
-impl<'a> Serialize for Ser<'a, StatusCode> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where S: Serializer,
-    {
-        self.v.as_u16().serialize(serializer)
+impl<'f> fmt::Debug for JarMerge<'f> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JarMerge::ReplaceAll => formatter.write_str("ReplaceAll"),
+            JarMerge::Union => formatter.write_str("Union"),
+            JarMerge::KeepNewer(_) => formatter.write_str("KeepNewer(..)"),
+        }
     }
 }
 
-impl<'a> Serialize for Ser<'a, (StatusCode, String)> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where S: Serializer,
-    {
-        let mut serializer = serializer.serialize_seq(Some(2))?;
-        serializer.serialize_element(&Ser::new(&self.v.0))?;
-        serializer.serialize_element(&self.v.1)?;
-        serializer.end()
-    }
+/// A seed to deserialize cookies into an existing `CookieJar`.
+///
+/// The cookies are expected in the same form as for `De<CookieJar>`. The
+/// jar is modified through `CookieJar::add` and `CookieJar::remove`, so its
+/// delta reflects what changed.
+#[derive(Debug)]
+pub struct MergeInto<'j, 'f> {
+    jar: &'j mut CookieJar,
+    strategy: JarMerge<'f>,
 }
 
-impl<'de> Deserialize<'de> for De<(StatusCode, String)> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where D: Deserializer<'de>,
-    {
-        Ok(De::new(deserializer.deserialize_seq(StatusVisitor)?))
+impl<'j, 'f> MergeInto<'j, 'f> {
+    /// Returns a new seed merging cookies into `jar` according to `strategy`.
+    #[inline(always)]
+    pub fn new(jar: &'j mut CookieJar, strategy: JarMerge<'f>) -> Self {
+        MergeInto { jar, strategy }
     }
 }
 
-struct StatusVisitor;
-
-impl<'de> Visitor<'de> for StatusVisitor {
-    type Value = (StatusCode, String);
+impl<'de, 'j, 'f> DeserializeSeed<'de> for MergeInto<'j, 'f> {
+    type Value = ();
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+        where D: Deserializer<'de>,
+    {
+        let cookies = Vec::<Serde<Cookie<'static>>>::deserialize(deserializer)?;
+        if let JarMerge::ReplaceAll = self.strategy {
+            let stale = self.jar
+                .iter()
+                .filter(|c| !cookies.iter().any(|new| new.name() == c.name()))
+                .cloned()
+                .collect::<Vec<_>>();
+            for cookie in stale {
+                self.jar.remove(cookie);
+            }
+        }
+        for cookie in cookies {
+            let cookie = cookie.into_inner();
+            if let JarMerge::KeepNewer(created) = self.strategy {
+                if let Some(old) = self.jar.get(cookie.name()) {
+                    if created(old) > created(&cookie) {
+                        continue;
+                    }
+                }
+            }
+            self.jar.add(cookie);
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for De<HeaderMap> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        DeConfig::default().seed::<HeaderMap>().deserialize(deserializer).map(De::new)
+    }
+}
+
+impl<'de, 'c> DeserializeSeed<'de> for DeWith<'c, HeaderMap> {
+    type Value = HeaderMap;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<HeaderMap, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct HeadersVisitor<'c> {
+            config: &'c DeConfig,
+        }
+
+        impl<'de, 'c> Visitor<'de> for HeadersVisitor<'c> {
+            type Value = HeaderMap;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                if self.config.header_format == HeaderMapFormat::Ordered {
+                    write!(formatter, "a sequence of header names and values")
+                } else {
+                    write!(formatter, "a map from header names to header values")
+                }
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Ok(HeaderMap::new())
+            }
+
+            fn visit_map<V>(self,
+                            mut visitor: V)
+                            -> Result<Self::Value, V::Error>
+                where V: MapAccess<'de>,
+            {
+                let budget = self.config.budget();
+                let mut headers = HeaderMap::new();
+                while let Some(k) = visitor.next_key::<String>()? {
+                    budget.spend(k.len())?;
+                    let values = visitor.next_value_seed(RawValuesSeed(&budget))?;
+                    self.append(&mut headers, &k, &values.0)?;
+                }
+                Ok(headers)
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let budget = self.config.budget();
+                let mut headers = HeaderMap::new();
+                while let Some((k, v)) = visitor.next_element::<(String, ByteBuf)>()? {
+                    budget.spend(k.len() + v.len())?;
+                    self.append(&mut headers, &k, &[v.into_vec()])?;
+                }
+                Ok(headers)
+            }
+        }
+
+        impl<'c> HeadersVisitor<'c> {
+            /// Appends the values of the header `k` to `headers`, following
+            /// the configured policy.
+            fn append<E>(&self, headers: &mut HeaderMap, k: &str, values: &[Vec<u8>])
+                         -> Result<(), E>
+                where E: de::Error,
+            {
+                match self.config.header_policy.action(k) {
+                    HeaderAction::Keep => {},
+                    HeaderAction::Drop => return Ok(()),
+                    HeaderAction::Reject => {
+                        return Err(E::custom(format!("header {:?} is not allowed", k)));
+                    },
+                }
+                if k.bytes().any(|b| b.is_ascii_uppercase()) {
+                    self.config.report(Issue::CasingNormalized(k.to_owned()));
+                }
+                let name = parse_header_name(k.as_bytes()).map_err(E::custom)?;
+                let binary = self.config.grpc && grpc::is_binary_key(k);
+                for v in values {
+                    let value = if binary {
+                        HeaderValue::from_str(&grpc::encode(v)).map_err(E::custom)
+                    } else {
+                        let v = match v.strip_prefix(BASE64_TAG.as_bytes()) {
+                            Some(v) if self.config.base64_fallback => {
+                                Cow::Owned(BASE64_STANDARD.decode(v).map_err(|_| {
+                                    E::custom(format_args!(
+                                        "invalid base64 in a {:?} header value", k))
+                                })?)
+                            },
+                            _ => Cow::Borrowed(&**v),
+                        };
+                        parse_field_value(format_args!("a {:?} header value", k), &v)
+                    };
+                    headers.append(name.clone(), value?);
+                }
+                Ok(())
+            }
+        }
+
+        let visitor = HeadersVisitor { config: self.config };
+        match self.config.header_format {
+            HeaderMapFormat::Multimap => deserializer.deserialize_map(visitor),
+            HeaderMapFormat::Ordered => deserializer.deserialize_seq(visitor),
+            HeaderMapFormat::Fetch => {
+                Err(D::Error::custom("the Fetch layout of header maps cannot be deserialised"))
+            },
+        }
+    }
+}
+
+/// The values of a header, as deserialised from a `HeaderMap`.
+pub(crate) struct RawValues(pub(crate) Vec<Vec<u8>>);
+
+impl<'de> Deserialize<'de> for RawValues {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        RawValuesSeed(&Budget::unlimited()).deserialize(deserializer)
+    }
+}
+
+/// Deserialises `RawValues`, spending their length from a `Budget`.
+struct RawValuesSeed<'b>(&'b Budget);
+
+impl<'de, 'b> DeserializeSeed<'de> for RawValuesSeed<'b> {
+    type Value = RawValues;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<RawValues, D::Error>
+        where D: Deserializer<'de>,
+    {
+        // Human-readable formats may also hold a single value instead of a
+        // sequence, as commonly found in hand-written fixtures.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_seq(self)
+        }
+    }
+}
+
+impl<'de, 'b> Visitor<'de> for RawValuesSeed<'b> {
+    type Value = RawValues;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string, bytes, or an array of strings and sequences of bytes")
+    }
+
+    fn visit_unit<E>(self) -> Result<RawValues, E>
+        where E: de::Error,
+    {
+        Ok(RawValues(vec![]))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<RawValues, E>
+        where E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<RawValues, E>
+        where E: de::Error,
+    {
+        self.0.spend(v.len())?;
+        Ok(RawValues(vec![v.to_owned()]))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<RawValues, V::Error>
+        where V: SeqAccess<'de>,
+    {
+        // Clamp to not OOM on rogue values.
+        let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+        let mut values = Vec::with_capacity(capacity);
+        // Values are accepted as strings, byte sequences or sequences
+        // of integers, so data produced before pretty mode existed, or
+        // by formats without a bytes type, keeps deserialising.
+        while let Some(v) = visitor.next_element::<ByteBuf>()? {
+            self.0.spend(v.len())?;
+            values.push(v.into_vec());
+        }
+        Ok(RawValues(values))
+    }
+}
+
+/// What redacted header values get replaced with.
+pub(crate) const REDACTED: &[u8] = b"[redacted]";
+
+/// Returns whether the values of a header hold credentials, cookies or other
+/// data which should not end up in logs and bug reports.
+fn is_sensitive_name(name: &HeaderName) -> bool {
+    *name == header::AUTHORIZATION || *name == header::COOKIE ||
+        *name == header::PROXY_AUTHORIZATION || *name == header::SET_COOKIE
+}
+
+/// Returns whether the values of a header can be combined into a single
+/// comma-separated value, which is not the case of `Set-Cookie` as its
+/// values may contain commas themselves.
+fn is_combinable(name: &HeaderName) -> bool {
+    *name != header::SET_COOKIE
+}
+
+impl<'a> Serialize for Ser<'a, HeaderMap> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        struct Value<'headers> {
+            name: &'headers HeaderName,
+            values: GetAll<'headers, HeaderValue>,
+            config: &'headers SerConfig,
+        }
+
+        impl<'headers> Serialize for Value<'headers> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer,
+            {
+                if self.config.grpc {
+                    return serialize_grpc_values(serializer,
+                                                 self.name,
+                                                 &self.values,
+                                                 self.config);
+                }
+                serialize_values(serializer,
+                                 self.values.iter(),
+                                 self.values.iter().count(),
+                                 is_sensitive_name(self.name),
+                                 self.config)
+            }
+        }
+
+        if self.config.header_format == HeaderMapFormat::Ordered {
+            return serialize_ordered(serializer, self.v.iter(), self.v.len(), self.config);
+        }
+
+        let fetch = self.config.header_format == HeaderMapFormat::Fetch;
+        // Names are only collected when they need sorting, so the default
+        // layout does not allocate.
+        let (mut sorted, mut unsorted);
+        let names: &mut dyn Iterator<Item = &HeaderName> = if self.config.sort_keys || fetch {
+            let mut names = self.v.keys().collect::<Vec<_>>();
+            names.sort_unstable_by_key(|name| name.as_str());
+            sorted = names.into_iter();
+            &mut sorted
+        } else {
+            unsorted = self.v.keys();
+            &mut unsorted
+        };
+
+        let len = if fetch {
+            self.v.keys_len() + self.v.get_all(header::SET_COOKIE).iter().count()
+                .saturating_sub(1)
+        } else {
+            self.v.keys_len()
+        };
+        let mut serializer = serializer.serialize_map(Some(len))?;
+        for name in names {
+            let values = self.v.get_all(name);
+            if !fetch {
+                if !is_combinable(name) && values.iter().nth(1).is_some() {
+                    self.config.report(Issue::ValuesGrouped(name.clone()));
+                }
+                let value = Value { name, values, config: self.config };
+                serializer.serialize_entry(name.as_str(), &value)?;
+                continue;
+            }
+            let values = values.iter().map(|v| {
+                if self.config.redact && (is_sensitive_name(name) || v.is_sensitive()) {
+                    REDACTED
+                } else {
+                    v.as_bytes()
+                }
+            }).collect::<SmallVec<[&[u8]; 2]>>();
+            if !is_combinable(name) {
+                for v in values {
+                    serializer.serialize_entry(name.as_str(), &StrOrBytes(v))?;
+                }
+            } else if let [v] = values[..] {
+                serializer.serialize_entry(name.as_str(), &StrOrBytes(v))?;
+            } else {
+                let combined = values.join(&b", "[..]);
+                serializer.serialize_entry(name.as_str(), &StrOrBytes(&combined))?;
+            }
+        }
+        serializer.end()
+    }
+}
+
+impl<'a, 'values> Serialize for Ser<'a, &'values [HeaderValue]> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serialize_values(serializer, self.v.iter(), self.v.len(), false, self.config)
+    }
+}
+
+impl<'a, 'values> Serialize for Ser<'a, GetAll<'values, HeaderValue>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serialize_values(serializer,
+                         self.v.iter(),
+                         self.v.iter().count(),
+                         false,
+                         self.config)
+    }
+}
+
+/// Serialises the values of a header following the conventions of gRPC
+/// metadata.
+fn serialize_grpc_values<S>(serializer: S,
+                            name: &HeaderName,
+                            values: &GetAll<HeaderValue>,
+                            config: &SerConfig)
+                            -> Result<S::Ok, S::Error>
+    where S: Serializer,
+{
+    let binary = grpc::is_binary_key(name.as_str());
+    let sensitive = is_sensitive_name(name);
+    let mut serializer = serializer.serialize_seq(Some(values.iter().count()))?;
+    for v in values {
+        if config.redact && (sensitive || v.is_sensitive()) {
+            serializer.serialize_element(str::from_utf8(REDACTED).unwrap())?;
+        } else if binary {
+            let decoded = grpc::decode(v.as_bytes()).ok_or_else(|| {
+                serde::ser::Error::custom(format_args!(
+                    "invalid base64 in a {:?} header value", name))
+            })?;
+            serializer.serialize_element(&Bytes::new(&decoded))?;
+        } else if let Ok(v) = v.to_str() {
+            serializer.serialize_element(v)?;
+        } else {
+            serializer.serialize_element(&Bytes::new(v.as_bytes()))?;
+        }
+    }
+    serializer.end()
+}
+
+/// A wrapper to serialize an iterator of header names and values as a
+/// `HeaderMap`.
+///
+/// This is useful to serialize a filtered or transformed view of a
+/// `HeaderMap` without building a new one. The wrapper holds a function
+/// returning the iterator, as it needs to be traversed twice. Consecutive
+/// items with the same name are grouped together, so the output is the same
+/// as serialising a `HeaderMap` of these items, as long as the items with the
+/// same name are adjacent. Otherwise, the same name appears multiple times
+/// in the output, which `De<HeaderMap>` accepts.
+///
+/// ```
+/// let view = SerIter::new(|| headers.iter().filter(|&(name, _)| name != COOKIE));
+/// serde_json::to_string(&view)
+/// ```
+pub struct SerIter<'a, F> {
+    iter: F,
+    config: &'a SerConfig,
+}
+
+impl<'a, F, I, N, V> SerIter<'a, F>
+    where F: Fn() -> I,
+          I: IntoIterator<Item = (N, V)>,
+          N: Borrow<HeaderName>,
+          V: Borrow<HeaderValue>,
+{
+    /// Returns a new `SerIter` wrapper.
+    #[inline(always)]
+    pub fn new(iter: F) -> Self {
+        SerIter::with_config(iter, &SerConfig::DEFAULT)
+    }
+
+    /// Returns a new `SerIter` wrapper, with the given configuration.
+    ///
+    /// Only the pretty mode and redaction options are supported, as well as
+    /// the `HeaderMapFormat::Ordered` layout, which keeps the order of the
+    /// items.
+    #[inline(always)]
+    pub fn with_config(iter: F, config: &'a SerConfig) -> Self {
+        SerIter { iter, config }
+    }
+}
+
+impl<'a, F> fmt::Debug for SerIter<'a, F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("SerIter").field("config", self.config).finish()
+    }
+}
+
+impl<'a, F, I, N, V> Serialize for SerIter<'a, F>
+    where F: Fn() -> I,
+          I: IntoIterator<Item = (N, V)>,
+          N: Borrow<HeaderName>,
+          V: Borrow<HeaderValue>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        struct Value<'a, 'i, I> {
+            iter: RefCell<&'i mut I>,
+            len: usize,
+            sensitive: bool,
+            config: &'a SerConfig,
+        }
+
+        impl<'a, 'i, I, N, V> Serialize for Value<'a, 'i, I>
+            where I: Iterator<Item = (N, V)>,
+                  V: Borrow<HeaderValue>,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer,
+            {
+                let mut iter = self.iter.borrow_mut();
+                let values = iter.by_ref().take(self.len).map(|(_, v)| v);
+                serialize_values(serializer,
+                                 values,
+                                 self.len,
+                                 self.sensitive,
+                                 self.config)
+            }
+        }
+
+        // Calls `f` with the name and length of each run of items with the
+        // same name.
+        fn runs<I, N, V, E>(iter: I,
+                            mut f: impl FnMut(&HeaderName, usize) -> Result<(), E>)
+                            -> Result<(), E>
+            where I: Iterator<Item = (N, V)>,
+                  N: Borrow<HeaderName>,
+        {
+            let mut iter = iter.peekable();
+            while let Some((name, _)) = iter.next() {
+                let mut len = 1;
+                while iter.next_if(|(n, _)| n.borrow() == name.borrow()).is_some() {
+                    len += 1;
+                }
+                f(name.borrow(), len)?;
+            }
+            Ok(())
+        }
+
+        if self.config.header_format == HeaderMapFormat::Ordered {
+            let len = (self.iter)().into_iter().count();
+            return serialize_ordered(serializer, (self.iter)().into_iter(), len, self.config);
+        }
+
+        let mut len = 0;
+        runs((self.iter)().into_iter(), |_, _| {
+            len += 1;
+            Ok::<_, S::Error>(())
+        })?;
+
+        let mut serializer = serializer.serialize_map(Some(len))?;
+        let mut values = (self.iter)().into_iter();
+        runs((self.iter)().into_iter(), |name, len| {
+            if !is_combinable(name) && len > 1 {
+                self.config.report(Issue::ValuesGrouped(name.clone()));
+            }
+            let value = Value {
+                iter: RefCell::new(&mut values),
+                len,
+                sensitive: is_sensitive_name(name),
+                config: self.config,
+            };
+            serializer.serialize_entry(name.as_str(), &value)
+        })?;
+        serializer.end()
+    }
+}
+
+/// Serialises the values of a header as a sequence, with each value
+/// serialised as a string in pretty mode if it represents UTF-8 text, and
+/// as a sequence of bytes otherwise.
+fn serialize_values<S, I>(serializer: S,
+                         values: I,
+                         len: usize,
+                         sensitive: bool,
+                         config: &SerConfig)
+                         -> Result<S::Ok, S::Error>
+    where S: Serializer,
+          I: Iterator,
+          I::Item: Borrow<HeaderValue>,
+{
+    let mut serializer = serializer.serialize_seq(Some(len))?;
+    for v in values {
+        let v = v.borrow();
+        let v = if config.redact && (sensitive || v.is_sensitive()) {
+            REDACTED
+        } else {
+            v.as_bytes()
+        };
+        match pretty_value(v, config) {
+            Some(v) => serializer.serialize_element(&*v)?,
+            None => serializer.serialize_element(&Bytes::new(v))?,
+        }
+    }
+    serializer.end()
+}
+
+/// The tag of header values serialised as base64 with
+/// `SerConfig::base64_fallback`.
+const BASE64_TAG: &str = "base64:";
+
+/// Returns the string a header value is serialised as in pretty mode, if
+/// any.
+fn pretty_value<'v>(v: &'v [u8], config: &SerConfig) -> Option<Cow<'v, str>> {
+    if !config.pretty {
+        return None;
+    }
+    match str::from_utf8(v) {
+        Ok(v) if !config.base64_fallback || !v.starts_with(BASE64_TAG) => Some(Cow::Borrowed(v)),
+        _ if config.base64_fallback => {
+            Some(Cow::Owned(format!("{}{}", BASE64_TAG, BASE64_STANDARD.encode(v))))
+        },
+        _ => None,
+    }
+}
+
+/// Serialises header names and values in the `HeaderMapFormat::Ordered`
+/// layout.
+fn serialize_ordered<S, I, N, V>(serializer: S,
+                                 items: I,
+                                 len: usize,
+                                 config: &SerConfig)
+                                 -> Result<S::Ok, S::Error>
+    where S: Serializer,
+          I: Iterator<Item = (N, V)>,
+          N: Borrow<HeaderName>,
+          V: Borrow<HeaderValue>,
+{
+    let mut serializer = serializer.serialize_seq(Some(len))?;
+    for (name, v) in items {
+        let (name, v) = (name.borrow(), v.borrow());
+        let v = if config.redact && (is_sensitive_name(name) || v.is_sensitive()) {
+            REDACTED
+        } else {
+            v.as_bytes()
+        };
+        match pretty_value(v, config) {
+            Some(v) => serializer.serialize_element(&(name.as_str(), &*v))?,
+            None => serializer.serialize_element(&(name.as_str(), Bytes::new(v)))?,
+        }
+    }
+    serializer.end()
+}
+
+impl<'de> Deserialize<'de> for De<HeaderName> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct HeaderNameVisitor;
+
+        impl<'de> Visitor<'de> for HeaderNameVisitor {
+            type Value = De<HeaderName>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP header name")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                parse_header_name(v.as_bytes()).map(De::new).map_err(|e| {
+                    E::custom(format_args!("invalid header name {:?}: {}", v, e))
+                })
+            }
+        }
+
+        deserializer.deserialize_str(HeaderNameVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, HeaderName> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
+
+/// A single `HeaderValue` is serialised as a string if it is valid UTF-8, and
+/// as bytes otherwise. Sensitive values are wrapped in a `{"sensitive": ..}`
+/// map. Formats which are not human-readable use a `(bytes, sensitive)`
+/// tuple instead. With redaction enabled, the bytes of sensitive values are
+/// replaced in both layouts.
+impl<'de> Deserialize<'de> for De<HeaderValue> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        fn value<E>(bytes: &[u8], sensitive: bool) -> Result<De<HeaderValue>, E>
+            where E: de::Error,
+        {
+            let mut value = parse_field_value("a header value", bytes)?;
+            value.set_sensitive(sensitive);
+            Ok(De::new(value))
+        }
+
+        struct HeaderValueVisitor;
+
+        impl<'de> Visitor<'de> for HeaderValueVisitor {
+            type Value = De<HeaderValue>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP header value")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                value(v.as_bytes(), false)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                value(v, false)
+            }
+
+            fn visit_seq<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let bytes = ByteBuf::deserialize(de::value::SeqAccessDeserializer::new(visitor))?;
+                value(&bytes, false)
+            }
+
+            fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: MapAccess<'de>,
+            {
+                match visitor.next_key::<String>()? {
+                    Some(ref key) if key == "sensitive" => {},
+                    Some(key) => return Err(V::Error::unknown_field(&key, &["sensitive"])),
+                    None => return Err(V::Error::missing_field("sensitive")),
+                }
+                let bytes = visitor.next_value::<ByteBuf>()?;
+                if let Some(key) = visitor.next_key::<String>()? {
+                    return Err(V::Error::unknown_field(&key, &["sensitive"]));
+                }
+                value(&bytes, true)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HeaderValueVisitor)
+        } else {
+            let (bytes, sensitive) = <(ByteBuf, bool)>::deserialize(deserializer)?;
+            value(&bytes, sensitive)
+        }
+    }
+}
+
+impl<'a> Serialize for Ser<'a, HeaderValue> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        struct Value<'a>(&'a HeaderValue);
+
+        impl<'a> Serialize for Value<'a> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer,
+            {
+                match self.0.to_str() {
+                    Ok(s) => serializer.serialize_str(s),
+                    Err(_) => serializer.serialize_bytes(self.0.as_bytes()),
+                }
+            }
+        }
+
+        let sensitive = self.v.is_sensitive();
+        if !serializer.is_human_readable() {
+            let v = if sensitive && self.config.redact { REDACTED } else { self.v.as_bytes() };
+            return (Bytes::new(v), sensitive).serialize(serializer);
+        }
+        if sensitive && self.config.redact {
+            return serializer.serialize_str(str::from_utf8(REDACTED).unwrap());
+        }
+        if sensitive {
+            let mut serializer = serializer.serialize_map(Some(1))?;
+            serializer.serialize_entry("sensitive", &Value(self.v))?;
+            serializer.end()
+        } else {
+            Value(self.v).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for De<Method> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct MethodVisitor;
+
+        impl<'de> Visitor<'de> for MethodVisitor {
+            type Value = De<Method>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP method")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                v.parse::<Method>().map(De::new).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_string(MethodVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Method> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        Serialize::serialize(self.v.as_ref(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for De<Authority> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct AuthorityVisitor;
+
+        impl<'de> Visitor<'de> for AuthorityVisitor {
+            type Value = De<Authority>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP URI authority")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                v.parse::<Authority>().map(De::new).map_err(|e| {
+                    E::custom(format_args!("invalid HTTP URI authority {:?}: {}", v, e))
+                })
+            }
+        }
+
+        deserializer.deserialize_str(AuthorityVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Authority> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for De<Scheme> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct SchemeVisitor;
+
+        impl<'de> Visitor<'de> for SchemeVisitor {
+            type Value = De<Scheme>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a URI scheme")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                // The http crate does not check that schemes start with
+                // a letter, as required by RFC 3986.
+                if !v.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                    return Err(E::custom(format_args!(
+                        "invalid URI scheme {:?}: schemes must start with a letter", v)));
+                }
+                v.parse::<Scheme>().map(De::new).map_err(|e| {
+                    E::custom(format_args!("invalid URI scheme {:?}: {}", v, e))
+                })
+            }
+        }
+
+        deserializer.deserialize_str(SchemeVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Scheme> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for De<PathAndQuery> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct PathAndQueryVisitor;
+
+        impl<'de> Visitor<'de> for PathAndQueryVisitor {
+            type Value = De<PathAndQuery>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP path and query")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                v.parse::<PathAndQuery>().map(De::new).map_err(|e| {
+                    E::custom(format_args!("invalid HTTP path and query {:?}: {}", v, e))
+                })
+            }
+        }
+
+        deserializer.deserialize_str(PathAndQueryVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, PathAndQuery> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for De<Version> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct VersionVisitor;
+
+        impl<'de> Visitor<'de> for VersionVisitor {
+            type Value = De<Version>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP version")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                text::parse_version(v)
+                    .map(De::new)
+                    .ok_or_else(|| E::custom(format_args!("unknown HTTP version {:?}", v)))
+            }
+        }
+
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Version> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(text::version_str(*self.v))
+    }
+}
+
+impl<'de> Deserialize<'de> for De<Mime> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct MimeVisitor;
+
+        impl<'de> Visitor<'de> for MimeVisitor {
+            type Value = De<Mime>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a mime type")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                v.parse::<Mime>().map(De::new).map_err(|_| {
+                    E::custom("could not parse mime type")
+                })
+            }
+        }
+
+        deserializer.deserialize_string(MimeVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Mime> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_ref())
+    }
+}
+
+/// `mime::Name` values are serialised as strings. They borrow from a `Mime`
+/// and cannot be deserialised, deserialise a `String` or a `Mime` instead.
+impl<'a, 'n> Serialize for Ser<'a, mime::Name<'n>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for De<StatusCode> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct StatusCodeVisitor;
+
+        impl<'de> Visitor<'de> for StatusCodeVisitor {
+            type Value = De<StatusCode>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an HTTP status code")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                u16::try_from(v)
+                    .ok()
+                    .and_then(|v| StatusCode::from_u16(v).ok())
+                    .map(De::new)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                u64::try_from(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+                    .and_then(|v| self.visit_u64(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                // The reason phrase of pretty mode must be the canonical one,
+                // so that a mismatched code and reason are not silently read.
+                let (code, reason) = v.split_once(' ').unwrap_or((v, ""));
+                let status = StatusCode::from_bytes(code.as_bytes()).map_err(E::custom)?;
+                if !reason.is_empty() && status.canonical_reason() != Some(reason) {
+                    return Err(E::invalid_value(de::Unexpected::Str(v), &self));
+                }
+                Ok(De::new(status))
+            }
+        }
+
+        // Only human-readable formats can hold the strings of pretty mode.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(StatusCodeVisitor)
+        } else {
+            deserializer.deserialize_u16(StatusCodeVisitor)
+        }
+    }
+}
+
+impl<'a> Serialize for Ser<'a, StatusCode> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        struct StatusLine(StatusCode);
+
+        impl fmt::Display for StatusLine {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                match self.0.canonical_reason() {
+                    Some(reason) => write!(formatter, "{} {}", self.0.as_u16(), reason),
+                    None => write!(formatter, "{}", self.0.as_u16()),
+                }
+            }
+        }
+
+        if self.config.pretty && serializer.is_human_readable() {
+            serializer.collect_str(&StatusLine(*self.v))
+        } else {
+            self.v.as_u16().serialize(serializer)
+        }
+    }
+}
+
+/// A status code with its reason phrase.
+///
+/// This is serialised like `(StatusCode, String)`, but its reason phrase
+/// borrows the canonical one of the status code when they match, saving
+/// an allocation when deserialising the common case.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusWithReason {
+    /// The status code.
+    pub status: StatusCode,
+    /// The reason phrase.
+    pub reason: Cow<'static, str>,
+}
+
+impl StatusWithReason {
+    /// Returns a status code with its canonical reason phrase, or an empty
+    /// reason phrase if it has none.
+    pub fn new(status: StatusCode) -> Self {
+        StatusWithReason {
+            status,
+            reason: Cow::Borrowed(status.canonical_reason().unwrap_or("")),
+        }
+    }
+
+    /// Returns whether the reason phrase is the canonical one.
+    pub fn is_canonical(&self) -> bool {
+        self.status.canonical_reason() == Some(&*self.reason)
+    }
+}
+
+impl From<StatusCode> for StatusWithReason {
+    fn from(status: StatusCode) -> Self {
+        StatusWithReason::new(status)
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusWithReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct StatusWithReasonVisitor;
+
+        impl<'de> Visitor<'de> for StatusWithReasonVisitor {
+            type Value = StatusWithReason;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an array containing a status code and a reason string")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let status = visitor.next_element::<De<StatusCode>>()?.ok_or_else(||
+                    V::Error::custom("Can't find the status code")
+                )?.into_inner();
+                let reason = visitor.next_element_seed(ReasonSeed(status))?.ok_or_else(||
+                    V::Error::custom("Can't find the reason string")
+                )?;
+                Ok(StatusWithReason { status, reason })
+            }
+        }
+
+        struct ReasonSeed(StatusCode);
+
+        impl<'de> DeserializeSeed<'de> for ReasonSeed {
+            type Value = Cow<'static, str>;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                where D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(self)
+            }
+        }
+
+        impl<'de> Visitor<'de> for ReasonSeed {
+            type Value = Cow<'static, str>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a reason string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                match self.0.canonical_reason() {
+                    Some(reason) if reason == v => Ok(Cow::Borrowed(reason)),
+                    _ => Ok(Cow::Owned(v.to_owned())),
+                }
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                match self.0.canonical_reason() {
+                    Some(reason) if reason == v => Ok(Cow::Borrowed(reason)),
+                    _ => Ok(Cow::Owned(v)),
+                }
+            }
+        }
+
+        deserializer.deserialize_seq(StatusWithReasonVisitor)
+    }
+}
+
+impl Serialize for StatusWithReason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut serializer = serializer.serialize_seq(Some(2))?;
+        serializer.serialize_element(&Ser::new(&self.status))?;
+        serializer.serialize_element(&*self.reason)?;
+        serializer.end()
+    }
+}
+
+/// A status code and an optional reason phrase, as found in a status line.
+///
+/// Unlike `StatusCode` and `StatusWithReason`, any code is kept, including
+/// the ones outside of the three-digit range `StatusCode` accepts, such as
+/// the `0` some clients report for failed requests, along with the absence
+/// of a reason phrase. This is serialised as a `[code, reason]` tuple, where
+/// `reason` may be null.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RawStatus {
+    /// The status code.
+    pub code: u16,
+    /// The reason phrase, if any.
+    pub reason: Option<String>,
+}
+
+impl RawStatus {
+    /// Returns the status code as a `StatusCode`, if it is in its range.
+    pub fn status(&self) -> Option<StatusCode> {
+        StatusCode::from_u16(self.code).ok()
+    }
+}
+
+impl From<StatusCode> for RawStatus {
+    fn from(status: StatusCode) -> Self {
+        RawStatus { code: status.as_u16(), reason: None }
+    }
+}
+
+impl From<StatusWithReason> for RawStatus {
+    fn from(status: StatusWithReason) -> Self {
+        RawStatus {
+            code: status.status.as_u16(),
+            reason: Some(status.reason.into_owned()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RawStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (code, reason) = <(u16, Option<String>)>::deserialize(deserializer)?;
+        if let Some(ref reason) = reason {
+            check_field_value("a reason phrase", reason.as_bytes())?;
+        }
+        Ok(RawStatus { code, reason })
+    }
+}
+
+impl Serialize for RawStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (self.code, &self.reason).serialize(serializer)
+    }
+}
+
+/// A `ReasonPhrase` is serialised as a string in human-readable formats if it
+/// is UTF-8 text, and as bytes otherwise.
+///
+/// It is stored in the extensions of a response, so it can be kept across a
+/// response snapshot by registering it in an `ExtensionRegistry`:
+///
+/// ```
+/// use hyper::ext::ReasonPhrase;
+/// use hyper_serde::ExtensionRegistry;
+/// use std::convert::TryFrom;
+///
+/// let registry = ExtensionRegistry::new().register::<ReasonPhrase, _, _>(
+///     "reason-phrase",
+///     |reason| reason.as_bytes().to_vec(),
+///     |bytes| ReasonPhrase::try_from(bytes).ok());
+/// ```
+#[cfg(feature = "http1")]
+impl<'de> Deserialize<'de> for De<hyper::ext::ReasonPhrase> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let bytes = ByteBuf::deserialize(deserializer)?.into_vec();
+        check_field_value("a reason phrase", &bytes)?;
+        hyper::ext::ReasonPhrase::try_from(bytes).map(De::new).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "http1")]
+impl<'a> Serialize for Ser<'a, hyper::ext::ReasonPhrase> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        StrOrBytes(self.v.as_bytes()).serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, (StatusCode, String)> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut serializer = serializer.serialize_seq(Some(2))?;
+        serializer.serialize_element(&Ser::new(&self.v.0))?;
+        serializer.serialize_element(&self.v.1)?;
+        serializer.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for De<(StatusCode, String)> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        Ok(De::new(deserializer.deserialize_seq(StatusVisitor)?))
+    }
+}
+
+struct StatusVisitor;
+
+impl<'de> Visitor<'de> for StatusVisitor {
+    type Value = (StatusCode, String);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         write!(formatter, "an array containing a status code and a reason string")
     }
 
@@ -576,33 +2304,149 @@ impl<'a> Serialize for Ser<'a, Tm> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
-        serializer.serialize_str(&self.v.rfc3339().to_string())
+        serializer.collect_str(&self.v.rfc3339())
     }
 }
 
-impl<'de> Deserialize<'de> for De<Uri> {
+/// The first second that cannot be formatted as an HTTP date, i.e. the start
+/// of year 10000.
+const HTTP_DATE_END: u64 = 253402300800;
+
+/// `SystemTime` values are serialised as HTTP dates in the IMF-fixdate format
+/// in human-readable formats, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`, and as
+/// the number of seconds since the Unix epoch otherwise.
+///
+/// The fractional part of the second is dropped, and times before the Unix
+/// epoch or after year 9999 cannot be serialised. The obsolete RFC 850 and
+/// asctime formats are accepted when deserialising.
+impl<'de> Deserialize<'de> for De<SystemTime> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>,
     {
-        struct UriVisitor;
+        struct SystemTimeVisitor;
 
-        impl<'de> Visitor<'de> for UriVisitor {
-            type Value = De<Uri>;
+        impl<'de> Visitor<'de> for SystemTimeVisitor {
+            type Value = De<SystemTime>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(formatter, "an HTTP Uri value")
+                write!(formatter, "an HTTP date")
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
                 where E: de::Error,
             {
-                Uri::from_str(v)
-                    .map(De::new)
-                    .map_err(|e| E::custom(format!("{}", e)))
+                httpdate::parse_http_date(v).map(De::new).map_err(|_| {
+                    E::custom(format_args!("invalid HTTP date {:?}", v))
+                })
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(SystemTimeVisitor);
+        }
+        let secs = u64::deserialize(deserializer)?;
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs)).map(De::new).ok_or_else(|| {
+            D::Error::custom("time out of range")
+        })
+    }
+}
+
+impl<'a> Serialize for Ser<'a, SystemTime> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let secs = match self.v.duration_since(UNIX_EPOCH) {
+            Ok(duration) if duration.as_secs() < HTTP_DATE_END => duration.as_secs(),
+            Ok(_) => return Err(serde::ser::Error::custom("time after year 9999")),
+            Err(_) => return Err(serde::ser::Error::custom("time before the Unix epoch")),
+        };
+        if serializer.is_human_readable() {
+            let date = httpdate::fmt_http_date(UNIX_EPOCH + Duration::from_secs(secs));
+            serializer.serialize_str(&date)
+        } else {
+            serializer.serialize_u64(secs)
+        }
+    }
+}
+
+/// The components of a `Uri`, as found in its map form.
+const URI_COMPONENTS: &[&str] = &["scheme", "authority", "path", "query"];
+
+/// The keys accepted in the map form of a `Uri`, which are its components
+/// and the `path_and_query` key of `uri::Parts`.
+const URI_FIELDS: &[&str] = &["scheme", "authority", "path", "query", "path_and_query"];
+
+/// A visitor of `Uri` values, as strings or as maps of components.
+struct UriVisitor;
+
+impl<'de> Visitor<'de> for UriVisitor {
+    type Value = De<Uri>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an HTTP Uri value")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where E: de::Error,
+    {
+        Uri::from_str(v)
+            .map(De::new)
+            .map_err(|e| E::custom(format!("{}", e)))
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+        where V: MapAccess<'de>,
+    {
+        let mut components: [Option<String>; 5] = Default::default();
+        while let Some(key) = visitor.next_key::<String>()? {
+            let index = URI_FIELDS.iter().position(|&c| c == key).ok_or_else(|| {
+                de::Error::unknown_field(&key, URI_FIELDS)
+            })?;
+            if components[index].is_some() {
+                return Err(de::Error::custom(format_args!("duplicate Uri {}", key)));
             }
+            components[index] = Some(visitor.next_value()?);
         }
+        let [scheme, authority, path, query, path_and_query] = components;
+        if path_and_query.is_some() && (path.is_some() || query.is_some()) {
+            return Err(de::Error::custom("Uri path_and_query conflicts with path and query"));
+        }
+
+        let mut builder = Uri::builder();
+        let absolute = scheme.is_some();
+        if let Some(scheme) = scheme {
+            builder = builder.scheme(&*scheme);
+        }
+        if let Some(authority) = authority {
+            builder = builder.authority(&*authority);
+        }
+        // Only the authority form, as used by CONNECT, has no path.
+        if let Some(path_and_query) = path_and_query {
+            builder = builder.path_and_query(path_and_query);
+        } else if path.is_some() || query.is_some() || absolute {
+            let mut path_and_query = path.unwrap_or_else(|| "/".to_owned());
+            if let Some(query) = query {
+                path_and_query.push('?');
+                path_and_query.push_str(&query);
+            }
+            builder = builder.path_and_query(path_and_query);
+        }
+        builder.build()
+            .map(De::new)
+            .map_err(|e| de::Error::custom(format_args!("{}", e)))
+    }
+}
 
-        deserializer.deserialize_string(UriVisitor)
+impl<'de> Deserialize<'de> for De<Uri> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        // Only self-describing formats can tell strings from components.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(UriVisitor)
+        } else {
+            deserializer.deserialize_string(UriVisitor)
+        }
     }
 }
 
@@ -610,9 +2454,60 @@ impl<'a> Serialize for Ser<'a, Uri> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer,
     {
+        if self.config.uri_components && serializer.is_human_readable() {
+            let uri = self.v;
+            let components = [uri.scheme_str(),
+                              uri.authority().map(|a| a.as_str()),
+                              uri.path_and_query().map(|pq| pq.path()),
+                              uri.query()];
+            let len = components.iter().filter(|c| c.is_some()).count();
+            let mut serializer = serializer.serialize_map(Some(len))?;
+            for (key, component) in URI_COMPONENTS.iter().zip(&components) {
+                if let Some(component) = component {
+                    serializer.serialize_entry(key, component)?;
+                }
+            }
+            return serializer.end();
+        }
         // As of hyper 0.12, hyper::Uri (re-exported http::Uri)
         // does not implement as_ref due to underlying implementation
-        // so we must construct a string to serialize it
-        serializer.serialize_str(&self.v.to_string())
+        // so we must format it to serialize it
+        serializer.collect_str(self.v)
+    }
+}
+
+/// URI parts are serialised as a map of their `scheme`, `authority` and
+/// `path_and_query`. When deserialising, the `path` and `query` keys of the
+/// components of a `Uri` are also accepted in place of `path_and_query`.
+impl<'de> Deserialize<'de> for De<uri::Parts> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        // Reassembling a Uri checks that the parts make up a valid one, such
+        // as a scheme always coming with an authority.
+        let uri = deserializer.deserialize_map(UriVisitor)?.into_inner();
+        Ok(De::new(uri.into_parts()))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, uri::Parts> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let parts = self.v;
+        let len = parts.scheme.is_some() as usize +
+                  parts.authority.is_some() as usize +
+                  parts.path_and_query.is_some() as usize;
+        let mut serializer = serializer.serialize_map(Some(len))?;
+        if let Some(ref scheme) = parts.scheme {
+            serializer.serialize_entry("scheme", &Ser::new(scheme))?;
+        }
+        if let Some(ref authority) = parts.authority {
+            serializer.serialize_entry("authority", &Ser::new(authority))?;
+        }
+        if let Some(ref path_and_query) = parts.path_and_query {
+            serializer.serialize_entry("path_and_query", &Ser::new(path_and_query))?;
+        }
+        serializer.end()
     }
 }
@@ -0,0 +1,122 @@
+//! The names of the standard headers.
+
+use hyper::header::{self, HeaderName};
+use phf::phf_map;
+use uncased::UncasedStr;
+
+/// Defines `STANDARD_HEADERS` and `BY_NAME` from the same list of constants.
+macro_rules! standard_headers {
+    ($($constant:ident => $name:tt,)*) => {
+        /// The names of the standard headers, i.e. those which have constants
+        /// in `hyper::header`, sorted by their string representations.
+        pub static STANDARD_HEADERS: &[HeaderName] = &[$(header::$constant,)*];
+
+        /// A perfect hash map from the names of the standard headers, which
+        /// are matched case-insensitively, to their constants.
+        static BY_NAME: phf::Map<&'static UncasedStr, HeaderName> = phf_map! {
+            $(UncasedStr::new($name) => header::$constant,)*
+        };
+    };
+}
+
+standard_headers! {
+    ACCEPT => "accept",
+    ACCEPT_CHARSET => "accept-charset",
+    ACCEPT_ENCODING => "accept-encoding",
+    ACCEPT_LANGUAGE => "accept-language",
+    ACCEPT_RANGES => "accept-ranges",
+    ACCESS_CONTROL_ALLOW_CREDENTIALS => "access-control-allow-credentials",
+    ACCESS_CONTROL_ALLOW_HEADERS => "access-control-allow-headers",
+    ACCESS_CONTROL_ALLOW_METHODS => "access-control-allow-methods",
+    ACCESS_CONTROL_ALLOW_ORIGIN => "access-control-allow-origin",
+    ACCESS_CONTROL_EXPOSE_HEADERS => "access-control-expose-headers",
+    ACCESS_CONTROL_MAX_AGE => "access-control-max-age",
+    ACCESS_CONTROL_REQUEST_HEADERS => "access-control-request-headers",
+    ACCESS_CONTROL_REQUEST_METHOD => "access-control-request-method",
+    AGE => "age",
+    ALLOW => "allow",
+    ALT_SVC => "alt-svc",
+    AUTHORIZATION => "authorization",
+    CACHE_CONTROL => "cache-control",
+    CACHE_STATUS => "cache-status",
+    CDN_CACHE_CONTROL => "cdn-cache-control",
+    CONNECTION => "connection",
+    CONTENT_DISPOSITION => "content-disposition",
+    CONTENT_ENCODING => "content-encoding",
+    CONTENT_LANGUAGE => "content-language",
+    CONTENT_LENGTH => "content-length",
+    CONTENT_LOCATION => "content-location",
+    CONTENT_RANGE => "content-range",
+    CONTENT_SECURITY_POLICY => "content-security-policy",
+    CONTENT_SECURITY_POLICY_REPORT_ONLY => "content-security-policy-report-only",
+    CONTENT_TYPE => "content-type",
+    COOKIE => "cookie",
+    DATE => "date",
+    DNT => "dnt",
+    ETAG => "etag",
+    EXPECT => "expect",
+    EXPIRES => "expires",
+    FORWARDED => "forwarded",
+    FROM => "from",
+    HOST => "host",
+    IF_MATCH => "if-match",
+    IF_MODIFIED_SINCE => "if-modified-since",
+    IF_NONE_MATCH => "if-none-match",
+    IF_RANGE => "if-range",
+    IF_UNMODIFIED_SINCE => "if-unmodified-since",
+    LAST_MODIFIED => "last-modified",
+    LINK => "link",
+    LOCATION => "location",
+    MAX_FORWARDS => "max-forwards",
+    ORIGIN => "origin",
+    PRAGMA => "pragma",
+    PROXY_AUTHENTICATE => "proxy-authenticate",
+    PROXY_AUTHORIZATION => "proxy-authorization",
+    PUBLIC_KEY_PINS => "public-key-pins",
+    PUBLIC_KEY_PINS_REPORT_ONLY => "public-key-pins-report-only",
+    RANGE => "range",
+    REFERER => "referer",
+    REFERRER_POLICY => "referrer-policy",
+    REFRESH => "refresh",
+    RETRY_AFTER => "retry-after",
+    SEC_WEBSOCKET_ACCEPT => "sec-websocket-accept",
+    SEC_WEBSOCKET_EXTENSIONS => "sec-websocket-extensions",
+    SEC_WEBSOCKET_KEY => "sec-websocket-key",
+    SEC_WEBSOCKET_PROTOCOL => "sec-websocket-protocol",
+    SEC_WEBSOCKET_VERSION => "sec-websocket-version",
+    SERVER => "server",
+    SET_COOKIE => "set-cookie",
+    STRICT_TRANSPORT_SECURITY => "strict-transport-security",
+    TE => "te",
+    TRAILER => "trailer",
+    TRANSFER_ENCODING => "transfer-encoding",
+    UPGRADE => "upgrade",
+    UPGRADE_INSECURE_REQUESTS => "upgrade-insecure-requests",
+    USER_AGENT => "user-agent",
+    VARY => "vary",
+    VIA => "via",
+    WARNING => "warning",
+    WWW_AUTHENTICATE => "www-authenticate",
+    X_CONTENT_TYPE_OPTIONS => "x-content-type-options",
+    X_DNS_PREFETCH_CONTROL => "x-dns-prefetch-control",
+    X_FRAME_OPTIONS => "x-frame-options",
+    X_XSS_PROTECTION => "x-xss-protection",
+}
+
+/// Returns the standard header named `name`, matched case-insensitively.
+///
+/// This is what the deserialisers of this crate use to avoid allocating
+/// the names of standard headers.
+pub fn standard_header_name(name: &[u8]) -> Option<HeaderName> {
+    let name = std::str::from_utf8(name).ok()?;
+    BY_NAME.get(UncasedStr::new(name)).cloned()
+}
+
+/// Parses a header name, using `STANDARD_HEADERS` for the standard ones.
+pub(crate) fn parse_header_name(name: &[u8])
+                                -> Result<HeaderName, header::InvalidHeaderName> {
+    match standard_header_name(name) {
+        Some(name) => Ok(name),
+        None => HeaderName::from_bytes(name),
+    }
+}
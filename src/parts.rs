@@ -0,0 +1,347 @@
+//! Request and response heads.
+
+use crate::extensions::RecordedExtensions;
+use crate::{De, DeConfig, DeWith, Ser};
+use http::{request, response, HeaderMap, Request, Response};
+use hyper::{Method, StatusCode, Uri, Version};
+use serde::de::{self, DeserializeSeed, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Returns empty request parts, as they cannot be built directly.
+fn empty_request_parts() -> request::Parts {
+    Request::new(()).into_parts().0
+}
+
+/// Returns empty response parts, as they cannot be built directly.
+fn empty_response_parts() -> response::Parts {
+    Response::new(()).into_parts().0
+}
+
+/// Returns the next element of a sequence, which must be present.
+fn element<'de, T, V>(visitor: &mut V,
+                      index: usize,
+                      expected: &dyn de::Expected)
+                      -> Result<T, V::Error>
+    where T: Deserialize<'de>,
+          V: SeqAccess<'de>,
+{
+    visitor.next_element()?.ok_or_else(|| de::Error::invalid_length(index, expected))
+}
+
+/// Request parts are serialised as a `[method, uri, version, headers]`
+/// tuple. Their extensions are not serialised, unless a registry is set
+/// with `SerConfig::extensions`, in which case they are recorded as an
+/// additional trailing element.
+impl<'de> Deserialize<'de> for De<request::Parts> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        DeConfig::default().seed::<request::Parts>().deserialize(deserializer).map(De::new)
+    }
+}
+
+impl<'de, 'c> DeserializeSeed<'de> for DeWith<'c, request::Parts> {
+    type Value = request::Parts;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<request::Parts, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct PartsVisitor<'c> {
+            config: &'c DeConfig,
+        }
+
+        impl<'de, 'c> Visitor<'de> for PartsVisitor<'c> {
+            type Value = request::Parts;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "request parts")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let mut parts = empty_request_parts();
+                parts.method = element::<De<Method>, _>(&mut visitor, 0, &self)?.into_inner();
+                parts.uri = element::<De<Uri>, _>(&mut visitor, 1, &self)?.into_inner();
+                parts.version = element::<De<Version>, _>(&mut visitor, 2, &self)?.into_inner();
+                parts.headers = visitor.next_element_seed(self.config.seed::<HeaderMap>())?
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                if let Some(ref registry) = self.config.extensions {
+                    let recorded = element::<RecordedExtensions, _>(&mut visitor, 4, &self)?;
+                    registry.restore(&recorded, &mut parts.extensions)
+                        .map_err(de::Error::custom)?;
+                }
+                Ok(parts)
+            }
+        }
+
+        let len = 4 + self.config.extensions.is_some() as usize;
+        deserializer.deserialize_tuple(len, PartsVisitor { config: self.config })
+    }
+}
+
+impl<'a> Serialize for Ser<'a, request::Parts> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let recorded = self.config.extensions.as_ref().map(|registry| {
+            registry.extract(&self.v.extensions)
+        });
+        let len = 4 + recorded.is_some() as usize;
+        let mut serializer = serializer.serialize_tuple(len)?;
+        serializer.serialize_element(&Ser::with_config(&self.v.method, self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.uri, self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.version, self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.headers, self.config))?;
+        if let Some(ref recorded) = recorded {
+            serializer.serialize_element(recorded)?;
+        }
+        serializer.end()
+    }
+}
+
+/// Response parts are serialised as a `[status, version, headers]` tuple.
+/// Their extensions are not serialised, unless a registry is set with
+/// `SerConfig::extensions`, in which case they are recorded as an
+/// additional trailing element.
+impl<'de> Deserialize<'de> for De<response::Parts> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        DeConfig::default().seed::<response::Parts>().deserialize(deserializer).map(De::new)
+    }
+}
+
+impl<'de, 'c> DeserializeSeed<'de> for DeWith<'c, response::Parts> {
+    type Value = response::Parts;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<response::Parts, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct PartsVisitor<'c> {
+            config: &'c DeConfig,
+        }
+
+        impl<'de, 'c> Visitor<'de> for PartsVisitor<'c> {
+            type Value = response::Parts;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "response parts")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let mut parts = empty_response_parts();
+                parts.status = element::<De<StatusCode>, _>(&mut visitor, 0, &self)?.into_inner();
+                parts.version = element::<De<Version>, _>(&mut visitor, 1, &self)?.into_inner();
+                parts.headers = visitor.next_element_seed(self.config.seed::<HeaderMap>())?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                if let Some(ref registry) = self.config.extensions {
+                    let recorded = element::<RecordedExtensions, _>(&mut visitor, 3, &self)?;
+                    registry.restore(&recorded, &mut parts.extensions)
+                        .map_err(de::Error::custom)?;
+                }
+                Ok(parts)
+            }
+        }
+
+        let len = 3 + self.config.extensions.is_some() as usize;
+        deserializer.deserialize_tuple(len, PartsVisitor { config: self.config })
+    }
+}
+
+impl<'a> Serialize for Ser<'a, response::Parts> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let recorded = self.config.extensions.as_ref().map(|registry| {
+            registry.extract(&self.v.extensions)
+        });
+        let len = 3 + recorded.is_some() as usize;
+        let mut serializer = serializer.serialize_tuple(len)?;
+        serializer.serialize_element(&Ser::with_config(&self.v.status, self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.version, self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.headers, self.config))?;
+        if let Some(ref recorded) = recorded {
+            serializer.serialize_element(recorded)?;
+        }
+        serializer.end()
+    }
+}
+
+/// Requests are serialised as a `[method, uri, version, headers, body]`
+/// tuple. Like for request parts, their extensions are only recorded as an
+/// additional trailing element when a registry is set with
+/// `SerConfig::extensions`.
+impl<'de, B> Deserialize<'de> for De<Request<B>>
+    where B: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        DeConfig::default().seed::<Request<B>>().deserialize(deserializer).map(De::new)
+    }
+}
+
+impl<'de, 'c, B> DeserializeSeed<'de> for DeWith<'c, Request<B>>
+    where B: Deserialize<'de>,
+{
+    type Value = Request<B>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Request<B>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct RequestVisitor<'c, B> {
+            config: &'c DeConfig,
+            marker: PhantomData<fn() -> B>,
+        }
+
+        impl<'de, 'c, B> Visitor<'de> for RequestVisitor<'c, B>
+            where B: Deserialize<'de>,
+        {
+            type Value = Request<B>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a request")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let method = element::<De<Method>, _>(&mut visitor, 0, &self)?.into_inner();
+                let uri = element::<De<Uri>, _>(&mut visitor, 1, &self)?.into_inner();
+                let version = element::<De<Version>, _>(&mut visitor, 2, &self)?.into_inner();
+                let headers = visitor.next_element_seed(self.config.seed::<HeaderMap>())?
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                let body = element::<B, _>(&mut visitor, 4, &self)?;
+                let mut request = Request::new(body);
+                *request.method_mut() = method;
+                *request.uri_mut() = uri;
+                *request.version_mut() = version;
+                *request.headers_mut() = headers;
+                if let Some(ref registry) = self.config.extensions {
+                    let recorded = element::<RecordedExtensions, _>(&mut visitor, 5, &self)?;
+                    registry.restore(&recorded, request.extensions_mut())
+                        .map_err(de::Error::custom)?;
+                }
+                Ok(request)
+            }
+        }
+
+        let len = 5 + self.config.extensions.is_some() as usize;
+        let visitor = RequestVisitor { config: self.config, marker: PhantomData };
+        deserializer.deserialize_tuple(len, visitor)
+    }
+}
+
+impl<'a, B> Serialize for Ser<'a, Request<B>>
+    where B: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let recorded = self.config.extensions.as_ref().map(|registry| {
+            registry.extract(self.v.extensions())
+        });
+        let len = 5 + recorded.is_some() as usize;
+        let mut serializer = serializer.serialize_tuple(len)?;
+        serializer.serialize_element(&Ser::with_config(self.v.method(), self.config))?;
+        serializer.serialize_element(&Ser::with_config(self.v.uri(), self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.version(), self.config))?;
+        serializer.serialize_element(&Ser::with_config(self.v.headers(), self.config))?;
+        serializer.serialize_element(self.v.body())?;
+        if let Some(ref recorded) = recorded {
+            serializer.serialize_element(recorded)?;
+        }
+        serializer.end()
+    }
+}
+
+/// Responses are serialised as a `[status, version, headers, body]` tuple.
+/// Like for response parts, their extensions are only recorded as an
+/// additional trailing element when a registry is set with
+/// `SerConfig::extensions`.
+impl<'de, B> Deserialize<'de> for De<Response<B>>
+    where B: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        DeConfig::default().seed::<Response<B>>().deserialize(deserializer).map(De::new)
+    }
+}
+
+impl<'de, 'c, B> DeserializeSeed<'de> for DeWith<'c, Response<B>>
+    where B: Deserialize<'de>,
+{
+    type Value = Response<B>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Response<B>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct ResponseVisitor<'c, B> {
+            config: &'c DeConfig,
+            marker: PhantomData<fn() -> B>,
+        }
+
+        impl<'de, 'c, B> Visitor<'de> for ResponseVisitor<'c, B>
+            where B: Deserialize<'de>,
+        {
+            type Value = Response<B>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a response")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let status = element::<De<StatusCode>, _>(&mut visitor, 0, &self)?.into_inner();
+                let version = element::<De<Version>, _>(&mut visitor, 1, &self)?.into_inner();
+                let headers = visitor.next_element_seed(self.config.seed::<HeaderMap>())?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let body = element::<B, _>(&mut visitor, 3, &self)?;
+                let mut response = Response::new(body);
+                *response.status_mut() = status;
+                *response.version_mut() = version;
+                *response.headers_mut() = headers;
+                if let Some(ref registry) = self.config.extensions {
+                    let recorded = element::<RecordedExtensions, _>(&mut visitor, 4, &self)?;
+                    registry.restore(&recorded, response.extensions_mut())
+                        .map_err(de::Error::custom)?;
+                }
+                Ok(response)
+            }
+        }
+
+        let len = 4 + self.config.extensions.is_some() as usize;
+        let visitor = ResponseVisitor { config: self.config, marker: PhantomData };
+        deserializer.deserialize_tuple(len, visitor)
+    }
+}
+
+impl<'a, B> Serialize for Ser<'a, Response<B>>
+    where B: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let recorded = self.config.extensions.as_ref().map(|registry| {
+            registry.extract(self.v.extensions())
+        });
+        let len = 4 + recorded.is_some() as usize;
+        let mut serializer = serializer.serialize_tuple(len)?;
+        serializer.serialize_element(&Ser::with_config(&self.v.status(), self.config))?;
+        serializer.serialize_element(&Ser::with_config(&self.v.version(), self.config))?;
+        serializer.serialize_element(&Ser::with_config(self.v.headers(), self.config))?;
+        serializer.serialize_element(self.v.body())?;
+        if let Some(ref recorded) = recorded {
+            serializer.serialize_element(recorded)?;
+        }
+        serializer.end()
+    }
+}
@@ -0,0 +1,225 @@
+//! `Permissions-Policy` headers.
+//!
+//! See https://w3c.github.io/webappsec-permissions-policy/.
+
+use crate::sf::{self, BareItem, Item, Member};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a `Permissions-Policy` header is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidPermissionsPolicy {
+    reason: &'static str,
+}
+
+impl InvalidPermissionsPolicy {
+    fn new(reason: &'static str) -> Self {
+        InvalidPermissionsPolicy { reason }
+    }
+}
+
+impl fmt::Display for InvalidPermissionsPolicy {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid Permissions-Policy header: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidPermissionsPolicy {}
+
+/// An entry of the allowlist of a feature.
+///
+/// This is serialised as `"*"`, `"self"`, or the origin itself.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AllowlistEntry {
+    /// All origins, `*`.
+    All,
+    /// The origin of the document, `self`.
+    SelfOrigin,
+    /// A specific origin, such as `https://servo.org`.
+    Origin(String),
+}
+
+impl AllowlistEntry {
+    fn to_item(&self) -> Item {
+        Item::new(match *self {
+            AllowlistEntry::All => BareItem::Token("*".to_owned()),
+            AllowlistEntry::SelfOrigin => BareItem::Token("self".to_owned()),
+            AllowlistEntry::Origin(ref origin) => BareItem::String(origin.clone()),
+        })
+    }
+
+    fn from_item(item: &Item) -> Option<Self> {
+        match item.bare.as_token() {
+            Some("*") => Some(AllowlistEntry::All),
+            Some("self") => Some(AllowlistEntry::SelfOrigin),
+            Some(_) => None,
+            None => item.bare.as_str().map(|origin| AllowlistEntry::Origin(origin.to_owned())),
+        }
+    }
+}
+
+/// A parsed `Permissions-Policy` header, mapping features to their
+/// allowlists.
+///
+/// This is serialised as a map from feature names to sequences of
+/// allowlist entries, in order.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PermissionsPolicy {
+    directives: Vec<(String, Vec<AllowlistEntry>)>,
+}
+
+impl PermissionsPolicy {
+    /// Returns the number of policy directives.
+    pub fn len(&self) -> usize {
+        self.directives.len()
+    }
+
+    /// Returns whether there are no policy directives.
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// Returns the allowlist of `feature`, if it has a policy directive.
+    ///
+    /// An empty allowlist disables the feature for all origins.
+    pub fn get(&self, feature: &str) -> Option<&[AllowlistEntry]> {
+        self.iter().find(|&(f, _)| f == feature).map(|(_, allowlist)| allowlist)
+    }
+
+    /// Returns an iterator over the features and their allowlists.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[AllowlistEntry])> {
+        self.directives.iter().map(|(f, allowlist)| (&**f, &**allowlist))
+    }
+
+    /// Sets the allowlist of `feature`, replacing the previous one if any.
+    pub fn insert(&mut self,
+                  feature: String,
+                  allowlist: Vec<AllowlistEntry>)
+                  -> Result<(), InvalidPermissionsPolicy>
+    {
+        if !sf::is_key(&feature) {
+            return Err(InvalidPermissionsPolicy::new("invalid feature name"));
+        }
+        let valid_origins = allowlist.iter().all(|entry| match *entry {
+            AllowlistEntry::Origin(ref origin) => sf::is_string(origin),
+            _ => true,
+        });
+        if !valid_origins {
+            return Err(InvalidPermissionsPolicy::new("invalid origin"));
+        }
+        match self.directives.iter_mut().find(|(f, _)| *f == feature) {
+            Some(directive) => directive.1 = allowlist,
+            None => self.directives.push((feature, allowlist)),
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PermissionsPolicy {
+    type Err = InvalidPermissionsPolicy;
+
+    /// Parses a policy, ignoring the allowlist entries which are neither
+    /// `*`, `self` nor strings, as required by the specification.
+    fn from_str(s: &str) -> Result<Self, InvalidPermissionsPolicy> {
+        let dictionary = sf::parse_dictionary(s)
+            .map_err(|_| InvalidPermissionsPolicy::new("not a dictionary"))?;
+        let directives = dictionary.into_iter().map(|(feature, member)| {
+            let allowlist = match member {
+                Member::Item(ref item) => AllowlistEntry::from_item(item).into_iter().collect(),
+                Member::InnerList(ref items, _) => {
+                    items.iter().filter_map(AllowlistEntry::from_item).collect()
+                },
+            };
+            (feature, allowlist)
+        }).collect();
+        Ok(PermissionsPolicy { directives })
+    }
+}
+
+impl fmt::Display for PermissionsPolicy {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let dictionary = self.directives.iter().map(|(feature, allowlist)| {
+            let member = match allowlist[..] {
+                [ref entry] => Member::Item(entry.to_item()),
+                _ => Member::InnerList(allowlist.iter().map(AllowlistEntry::to_item).collect(),
+                                       vec![]),
+            };
+            (feature.clone(), member)
+        }).collect::<Vec<_>>();
+        sf::Dictionary(&dictionary).fmt(formatter)
+    }
+}
+
+impl<'de> Deserialize<'de> for AllowlistEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let entry = String::deserialize(deserializer)?;
+        Ok(match &*entry {
+            "*" => AllowlistEntry::All,
+            "self" => AllowlistEntry::SelfOrigin,
+            _ => AllowlistEntry::Origin(entry),
+        })
+    }
+}
+
+impl Serialize for AllowlistEntry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(match *self {
+            AllowlistEntry::All => "*",
+            AllowlistEntry::SelfOrigin => "self",
+            AllowlistEntry::Origin(ref origin) => origin,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for PermissionsPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct PolicyVisitor;
+
+        impl<'de> Visitor<'de> for PolicyVisitor {
+            type Value = PermissionsPolicy;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a map from features to allowlists")
+            }
+
+            fn visit_map<V>(self, mut visitor: V) -> Result<PermissionsPolicy, V::Error>
+                where V: MapAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut policy = PermissionsPolicy {
+                    directives: Vec::with_capacity(capacity),
+                };
+                while let Some((feature, allowlist)) = visitor.next_entry()? {
+                    policy.insert(feature, allowlist).map_err(de::Error::custom)?;
+                }
+                Ok(policy)
+            }
+        }
+
+        deserializer.deserialize_map(PolicyVisitor)
+    }
+}
+
+impl Serialize for PermissionsPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut serializer = serializer.serialize_map(Some(self.directives.len()))?;
+        for (feature, allowlist) in &self.directives {
+            serializer.serialize_entry(feature, allowlist)?;
+        }
+        serializer.end()
+    }
+}
@@ -0,0 +1,101 @@
+//! HTTP/2 server pushes.
+
+use crate::{De, Ser};
+use http::HeaderMap;
+use hyper::{Method, StatusCode, Uri};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_bytes::{ByteBuf, Bytes};
+
+/// A `PUSH_PROMISE` frame received during an HTTP/2 session, along with the
+/// response which got pushed, if any.
+///
+/// This is serialised as a `[method, uri, headers, response]` tuple, where
+/// `uri` combines the `:scheme`, `:authority` and `:path` pseudo-headers and
+/// `response` may be null. Deserialisation fails if the promised request is
+/// not a `GET` or `HEAD` request with an absolute URI, as required by the
+/// HTTP/2 specification.
+#[derive(Clone, Debug)]
+pub struct PushPromise {
+    /// The `:method` pseudo-header of the promised request.
+    pub method: Method,
+    /// The URI of the promised request.
+    pub uri: Uri,
+    /// The regular headers of the promised request.
+    pub headers: HeaderMap,
+    /// The pushed response.
+    pub response: Option<PushedResponse>,
+}
+
+/// A response pushed by a server, with its buffered body.
+///
+/// This is serialised as a `[status, headers, body]` tuple.
+#[derive(Clone, Debug)]
+pub struct PushedResponse {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl<'de> Deserialize<'de> for PushPromise {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (method, uri, headers, response) =
+            <(De<Method>, De<Uri>, De<HeaderMap>, Option<PushedResponse>)>::deserialize(
+                deserializer)?;
+        let (method, uri) = (method.into_inner(), uri.into_inner());
+        if method != Method::GET && method != Method::HEAD {
+            return Err(D::Error::custom(format_args!(
+                "cannot push a response to a {} request", method)));
+        }
+        if uri.scheme().is_none() || uri.authority().is_none() {
+            return Err(D::Error::custom(format_args!(
+                "promised request URI {:?} is not absolute", uri)));
+        }
+        Ok(PushPromise {
+            method,
+            uri,
+            headers: headers.into_inner(),
+            response,
+        })
+    }
+}
+
+impl Serialize for PushPromise {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (Ser::new(&self.method),
+         Ser::new(&self.uri),
+         Ser::new(&self.headers),
+         &self.response).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PushedResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (status, headers, body) =
+            <(De<StatusCode>, De<HeaderMap>, ByteBuf)>::deserialize(deserializer)?;
+        Ok(PushedResponse {
+            status: status.into_inner(),
+            headers: headers.into_inner(),
+            body: body.into_vec(),
+        })
+    }
+}
+
+impl Serialize for PushedResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (Ser::new(&self.status),
+         Ser::new(&self.headers),
+         Bytes::new(&self.body)).serialize(serializer)
+    }
+}
@@ -0,0 +1,174 @@
+//! RateLimit header fields.
+//!
+//! See https://datatracker.ietf.org/doc/draft-ietf-httpapi-ratelimit-headers/.
+
+use crate::sf::{self, BareItem, Item, Member};
+use http::HeaderMap;
+use hyper::header::{HeaderName, HeaderValue};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The `RateLimit-Limit` header name.
+pub static RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+
+/// The `RateLimit-Remaining` header name.
+pub static RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+
+/// The `RateLimit-Reset` header name.
+pub static RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// The `RateLimit` header name, used by the combined form.
+pub static RATELIMIT: HeaderName = HeaderName::from_static("ratelimit");
+
+/// An error returned when RateLimit header fields are invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidRateLimit {
+    name: &'static str,
+}
+
+impl fmt::Display for InvalidRateLimit {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid {} header", self.name)
+    }
+}
+
+impl error::Error for InvalidRateLimit {}
+
+/// The quota state advertised by a server through the RateLimit header
+/// fields.
+///
+/// This is serialised as a `[limit, remaining, reset]` tuple. When formatted
+/// as header values, numbers are clamped to 999 999 999 999 999, the largest
+/// integer of structured fields.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RateLimit {
+    /// The number of requests allowed in the current time window.
+    pub limit: u64,
+    /// The number of requests left in the current time window.
+    pub remaining: u64,
+    /// The number of seconds until the quota resets.
+    pub reset: u64,
+}
+
+impl RateLimit {
+    /// Returns the quota state advertised by `headers`, if any.
+    ///
+    /// The combined `RateLimit` header is looked at first, and otherwise
+    /// all of `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset`
+    /// must be present. The quota policies which may follow the limit in
+    /// `RateLimit-Limit` are ignored.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Option<Self>, InvalidRateLimit> {
+        if let Some(value) = headers.get(&RATELIMIT) {
+            let invalid = InvalidRateLimit { name: "RateLimit" };
+            let value = value.to_str().map_err(|_| invalid.clone())?;
+            return value.parse().map(Some).map_err(|_| invalid);
+        }
+        let limit = headers.get(&RATELIMIT_LIMIT);
+        let remaining = headers.get(&RATELIMIT_REMAINING);
+        let reset = headers.get(&RATELIMIT_RESET);
+        let (limit, remaining, reset) = match (limit, remaining, reset) {
+            (Some(limit), Some(remaining), Some(reset)) => (limit, remaining, reset),
+            (None, None, None) => return Ok(None),
+            _ => return Err(InvalidRateLimit { name: "RateLimit-*" }),
+        };
+        Ok(Some(RateLimit {
+            limit: parse_limit(limit).ok_or(InvalidRateLimit { name: "RateLimit-Limit" })?,
+            remaining: parse_integer(remaining)
+                .ok_or(InvalidRateLimit { name: "RateLimit-Remaining" })?,
+            reset: parse_integer(reset).ok_or(InvalidRateLimit { name: "RateLimit-Reset" })?,
+        }))
+    }
+
+    /// Inserts the `RateLimit-Limit`, `RateLimit-Remaining` and
+    /// `RateLimit-Reset` headers in `headers`.
+    pub fn insert_into(&self, headers: &mut HeaderMap) {
+        headers.insert(RATELIMIT_LIMIT.clone(), HeaderValue::from(clamp(self.limit)));
+        headers.insert(RATELIMIT_REMAINING.clone(), HeaderValue::from(clamp(self.remaining)));
+        headers.insert(RATELIMIT_RESET.clone(), HeaderValue::from(clamp(self.reset)));
+    }
+}
+
+/// Clamps `i` to the range of structured field integers.
+fn clamp(i: u64) -> i64 {
+    i64::try_from(i).map_or(sf::MAX_INTEGER, |i| i.min(sf::MAX_INTEGER))
+}
+
+fn non_negative(item: &BareItem) -> Option<u64> {
+    item.as_integer().and_then(|i| u64::try_from(i).ok())
+}
+
+fn parse_integer(value: &HeaderValue) -> Option<u64> {
+    sf::parse_item(value.to_str().ok()?).ok().and_then(|item| non_negative(&item.bare))
+}
+
+fn parse_limit(value: &HeaderValue) -> Option<u64> {
+    match sf::parse_list(value.to_str().ok()?).ok()?.first()? {
+        Member::Item(item) => non_negative(&item.bare),
+        Member::InnerList(..) => None,
+    }
+}
+
+/// An error returned when parsing the combined form fails.
+#[derive(Clone, Copy, Debug)]
+pub struct ParseRateLimitError(());
+
+impl fmt::Display for ParseRateLimitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("invalid RateLimit header")
+    }
+}
+
+impl error::Error for ParseRateLimitError {}
+
+impl FromStr for RateLimit {
+    type Err = ParseRateLimitError;
+
+    /// Parses the combined form, a dictionary such as
+    /// `limit=100, remaining=50, reset=30`.
+    fn from_str(s: &str) -> Result<Self, ParseRateLimitError> {
+        let error = ParseRateLimitError(());
+        let dictionary = sf::parse_dictionary(s).map_err(|_| error)?;
+        let get = |key| {
+            dictionary.iter().find(|(k, _)| k == key).and_then(|(_, member)| match member {
+                Member::Item(item) => non_negative(&item.bare),
+                Member::InnerList(..) => None,
+            })
+        };
+        Ok(RateLimit {
+            limit: get("limit").ok_or(error)?,
+            remaining: get("remaining").ok_or(error)?,
+            reset: get("reset").ok_or(error)?,
+        })
+    }
+}
+
+impl fmt::Display for RateLimit {
+    /// Formats the combined form.
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let integer = |i: u64| Member::Item(Item::new(BareItem::Integer(clamp(i))));
+        let dictionary = [("limit".to_owned(), integer(self.limit)),
+                          ("remaining".to_owned(), integer(self.remaining)),
+                          ("reset".to_owned(), integer(self.reset))];
+        sf::Dictionary(&dictionary).fmt(formatter)
+    }
+}
+
+impl<'de> Deserialize<'de> for RateLimit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (limit, remaining, reset) = Deserialize::deserialize(deserializer)?;
+        Ok(RateLimit { limit, remaining, reset })
+    }
+}
+
+impl Serialize for RateLimit {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (self.limit, self.remaining, self.reset).serialize(serializer)
+    }
+}
@@ -0,0 +1,119 @@
+//! Header maps whose names and values are not validated yet.
+
+use crate::RawValues;
+use crate::names::parse_header_name;
+use http::HeaderMap;
+use hyper::header::HeaderValue;
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_bytes::Bytes;
+use std::cmp;
+use std::fmt;
+
+/// Headers deserialised without being validated.
+///
+/// This uses the same representation as `HeaderMap`, but header names and
+/// values are kept as they were deserialised, and no `HeaderName` or
+/// `HeaderValue` gets constructed until `parse` is called. This saves the
+/// validation cost when headers are only forwarded or counted.
+///
+/// Serialising `RawHeaders` outputs them as they were deserialised, so an
+/// invalid header is forwarded as is.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RawHeaders {
+    entries: Vec<(String, Vec<Vec<u8>>)>,
+}
+
+impl RawHeaders {
+    /// Returns the number of header values.
+    ///
+    /// Like `HeaderMap::len`, this counts each value of a header separately.
+    pub fn len(&self) -> usize {
+        self.entries.iter().map(|(_, values)| values.len()).sum()
+    }
+
+    /// Returns whether there are no header values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the header names and their values.
+    ///
+    /// Names are given as they were deserialised, without being lowercased.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Vec<u8>])> {
+        self.entries.iter().map(|(name, values)| (&**name, &**values))
+    }
+
+    /// Validates the headers, returning them as a `HeaderMap`.
+    pub fn parse(&self) -> Result<HeaderMap, http::Error> {
+        let mut headers = HeaderMap::with_capacity(self.entries.len());
+        for (name, values) in &self.entries {
+            let name = parse_header_name(name.as_bytes())?;
+            for v in values {
+                headers.append(name.clone(), HeaderValue::from_bytes(v)?);
+            }
+        }
+        Ok(headers)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawHeaders {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct RawHeadersVisitor;
+
+        impl<'de> Visitor<'de> for RawHeadersVisitor {
+            type Value = RawHeaders;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a map from header names to header values")
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Ok(RawHeaders::default())
+            }
+
+            fn visit_map<V>(self,
+                            mut visitor: V)
+                            -> Result<Self::Value, V::Error>
+                where V: MapAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut entries = Vec::with_capacity(capacity);
+                while let Some((k, values)) = visitor.next_entry::<String, RawValues>()? {
+                    entries.push((k, values.0));
+                }
+                Ok(RawHeaders { entries })
+            }
+        }
+
+        deserializer.deserialize_map(RawHeadersVisitor)
+    }
+}
+
+impl Serialize for RawHeaders {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        struct Value<'headers>(&'headers [Vec<u8>]);
+
+        impl<'headers> Serialize for Value<'headers> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer,
+            {
+                serializer.collect_seq(self.0.iter().map(|v| Bytes::new(v)))
+            }
+        }
+
+        let mut serializer = serializer.serialize_map(Some(self.entries.len()))?;
+        for (name, values) in &self.entries {
+            serializer.serialize_entry(name, &Value(values))?;
+        }
+        serializer.end()
+    }
+}
@@ -0,0 +1,87 @@
+//! Conversions from and to the types of reqwest, to record its traffic as
+//! fixtures and to replay them.
+//!
+//! reqwest re-exports the `http` types this crate supports, so its
+//! `HeaderMap`, `StatusCode`, `Method` and `Version` values can be used with
+//! `Ser` and `De` directly. What is left are its responses, whose metadata
+//! can be taken apart as `response::Parts`, and its cookies, which are not
+//! the `cookie` crate's.
+
+use cookie::time::{Duration, OffsetDateTime};
+use cookie::{Cookie, SameSite};
+use http::{response, Response};
+use reqwest::cookie::Jar;
+use reqwest::{Body, Url};
+use std::convert::TryFrom;
+use std::time::UNIX_EPOCH;
+
+/// Returns the status, version and headers of a reqwest response, which
+/// can be serialised with `Ser`.
+///
+/// The extensions of the returned parts are empty.
+pub fn reqwest_response_parts(response: &reqwest::Response) -> response::Parts {
+    let (mut parts, ()) = Response::new(()).into_parts();
+    parts.status = response.status();
+    parts.version = response.version();
+    parts.headers = response.headers().clone();
+    parts
+}
+
+/// Returns a reqwest response made of recorded parts and a body, e.g. to
+/// feed a fixture to code written against reqwest.
+///
+/// As there is no way to set it, the URL of the response is a placeholder.
+pub fn into_reqwest_response<B>(parts: response::Parts, body: B) -> reqwest::Response
+    where B: Into<Body>,
+{
+    reqwest::Response::from(Response::from_parts(parts, body))
+}
+
+/// Returns the cookies set by a reqwest response, skipping the invalid
+/// ones.
+pub fn reqwest_response_cookies(response: &reqwest::Response) -> Vec<Cookie<'static>> {
+    response.cookies().map(|cookie| cookie_from_reqwest(&cookie)).collect()
+}
+
+/// Converts a reqwest cookie to a `Cookie`, which can be serialised with
+/// `Ser`.
+///
+/// Expiration dates which cannot be represented are dropped.
+pub fn cookie_from_reqwest(cookie: &reqwest::cookie::Cookie) -> Cookie<'static> {
+    let mut builder = Cookie::build((cookie.name().to_owned(), cookie.value().to_owned()))
+        .http_only(cookie.http_only())
+        .secure(cookie.secure());
+    if cookie.same_site_strict() {
+        builder = builder.same_site(SameSite::Strict);
+    } else if cookie.same_site_lax() {
+        builder = builder.same_site(SameSite::Lax);
+    }
+    if let Some(path) = cookie.path() {
+        builder = builder.path(path.to_owned());
+    }
+    if let Some(domain) = cookie.domain() {
+        builder = builder.domain(domain.to_owned());
+    }
+    if let Some(max_age) = cookie.max_age() {
+        let max_age = Duration::try_from(max_age).unwrap_or(Duration::MAX);
+        builder = builder.max_age(max_age);
+    }
+    if let Some(expires) = cookie.expires() {
+        // Dates before the Unix epoch are replaced by it, as the cookie has
+        // expired either way.
+        let secs = expires.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        let expires = i64::try_from(secs)
+            .ok()
+            .and_then(|secs| OffsetDateTime::from_unix_timestamp(secs).ok());
+        if let Some(expires) = expires {
+            builder = builder.expires(expires);
+        }
+    }
+    builder.build()
+}
+
+/// Adds a recorded cookie to a reqwest cookie jar, as if it had been set by
+/// a response from `url`.
+pub fn add_to_reqwest_jar(jar: &Jar, cookie: &Cookie, url: &Url) {
+    jar.add_cookie_str(&cookie.to_string(), url);
+}
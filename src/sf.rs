@@ -0,0 +1,478 @@
+//! Structured Field Values for HTTP, as defined by RFC 8941.
+//!
+//! This only implements what the typed headers of this crate need to parse
+//! and emit their values.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::fmt::{self, Write};
+
+/// The largest integer, as integers have at most 15 digits.
+pub(crate) const MAX_INTEGER: i64 = 999_999_999_999_999;
+
+/// A bare item.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum BareItem {
+    Integer(i64),
+    Decimal(f64),
+    String(String),
+    Token(String),
+    ByteSequence(Vec<u8>),
+    Boolean(bool),
+}
+
+impl BareItem {
+    pub(crate) fn as_integer(&self) -> Option<i64> {
+        match *self {
+            BareItem::Integer(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match *self {
+            BareItem::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_token(&self) -> Option<&str> {
+        match *self {
+            BareItem::Token(ref t) => Some(t),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match *self {
+            BareItem::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) type Parameters = Vec<(String, BareItem)>;
+
+/// An item with its parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Item {
+    pub(crate) bare: BareItem,
+    pub(crate) params: Parameters,
+}
+
+impl Item {
+    pub(crate) fn new(bare: BareItem) -> Self {
+        Item { bare, params: vec![] }
+    }
+}
+
+/// A member of a list or a dictionary.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Member {
+    Item(Item),
+    InnerList(Vec<Item>, Parameters),
+}
+
+/// The error returned when a structured field is invalid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Invalid;
+
+type Result<T> = std::result::Result<T, Invalid>;
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser { input: input.as_bytes(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_sp(&mut self) {
+        while self.eat(b' ') {}
+    }
+
+    fn skip_ows(&mut self) {
+        while self.eat(b' ') || self.eat(b'\t') {}
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    /// Parses the whole input with `f`, surrounded by optional spaces.
+    fn top<T>(mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.skip_sp();
+        let value = f(&mut self)?;
+        self.skip_sp();
+        if self.at_end() { Ok(value) } else { Err(Invalid) }
+    }
+
+    /// Calls `f` for each comma-separated member, allowing an empty input.
+    fn members(&mut self, mut f: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        while !self.at_end() {
+            f(self)?;
+            self.skip_ows();
+            if self.at_end() {
+                break;
+            }
+            if !self.eat(b',') {
+                return Err(Invalid);
+            }
+            self.skip_ows();
+            if self.at_end() {
+                return Err(Invalid);
+            }
+        }
+        Ok(())
+    }
+
+    fn member(&mut self) -> Result<Member> {
+        if self.peek() == Some(b'(') {
+            self.inner_list()
+        } else {
+            self.item().map(Member::Item)
+        }
+    }
+
+    fn inner_list(&mut self) -> Result<Member> {
+        self.pos += 1;
+        let mut items = vec![];
+        loop {
+            self.skip_sp();
+            if self.eat(b')') {
+                return Ok(Member::InnerList(items, self.parameters()?));
+            }
+            items.push(self.item()?);
+            match self.peek() {
+                Some(b' ') | Some(b')') => {},
+                _ => return Err(Invalid),
+            }
+        }
+    }
+
+    fn item(&mut self) -> Result<Item> {
+        Ok(Item {
+            bare: self.bare_item()?,
+            params: self.parameters()?,
+        })
+    }
+
+    fn parameters(&mut self) -> Result<Parameters> {
+        let mut params = Parameters::new();
+        while self.eat(b';') {
+            self.skip_sp();
+            let key = self.key()?;
+            let value = if self.eat(b'=') {
+                self.bare_item()?
+            } else {
+                BareItem::Boolean(true)
+            };
+            insert(&mut params, key, value);
+        }
+        Ok(params)
+    }
+
+    fn key(&mut self) -> Result<String> {
+        let start = self.pos;
+        match self.peek() {
+            Some(b'a'..=b'z') | Some(b'*') => self.pos += 1,
+            _ => return Err(Invalid),
+        }
+        while let Some(b'a'..=b'z') | Some(b'0'..=b'9') | Some(b'_' | b'-' | b'.' | b'*') =
+            self.peek()
+        {
+            self.pos += 1;
+        }
+        Ok(self.slice(start).to_owned())
+    }
+
+    fn bare_item(&mut self) -> Result<BareItem> {
+        match self.peek().ok_or(Invalid)? {
+            b'-' | b'0'..=b'9' => self.number(),
+            b'"' => self.string(),
+            b':' => self.byte_sequence(),
+            b'?' => {
+                self.pos += 1;
+                let value = match self.peek() {
+                    Some(b'0') => false,
+                    Some(b'1') => true,
+                    _ => return Err(Invalid),
+                };
+                self.pos += 1;
+                Ok(BareItem::Boolean(value))
+            },
+            b'*' | b'a'..=b'z' | b'A'..=b'Z' => Ok(BareItem::Token(self.token())),
+            _ => Err(Invalid),
+        }
+    }
+
+    fn number(&mut self) -> Result<BareItem> {
+        let start = self.pos;
+        self.eat(b'-');
+        let digits = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        let integer_len = self.pos - digits;
+        if integer_len == 0 {
+            return Err(Invalid);
+        }
+        if !self.eat(b'.') {
+            if integer_len > 15 {
+                return Err(Invalid);
+            }
+            return self.slice(start).parse().map(BareItem::Integer).map_err(|_| Invalid);
+        }
+        let fraction = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        let fraction_len = self.pos - fraction;
+        if integer_len > 12 || fraction_len == 0 || fraction_len > 3 {
+            return Err(Invalid);
+        }
+        self.slice(start).parse().map(BareItem::Decimal).map_err(|_| Invalid)
+    }
+
+    fn string(&mut self) -> Result<BareItem> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            match self.peek().ok_or(Invalid)? {
+                b'"' => {
+                    self.pos += 1;
+                    return Ok(BareItem::String(s));
+                },
+                b'\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c @ b'"') | Some(c @ b'\\') => s.push(c as char),
+                        _ => return Err(Invalid),
+                    }
+                },
+                c @ b' '..=b'~' => s.push(c as char),
+                _ => return Err(Invalid),
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn token(&mut self) -> String {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(b) = self.peek() {
+            if !(is_tchar(b) || b == b':' || b == b'/') {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.slice(start).to_owned()
+    }
+
+    fn byte_sequence(&mut self) -> Result<BareItem> {
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            if b == b':' {
+                let bytes = STANDARD.decode(self.slice(start)).map_err(|_| Invalid)?;
+                self.pos += 1;
+                return Ok(BareItem::ByteSequence(bytes));
+            }
+            if !(b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'=') {
+                break;
+            }
+            self.pos += 1;
+        }
+        Err(Invalid)
+    }
+
+    fn slice(&self, start: usize) -> &'a str {
+        // Only ASCII bytes are consumed by the functions calling this.
+        std::str::from_utf8(&self.input[start..self.pos]).unwrap()
+    }
+}
+
+/// Inserts a dictionary member or parameter, overwriting the previous value
+/// with the same key, as required by the specification.
+fn insert<T>(entries: &mut Vec<(String, T)>, key: String, value: T) {
+    match entries.iter_mut().find(|(k, _)| *k == key) {
+        Some(entry) => entry.1 = value,
+        None => entries.push((key, value)),
+    }
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// Returns whether `s` can be serialised as a key.
+pub(crate) fn is_key(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    bytes.next().is_some_and(|b| b.is_ascii_lowercase() || b == b'*') &&
+        bytes.all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b)
+        })
+}
+
+/// Returns whether `s` can be serialised as a string.
+pub(crate) fn is_string(s: &str) -> bool {
+    s.bytes().all(|b| (b' '..=b'~').contains(&b))
+}
+
+/// Parses a list.
+pub(crate) fn parse_list(input: &str) -> Result<Vec<Member>> {
+    Parser::new(input).top(|parser| {
+        let mut members = vec![];
+        parser.members(|parser| {
+            members.push(parser.member()?);
+            Ok(())
+        })?;
+        Ok(members)
+    })
+}
+
+/// Parses a dictionary.
+pub(crate) fn parse_dictionary(input: &str) -> Result<Vec<(String, Member)>> {
+    Parser::new(input).top(|parser| {
+        let mut members = vec![];
+        parser.members(|parser| {
+            let key = parser.key()?;
+            let member = if parser.eat(b'=') {
+                parser.member()?
+            } else {
+                Member::Item(Item {
+                    bare: BareItem::Boolean(true),
+                    params: parser.parameters()?,
+                })
+            };
+            insert(&mut members, key, member);
+            Ok(())
+        })?;
+        Ok(members)
+    })
+}
+
+/// Parses an item.
+pub(crate) fn parse_item(input: &str) -> Result<Item> {
+    Parser::new(input).top(Parser::item)
+}
+
+impl fmt::Display for BareItem {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BareItem::Integer(i) => write!(formatter, "{}", i),
+            BareItem::Decimal(d) => {
+                let d = (d * 1000.).round() / 1000.;
+                if d.fract() == 0. {
+                    write!(formatter, "{:.1}", d)
+                } else {
+                    write!(formatter, "{}", d)
+                }
+            },
+            BareItem::String(ref s) => {
+                formatter.write_char('"')?;
+                for c in s.chars() {
+                    if c == '"' || c == '\\' {
+                        formatter.write_char('\\')?;
+                    }
+                    formatter.write_char(c)?;
+                }
+                formatter.write_char('"')
+            },
+            BareItem::Token(ref t) => formatter.write_str(t),
+            BareItem::ByteSequence(ref bytes) => {
+                write!(formatter, ":{}:", STANDARD.encode(bytes))
+            },
+            BareItem::Boolean(b) => write!(formatter, "?{}", b as u8),
+        }
+    }
+}
+
+struct Params<'a>(&'a Parameters);
+
+impl<'a> fmt::Display for Params<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (key, value) in self.0 {
+            write!(formatter, ";{}", key)?;
+            if *value != BareItem::Boolean(true) {
+                write!(formatter, "={}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}{}", self.bare, Params(&self.params))
+    }
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Member::Item(ref item) => item.fmt(formatter),
+            Member::InnerList(ref items, ref params) => {
+                formatter.write_char('(')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        formatter.write_char(' ')?;
+                    }
+                    item.fmt(formatter)?;
+                }
+                write!(formatter, "){}", Params(params))
+            },
+        }
+    }
+}
+
+/// Serialises a list.
+pub(crate) struct List<'a>(pub(crate) &'a [Member]);
+
+impl<'a> fmt::Display for List<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, member) in self.0.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            member.fmt(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialises a dictionary.
+pub(crate) struct Dictionary<'a>(pub(crate) &'a [(String, Member)]);
+
+impl<'a> fmt::Display for Dictionary<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (key, member)) in self.0.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            formatter.write_str(key)?;
+            match *member {
+                Member::Item(Item { bare: BareItem::Boolean(true), ref params }) => {
+                    Params(params).fmt(formatter)?;
+                },
+                _ => write!(formatter, "={}", member)?,
+            }
+        }
+        Ok(())
+    }
+}
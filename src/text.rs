@@ -0,0 +1,224 @@
+//! HTTP/1.1 request lines and status lines.
+//!
+//! See https://httpwg.org/specs/rfc9112.html#start.line.
+
+use crate::ascii::invalid_field_byte;
+use hyper::{Method, StatusCode, Uri, Version};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// An error returned when a request line or a status line is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidStartLine {
+    reason: &'static str,
+}
+
+impl InvalidStartLine {
+    fn new(reason: &'static str) -> Self {
+        InvalidStartLine { reason }
+    }
+}
+
+impl fmt::Display for InvalidStartLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid start line: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidStartLine {}
+
+/// Parses an HTTP version such as `HTTP/1.1`.
+///
+/// HTTP/2 and HTTP/3 have no textual version, they are accepted both as
+/// `HTTP/2` and as `HTTP/2.0`, like `Version`'s `Debug` output.
+pub(crate) fn parse_version(s: &str) -> Option<Version> {
+    Some(match s {
+        "HTTP/0.9" => Version::HTTP_09,
+        "HTTP/1.0" => Version::HTTP_10,
+        "HTTP/1.1" => Version::HTTP_11,
+        "HTTP/2" | "HTTP/2.0" => Version::HTTP_2,
+        "HTTP/3" | "HTTP/3.0" => Version::HTTP_3,
+        _ => return None,
+    })
+}
+
+/// Returns the textual form of `version`, e.g. `HTTP/1.1`.
+pub(crate) fn version_str(version: Version) -> &'static str {
+    match version {
+        Version::HTTP_09 => "HTTP/0.9",
+        Version::HTTP_10 => "HTTP/1.0",
+        Version::HTTP_11 => "HTTP/1.1",
+        Version::HTTP_2 => "HTTP/2.0",
+        Version::HTTP_3 => "HTTP/3.0",
+        _ => unreachable!("unknown HTTP version {:?}", version),
+    }
+}
+
+/// A request line, such as `GET /path HTTP/1.1`.
+///
+/// This is serialised as the line itself, without its trailing CRLF.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestLine {
+    /// The request method.
+    pub method: Method,
+    /// The request target.
+    pub uri: Uri,
+    /// The HTTP version.
+    pub version: Version,
+}
+
+impl From<(Method, Uri, Version)> for RequestLine {
+    fn from((method, uri, version): (Method, Uri, Version)) -> Self {
+        RequestLine { method, uri, version }
+    }
+}
+
+impl From<RequestLine> for (Method, Uri, Version) {
+    fn from(line: RequestLine) -> Self {
+        (line.method, line.uri, line.version)
+    }
+}
+
+impl FromStr for RequestLine {
+    type Err = InvalidStartLine;
+
+    fn from_str(s: &str) -> Result<Self, InvalidStartLine> {
+        let mut parts = s.split(' ');
+        let (method, uri, version) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(uri), Some(version)) if parts.next().is_none() => {
+                (method, uri, version)
+            },
+            _ => return Err(InvalidStartLine::new("expected three parts")),
+        };
+        Ok(RequestLine {
+            method: method.parse().map_err(|_| InvalidStartLine::new("invalid method"))?,
+            uri: uri.parse().map_err(|_| InvalidStartLine::new("invalid request target"))?,
+            version: parse_version(version)
+                .ok_or_else(|| InvalidStartLine::new("invalid HTTP version"))?,
+        })
+    }
+}
+
+impl fmt::Display for RequestLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} {} {}", self.method, self.uri, version_str(self.version))
+    }
+}
+
+/// A status line, such as `HTTP/1.1 200 OK`.
+///
+/// This is serialised as the line itself, without its trailing CRLF.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusLine {
+    /// The HTTP version.
+    pub version: Version,
+    /// The status code.
+    pub status: StatusCode,
+    /// The reason phrase, which may be empty.
+    pub reason: String,
+}
+
+impl From<(Version, StatusCode, String)> for StatusLine {
+    fn from((version, status, reason): (Version, StatusCode, String)) -> Self {
+        StatusLine { version, status, reason }
+    }
+}
+
+impl From<StatusLine> for (Version, StatusCode, String) {
+    fn from(line: StatusLine) -> Self {
+        (line.version, line.status, line.reason)
+    }
+}
+
+impl FromStr for StatusLine {
+    type Err = InvalidStartLine;
+
+    /// Parses a status line, where the space following the status code may
+    /// be omitted if the reason phrase is empty.
+    fn from_str(s: &str) -> Result<Self, InvalidStartLine> {
+        let mut parts = s.splitn(3, ' ');
+        let (version, status) = match (parts.next(), parts.next()) {
+            (Some(version), Some(status)) => (version, status),
+            _ => return Err(InvalidStartLine::new("expected a status code")),
+        };
+        let reason = parts.next().unwrap_or("");
+        if status.len() != 3 {
+            return Err(InvalidStartLine::new("invalid status code"));
+        }
+        if invalid_field_byte(reason.as_bytes()).is_some() {
+            return Err(InvalidStartLine::new("invalid reason phrase"));
+        }
+        Ok(StatusLine {
+            version: parse_version(version)
+                .ok_or_else(|| InvalidStartLine::new("invalid HTTP version"))?,
+            status: status.parse().map_err(|_| InvalidStartLine::new("invalid status code"))?,
+            reason: reason.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for StatusLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter,
+               "{} {} {}",
+               version_str(self.version),
+               self.status.as_str(),
+               self.reason)
+    }
+}
+
+struct LineVisitor<T>(&'static str, PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for LineVisitor<T>
+    where T: FromStr<Err = InvalidStartLine>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.0)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+        where E: de::Error,
+    {
+        v.parse().map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for RequestLine {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let visitor = LineVisitor("an HTTP request line", PhantomData);
+        deserializer.deserialize_str(visitor)
+    }
+}
+
+impl Serialize for RequestLine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusLine {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let visitor = LineVisitor("an HTTP status line", PhantomData);
+        deserializer.deserialize_str(visitor)
+    }
+}
+
+impl Serialize for StatusLine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
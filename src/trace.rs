@@ -0,0 +1,458 @@
+//! W3C Trace Context headers.
+//!
+//! See https://www.w3.org/TR/trace-context/.
+
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_bytes::{ByteBuf, Bytes};
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a Trace Context header is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidTraceContext {
+    reason: &'static str,
+}
+
+impl InvalidTraceContext {
+    fn new(reason: &'static str) -> Self {
+        InvalidTraceContext { reason }
+    }
+}
+
+impl fmt::Display for InvalidTraceContext {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid trace context: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidTraceContext {}
+
+/// A parsed `traceparent` header.
+///
+/// This is serialised as a structure with `version`, `trace_id`, `span_id`
+/// and `flags` fields. The identifiers are serialised as lowercase hex
+/// strings in human-readable formats and as bytes otherwise.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TraceParent {
+    /// The version of the header format.
+    pub version: u8,
+    /// The identifier of the whole trace.
+    pub trace_id: [u8; 16],
+    /// The identifier of the request in the trace, called `parent-id` by
+    /// the specification.
+    pub span_id: [u8; 8],
+    /// The trace flags.
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// The `sampled` trace flag.
+    pub const SAMPLED: u8 = 0x01;
+
+    /// Returns whether the `sampled` flag is set.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & Self::SAMPLED != 0
+    }
+
+    fn validate(self) -> Result<Self, InvalidTraceContext> {
+        if self.version == 0xff {
+            return Err(InvalidTraceContext::new("version ff is forbidden"));
+        }
+        if self.trace_id == [0; 16] {
+            return Err(InvalidTraceContext::new("trace-id is all zeroes"));
+        }
+        if self.span_id == [0; 8] {
+            return Err(InvalidTraceContext::new("parent-id is all zeroes"));
+        }
+        Ok(self)
+    }
+}
+
+impl FromStr for TraceParent {
+    type Err = InvalidTraceContext;
+
+    fn from_str(s: &str) -> Result<Self, InvalidTraceContext> {
+        let mut parts = s.split('-');
+        let mut next = |reason| parts.next().ok_or(InvalidTraceContext::new(reason));
+        let version = next("missing version")?;
+        let trace_id = next("missing trace-id")?;
+        let span_id = next("missing parent-id")?;
+        let flags = next("missing trace-flags")?;
+        let rest = parts.next();
+        let mut parent = TraceParent {
+            version: 0,
+            trace_id: [0; 16],
+            span_id: [0; 8],
+            flags: 0,
+        };
+        decode_hex(version, std::slice::from_mut(&mut parent.version))
+            .ok_or(InvalidTraceContext::new("invalid version"))?;
+        decode_hex(trace_id, &mut parent.trace_id)
+            .ok_or(InvalidTraceContext::new("invalid trace-id"))?;
+        decode_hex(span_id, &mut parent.span_id)
+            .ok_or(InvalidTraceContext::new("invalid parent-id"))?;
+        decode_hex(flags, std::slice::from_mut(&mut parent.flags))
+            .ok_or(InvalidTraceContext::new("invalid trace-flags"))?;
+        // Future versions may append fields, but version 00 has exactly four.
+        if parent.version == 0 && rest.is_some() {
+            return Err(InvalidTraceContext::new("trailing data"));
+        }
+        parent.validate()
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter,
+               "{}-{}-{}-{}",
+               Hex(&[self.version]),
+               Hex(&self.trace_id),
+               Hex(&self.span_id),
+               Hex(&[self.flags]))
+    }
+}
+
+const TRACE_PARENT_FIELDS: &[&str] = &["version", "trace_id", "span_id", "flags"];
+
+impl<'de> Deserialize<'de> for TraceParent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        enum Field {
+            Version,
+            TraceId,
+            SpanId,
+            Flags,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        write!(formatter, "a traceparent field")
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Field, E>
+                        where E: de::Error,
+                    {
+                        match v {
+                            "version" => Ok(Field::Version),
+                            "trace_id" => Ok(Field::TraceId),
+                            "span_id" => Ok(Field::SpanId),
+                            "flags" => Ok(Field::Flags),
+                            _ => Err(E::unknown_field(v, TRACE_PARENT_FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct TraceParentVisitor;
+
+        impl<'de> Visitor<'de> for TraceParentVisitor {
+            type Value = TraceParent;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a traceparent structure")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<TraceParent, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                let missing = |i| de::Error::invalid_length(i, &self);
+                let version = visitor.next_element()?.ok_or_else(|| missing(0))?;
+                let trace_id = visitor.next_element::<Id<16>>()?.ok_or_else(|| missing(1))?;
+                let span_id = visitor.next_element::<Id<8>>()?.ok_or_else(|| missing(2))?;
+                let flags = visitor.next_element()?.ok_or_else(|| missing(3))?;
+                TraceParent { version, trace_id: trace_id.0, span_id: span_id.0, flags }
+                    .validate()
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut visitor: V) -> Result<TraceParent, V::Error>
+                where V: MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut trace_id = None;
+                let mut span_id = None;
+                let mut flags = None;
+                while let Some(field) = visitor.next_key()? {
+                    match field {
+                        Field::Version => version = Some(visitor.next_value()?),
+                        Field::TraceId => trace_id = Some(visitor.next_value::<Id<16>>()?.0),
+                        Field::SpanId => span_id = Some(visitor.next_value::<Id<8>>()?.0),
+                        Field::Flags => flags = Some(visitor.next_value()?),
+                    }
+                }
+                TraceParent {
+                    version: version.ok_or_else(|| de::Error::missing_field("version"))?,
+                    trace_id: trace_id.ok_or_else(|| de::Error::missing_field("trace_id"))?,
+                    span_id: span_id.ok_or_else(|| de::Error::missing_field("span_id"))?,
+                    flags: flags.ok_or_else(|| de::Error::missing_field("flags"))?,
+                }
+                .validate()
+                .map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_struct("TraceParent", TRACE_PARENT_FIELDS, TraceParentVisitor)
+    }
+}
+
+impl Serialize for TraceParent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut serializer = serializer.serialize_struct("TraceParent", 4)?;
+        serializer.serialize_field("version", &self.version)?;
+        serializer.serialize_field("trace_id", &Id(self.trace_id))?;
+        serializer.serialize_field("span_id", &Id(self.span_id))?;
+        serializer.serialize_field("flags", &self.flags)?;
+        serializer.end()
+    }
+}
+
+/// A trace or span identifier, as hex in human-readable formats.
+struct Id<const N: usize>([u8; N]);
+
+impl<'de, const N: usize> Deserialize<'de> for Id<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let mut id = [0; N];
+        if deserializer.is_human_readable() {
+            let hex = String::deserialize(deserializer)?;
+            decode_hex(&hex, &mut id)
+                .ok_or_else(|| de::Error::custom(format_args!(
+                    "expected {} lowercase hex digits", N * 2)))?;
+        } else {
+            let bytes = ByteBuf::deserialize(deserializer)?;
+            if bytes.len() != N {
+                return Err(de::Error::invalid_length(bytes.len(), &&*format!("{} bytes", N)));
+            }
+            id.copy_from_slice(&bytes);
+        }
+        Ok(Id(id))
+    }
+}
+
+impl<const N: usize> Serialize for Id<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Hex(&self.0))
+        } else {
+            serializer.serialize_bytes(Bytes::new(&self.0))
+        }
+    }
+}
+
+/// Decodes lowercase hex digits into `out`, which must be exactly filled.
+fn decode_hex(hex: &str, out: &mut [u8]) -> Option<()> {
+    fn digit(d: u8) -> Option<u8> {
+        match d {
+            b'0'..=b'9' => Some(d - b'0'),
+            b'a'..=b'f' => Some(d - b'a' + 10),
+            _ => None,
+        }
+    }
+
+    let hex = hex.as_bytes();
+    if hex.len() != out.len() * 2 {
+        return None;
+    }
+    for (byte, pair) in out.iter_mut().zip(hex.chunks(2)) {
+        *byte = digit(pair[0])? << 4 | digit(pair[1])?;
+    }
+    Some(())
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for Hex<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0 {
+            write!(formatter, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// A parsed `tracestate` header.
+///
+/// This is serialised as a map from the list members' keys to their values,
+/// in order.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TraceState {
+    entries: Vec<(String, String)>,
+}
+
+impl TraceState {
+    /// The maximum number of list members.
+    pub const MAX_ENTRIES: usize = 32;
+
+    /// Returns the number of list members.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no list members.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value associated with `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.iter().find(|&(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the keys and values of the list members.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (&**k, &**v))
+    }
+
+    /// Adds a list member, or moves it to the front if `key` was already
+    /// present, as required when a vendor updates its entry.
+    pub fn insert(&mut self,
+                  key: String,
+                  value: String)
+                  -> Result<(), InvalidTraceContext>
+    {
+        validate_member(&key, &value)?;
+        self.entries.retain(|(k, _)| *k != key);
+        if self.entries.len() == Self::MAX_ENTRIES {
+            return Err(InvalidTraceContext::new("too many list members"));
+        }
+        self.entries.insert(0, (key, value));
+        Ok(())
+    }
+
+    fn push(&mut self, key: String, value: String) -> Result<(), InvalidTraceContext> {
+        validate_member(&key, &value)?;
+        if self.get(&key).is_some() {
+            return Err(InvalidTraceContext::new("duplicate key"));
+        }
+        if self.entries.len() == Self::MAX_ENTRIES {
+            return Err(InvalidTraceContext::new("too many list members"));
+        }
+        self.entries.push((key, value));
+        Ok(())
+    }
+}
+
+fn validate_member(key: &str, value: &str) -> Result<(), InvalidTraceContext> {
+    fn is_simple_key(key: &[u8], max: usize, first: fn(u8) -> bool) -> bool {
+        key.first().copied().is_some_and(first) && key.len() <= max &&
+            key.iter().all(|&b| {
+                matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'*' | b'/')
+            })
+    }
+
+    let lowercase = |b: u8| b.is_ascii_lowercase();
+    let alphanumeric = |b: u8| b.is_ascii_lowercase() || b.is_ascii_digit();
+    let valid_key = match key.split_once('@') {
+        None => is_simple_key(key.as_bytes(), 256, lowercase),
+        Some((tenant, system)) => {
+            is_simple_key(tenant.as_bytes(), 241, alphanumeric) &&
+                is_simple_key(system.as_bytes(), 14, lowercase)
+        },
+    };
+    if !valid_key {
+        return Err(InvalidTraceContext::new("invalid list member key"));
+    }
+    let valid_value = !value.is_empty() && value.len() <= 256 &&
+        !value.ends_with(' ') &&
+        value.bytes().all(|b| (b' '..=b'~').contains(&b) && b != b',' && b != b'=');
+    if !valid_value {
+        return Err(InvalidTraceContext::new("invalid list member value"));
+    }
+    Ok(())
+}
+
+impl FromStr for TraceState {
+    type Err = InvalidTraceContext;
+
+    fn from_str(s: &str) -> Result<Self, InvalidTraceContext> {
+        let mut state = TraceState::default();
+        for member in s.split(',') {
+            let member = member.trim_matches(|c| c == ' ' || c == '\t');
+            if member.is_empty() {
+                continue;
+            }
+            let (key, value) = member
+                .split_once('=')
+                .ok_or(InvalidTraceContext::new("list member without a value"))?;
+            state.push(key.to_owned(), value.to_owned())?;
+        }
+        Ok(state)
+    }
+}
+
+impl fmt::Display for TraceState {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(",")?;
+            }
+            write!(formatter, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for TraceState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct TraceStateVisitor;
+
+        impl<'de> Visitor<'de> for TraceStateVisitor {
+            type Value = TraceState;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a map from tracestate keys to values")
+            }
+
+            fn visit_map<V>(self, mut visitor: V) -> Result<TraceState, V::Error>
+                where V: MapAccess<'de>,
+            {
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0),
+                                        TraceState::MAX_ENTRIES);
+                let mut state = TraceState {
+                    entries: Vec::with_capacity(capacity),
+                };
+                while let Some((key, value)) = visitor.next_entry()? {
+                    state.push(key, value).map_err(de::Error::custom)?;
+                }
+                Ok(state)
+            }
+        }
+
+        deserializer.deserialize_map(TraceStateVisitor)
+    }
+}
+
+impl Serialize for TraceState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut serializer = serializer.serialize_map(Some(self.entries.len()))?;
+        for (key, value) in &self.entries {
+            serializer.serialize_entry(key, value)?;
+        }
+        serializer.end()
+    }
+}
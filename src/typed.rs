@@ -0,0 +1,816 @@
+//! Typed headers of the `headers` crate.
+
+use crate::{De, Ser, REDACTED};
+use headers::{AcceptRanges, AccessControlAllowCredentials, AccessControlAllowHeaders};
+use headers::{AccessControlAllowMethods, AccessControlAllowOrigin, AccessControlExposeHeaders};
+use headers::{AccessControlMaxAge, AccessControlRequestHeaders, AccessControlRequestMethod, Age};
+use headers::{Allow, CacheControl, Connection, ContentEncoding, ContentLength, ContentLocation};
+use headers::{ContentRange, Date, ETag, Expect, Expires, Header, Host, IfMatch, IfModifiedSince};
+use headers::{IfNoneMatch, IfUnmodifiedSince, LastModified, Location, Origin, Pragma};
+use headers::{ProxyAuthorization, Range, Referer, RetryAfter, SecWebsocketAccept, SecWebsocketKey};
+use headers::{SecWebsocketVersion, Server, SetCookie, StrictTransportSecurity, Te};
+use headers::{TransferEncoding, Upgrade, UserAgent, Vary};
+use headers::authorization::{Authorization, Basic, Bearer};
+use http::uri::Authority;
+use http::{HeaderName, HeaderValue};
+use hyper::{Method, Uri};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::iter;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::str;
+use std::time::{Duration, SystemTime};
+
+/// Returns the string of the values of `header`, joined by commas.
+fn encode<H, E>(header: &H) -> Result<String, E>
+    where H: Header,
+          E: ser::Error,
+{
+    let mut values = Vec::<HeaderValue>::new();
+    header.encode(&mut values);
+    let values = values.iter()
+        .map(|v| str::from_utf8(v.as_bytes()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| E::custom(format_args!("non UTF-8 {} header", H::name())))?;
+    Ok(values.join(", "))
+}
+
+/// Parses a typed header from the string of its value.
+fn decode<H, E>(v: &str) -> Result<H, E>
+    where H: Header,
+          E: de::Error,
+{
+    HeaderValue::from_str(v)
+        .ok()
+        .and_then(|value| H::decode(&mut iter::once(&value)).ok())
+        .ok_or_else(|| E::custom(format_args!("invalid {} header {:?}", H::name(), v)))
+}
+
+/// Serialises `header` as the string of its values, joined by commas.
+fn serialize_header<H, S>(header: &H, serializer: S) -> Result<S::Ok, S::Error>
+    where H: Header,
+          S: Serializer,
+{
+    serializer.serialize_str(&encode::<H, S::Error>(header)?)
+}
+
+struct HeaderVisitor<H> {
+    /// The separator of the directives in the map form, if `H` has one.
+    separator: Option<&'static str>,
+    marker: PhantomData<H>,
+}
+
+impl<'de, H> Visitor<'de> for HeaderVisitor<H>
+    where H: Header,
+{
+    type Value = H;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a {} header value", H::name())?;
+        if self.separator.is_some() {
+            write!(formatter, " or a map of directives")?;
+        }
+        Ok(())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<H, E>
+        where E: de::Error,
+    {
+        decode(v)
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<H, V::Error>
+        where V: MapAccess<'de>,
+    {
+        let separator = match self.separator {
+            Some(separator) => separator,
+            None => return Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+        };
+        let mut directives = vec![];
+        while let Some(name) = visitor.next_key::<String>()? {
+            match visitor.next_value()? {
+                Directive::Flag(false) => {},
+                Directive::Flag(true) => directives.push(name),
+                Directive::Number(v) => directives.push(format!("{}={}", name, v)),
+                Directive::Str(v) => directives.push(format!("{}={}", name, v)),
+            }
+        }
+        decode(&directives.join(separator))
+    }
+}
+
+/// Deserialises a typed header from the string of its value.
+fn deserialize_header<'de, H, D>(deserializer: D) -> Result<H, D::Error>
+    where H: Header,
+          D: Deserializer<'de>,
+{
+    let visitor = HeaderVisitor { separator: None, marker: PhantomData };
+    deserializer.deserialize_str(visitor)
+}
+
+/// The value of a directive in the map form of a typed header.
+enum Directive<'a> {
+    Flag(bool),
+    Number(u64),
+    Str(Cow<'a, str>),
+}
+
+impl<'de> Deserialize<'de> for Directive<'static> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct DirectiveVisitor;
+
+        impl<'de> Visitor<'de> for DirectiveVisitor {
+            type Value = Directive<'static>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a boolean, a number or a string")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Ok(Directive::Flag(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Ok(Directive::Number(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Ok(Directive::Str(v.to_owned().into()))
+            }
+        }
+
+        deserializer.deserialize_any(DirectiveVisitor)
+    }
+}
+
+impl<'a> Serialize for Directive<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        match *self {
+            Directive::Flag(v) => serializer.serialize_bool(v),
+            Directive::Number(v) => serializer.serialize_u64(v),
+            Directive::Str(ref v) => serializer.serialize_str(v),
+        }
+    }
+}
+
+/// Serialises `header` as a map of its directives, separated by `separator`
+/// in its value, if enabled by `SerConfig::directive_maps`.
+fn serialize_directives<H, S>(header: &Ser<H>,
+                              separator: char,
+                              serializer: S)
+                              -> Result<S::Ok, S::Error>
+    where H: Header,
+          S: Serializer,
+{
+    if !header.config.directive_maps || !serializer.is_human_readable() {
+        return serialize_header(header.v, serializer);
+    }
+    let value = encode::<H, S::Error>(header.v)?;
+    let directives = value.split(separator).map(str::trim).filter(|d| !d.is_empty());
+    serializer.collect_map(directives.map(|directive| match directive.split_once('=') {
+        Some((name, v)) => {
+            let v = v.parse().map_or_else(|_| Directive::Str(v.into()), Directive::Number);
+            (name, v)
+        },
+        None => (directive, Directive::Flag(true)),
+    }))
+}
+
+/// Deserialises a typed header from the string of its value or, in
+/// human-readable formats, from a map of its directives.
+fn deserialize_directives<'de, H, D>(deserializer: D,
+                                     separator: &'static str)
+                                     -> Result<H, D::Error>
+    where H: Header,
+          D: Deserializer<'de>,
+{
+    let visitor = HeaderVisitor { separator: Some(separator), marker: PhantomData };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_str(visitor)
+    }
+}
+
+/// Implements `De` and `Ser` for typed headers serialised as the string of
+/// their value, through `deserialize_header` and `serialize_header`.
+macro_rules! string_headers {
+    ($($header:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for De<$header> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where D: Deserializer<'de>,
+                {
+                    deserialize_header(deserializer).map(De::new)
+                }
+            }
+
+            impl<'a> Serialize for Ser<'a, $header> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: Serializer,
+                {
+                    serialize_header(self.v, serializer)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `De` and `Ser` for typed headers made of a comma-separated list,
+/// which are serialised as the sequence of the list elements, in order.
+macro_rules! list_headers {
+    ($($header:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for De<$header> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where D: Deserializer<'de>,
+                {
+                    let elements = Vec::<String>::deserialize(deserializer)?;
+                    if let Some(v) = elements.iter().find(|v| v.is_empty() || v.contains(',')) {
+                        return Err(de::Error::custom(format_args!(
+                            "invalid {} header element {:?}",
+                            <$header>::name(),
+                            v)));
+                    }
+                    decode(&elements.join(", ")).map(De::new)
+                }
+            }
+
+            impl<'a> Serialize for Ser<'a, $header> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: Serializer,
+                {
+                    let value = encode::<$header, S::Error>(self.v)?;
+                    let elements = value.split(',').map(str::trim).filter(|v| !v.is_empty());
+                    serializer.collect_seq(elements)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `De` and `Ser` for typed headers made of a list of typed
+/// elements, which are serialised as the sequence of the elements, in order.
+macro_rules! iter_headers {
+    ($($header:ty => $element:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for De<$header> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where D: Deserializer<'de>,
+                {
+                    let elements = Vec::<De<$element>>::deserialize(deserializer)?;
+                    Ok(De::new(elements.into_iter().map(De::into_inner).collect()))
+                }
+            }
+
+            impl<'a> Serialize for Ser<'a, $header> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: Serializer,
+                {
+                    let elements = self.v.iter().collect::<Vec<$element>>();
+                    serializer.collect_seq(elements.iter().map(Ser::new))
+                }
+            }
+        )*
+    };
+}
+
+/// Returns whether `v` is a URI reference, i.e. a URI or a relative reference
+/// such as `../index.html`, possibly with a fragment.
+fn is_uri_reference(v: &str) -> bool {
+    // Relative references are validated as the path of an origin-form URI.
+    v.parse::<Uri>().is_ok() || format!("/{}", v).parse::<Uri>().is_ok()
+}
+
+/// Implements `De` and `Ser` for typed headers holding a URI reference, which
+/// are serialised like with `string_headers`, except that their value must
+/// be a valid URI reference.
+macro_rules! uri_headers {
+    ($($header:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for De<$header> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where D: Deserializer<'de>,
+                {
+                    let v = String::deserialize(deserializer)?;
+                    if !is_uri_reference(&v) {
+                        return Err(de::Error::custom(format_args!(
+                            "invalid {} header {:?}: not a URI reference",
+                            <$header>::name(),
+                            v)));
+                    }
+                    decode(&v).map(De::new)
+                }
+            }
+
+            impl<'a> Serialize for Ser<'a, $header> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: Serializer,
+                {
+                    serialize_header(self.v, serializer)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `De` and `Ser` for typed headers holding credentials, which are
+/// serialised like with `string_headers`, unless `SerConfig::redact` is set,
+/// in which case they are replaced by `[redacted]`.
+macro_rules! credential_headers {
+    ($($header:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for De<$header> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where D: Deserializer<'de>,
+                {
+                    deserialize_header(deserializer).map(De::new)
+                }
+            }
+
+            impl<'a> Serialize for Ser<'a, $header> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: Serializer,
+                {
+                    if self.config.redact {
+                        return serializer.serialize_str(str::from_utf8(REDACTED).unwrap());
+                    }
+                    serialize_header(self.v, serializer)
+                }
+            }
+        )*
+    };
+}
+
+// Entity tags are serialised as in their header, e.g. `W/"xyzzy"`, so weak
+// tags stay weak, and `IfMatch` and `IfNoneMatch` may be a `*` wildcard.
+string_headers!(ETag, IfMatch, IfNoneMatch);
+
+string_headers!(Server, UserAgent);
+
+string_headers!(SecWebsocketAccept, SecWebsocketKey);
+
+string_headers!(Pragma);
+
+// Byte ranges are serialised as in their header, e.g. `bytes=0-499, -500`,
+// or `bytes */1234` for the `ContentRange` of an unsatisfied range.
+string_headers!(ContentRange, Range);
+
+credential_headers!(Authorization<Basic>, Authorization<Bearer>);
+
+credential_headers!(ProxyAuthorization<Basic>, ProxyAuthorization<Bearer>);
+
+uri_headers!(ContentLocation, Location, Referer);
+
+list_headers!(ContentEncoding, TransferEncoding);
+
+// `Vary` may be a `*` wildcard, serialised as `["*"]`.
+list_headers!(Vary);
+
+list_headers!(Connection, Upgrade);
+
+// Transfer codings may have parameters, e.g. `["trailers", "deflate;q=0.5"]`.
+list_headers!(Te);
+
+// Range units are serialised as in their header, e.g. `["bytes"]`, or
+// `["none"]` if ranges are not accepted.
+list_headers!(AcceptRanges);
+
+iter_headers!(Allow => Method);
+
+// The origin of `AccessControlAllowOrigin` may be a `*` wildcard or `null`.
+string_headers!(AccessControlAllowOrigin);
+
+iter_headers!(AccessControlAllowHeaders => HeaderName,
+              AccessControlAllowMethods => Method,
+              AccessControlExposeHeaders => HeaderName,
+              AccessControlRequestHeaders => HeaderName);
+
+/// `AccessControlAllowCredentials` values are serialised as a unit, as they
+/// can only be `true`.
+impl<'de> Deserialize<'de> for De<AccessControlAllowCredentials> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        <()>::deserialize(deserializer).map(|()| De::new(AccessControlAllowCredentials))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, AccessControlAllowCredentials> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_unit()
+    }
+}
+
+/// `Expect` values are serialised as a unit, as they can only be
+/// `100-continue`.
+impl<'de> Deserialize<'de> for De<Expect> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        <()>::deserialize(deserializer).map(|()| De::new(Expect::CONTINUE))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Expect> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_unit()
+    }
+}
+
+/// `AccessControlMaxAge` values are serialised as a number of seconds.
+impl<'de> Deserialize<'de> for De<AccessControlMaxAge> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let secs = u64::deserialize(deserializer)?;
+        Ok(De::new(Duration::from_secs(secs).into()))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, AccessControlMaxAge> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_u64(Duration::from(self.v.clone()).as_secs())
+    }
+}
+
+/// `AccessControlRequestMethod` values are serialised like a `Method`.
+impl<'de> Deserialize<'de> for De<AccessControlRequestMethod> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let method = De::<Method>::deserialize(deserializer)?.into_inner();
+        Ok(De::new(method.into()))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, AccessControlRequestMethod> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        Ser::new(&Method::from(self.v.clone())).serialize(serializer)
+    }
+}
+
+/// Implements `De` and `Ser` for typed headers holding an HTTP date, which
+/// are serialised like a `SystemTime`.
+macro_rules! date_headers {
+    ($($header:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for De<$header> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where D: Deserializer<'de>,
+                {
+                    let time = De::<SystemTime>::deserialize(deserializer)?.into_inner();
+                    Ok(De::new(time.into()))
+                }
+            }
+
+            impl<'a> Serialize for Ser<'a, $header> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: Serializer,
+                {
+                    Ser::new(&SystemTime::from(self.v.clone())).serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+
+date_headers!(Date, Expires, IfModifiedSince, IfUnmodifiedSince, LastModified);
+
+/// `CacheControl` values are serialised as the string of their directives,
+/// or as a map of them with `SerConfig::directive_maps`.
+impl<'de> Deserialize<'de> for De<CacheControl> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserialize_directives(deserializer, ", ").map(De::new)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, CacheControl> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serialize_directives(self, ',', serializer)
+    }
+}
+
+/// `headers::Cookie` values are serialised as the sequence of their
+/// `[name, value]` pairs, in order. With `SerConfig::redact`, the values are
+/// replaced by `[redacted]`.
+impl<'de> Deserialize<'de> for De<headers::Cookie> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(String, String)>::deserialize(deserializer)?;
+        for (name, value) in &pairs {
+            if name.is_empty() || name.contains(&['=', ';'][..]) || value.contains(';') {
+                return Err(de::Error::custom(format_args!("invalid cookie pair {:?}",
+                                                          (name, value))));
+            }
+        }
+        let pairs = pairs.iter().map(|(name, value)| format!("{}={}", name, value));
+        decode(&pairs.collect::<Vec<_>>().join("; ")).map(De::new)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, headers::Cookie> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let redacted = str::from_utf8(REDACTED).unwrap();
+        serializer.collect_seq(self.v.iter().map(|(name, value)| {
+            (name, if self.config.redact { redacted } else { value })
+        }))
+    }
+}
+
+/// `SetCookie` values are serialised as the sequence of their values, one per
+/// cookie, so that they are never folded together. With `SerConfig::redact`,
+/// the values are replaced by `[redacted]`.
+impl<'de> Deserialize<'de> for De<SetCookie> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let values = Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|v| {
+                HeaderValue::from_str(v).map_err(|_| {
+                    de::Error::custom(format_args!("invalid set-cookie header {:?}", v))
+                })
+            })
+            .collect::<Result<Vec<_>, D::Error>>()?;
+        SetCookie::decode(&mut values.iter())
+            .map(De::new)
+            .map_err(|_| de::Error::invalid_length(0, &"at least one cookie"))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, SetCookie> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let mut values = Vec::<HeaderValue>::new();
+        self.v.encode(&mut values);
+        if self.config.redact {
+            let redacted = str::from_utf8(REDACTED).unwrap();
+            return serializer.collect_seq(values.iter().map(|_| redacted));
+        }
+        let values = values.iter()
+            .map(|v| str::from_utf8(v.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ser::Error::custom("non UTF-8 set-cookie header"))?;
+        serializer.collect_seq(values)
+    }
+}
+
+/// `Host` values are serialised as a `[hostname, port]` tuple, where `port`
+/// may be none.
+impl<'de> Deserialize<'de> for De<Host> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (hostname, port) = <(String, Option<u16>)>::deserialize(deserializer)?;
+        let authority = match port {
+            Some(port) => format!("{}:{}", hostname, port),
+            None => hostname,
+        };
+        // Unlike the header, the tuple form has no room for userinfo.
+        match authority.parse::<Authority>() {
+            Ok(ref v) if !v.as_str().contains('@') => Ok(De::new(Host::from(v.clone()))),
+            _ => Err(de::Error::custom(format_args!("invalid host {:?}", authority))),
+        }
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Host> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (self.v.hostname(), self.v.port()).serialize(serializer)
+    }
+}
+
+/// `StrictTransportSecurity` values are serialised as the string of their
+/// directives, or as a map of them with `SerConfig::directive_maps`, e.g.
+/// `{"max-age": 31536000, "includeSubdomains": true}`.
+///
+/// The `preload` directive is ignored, as `StrictTransportSecurity` does not
+/// record it.
+impl<'de> Deserialize<'de> for De<StrictTransportSecurity> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserialize_directives(deserializer, "; ").map(De::new)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, StrictTransportSecurity> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serialize_directives(self, ';', serializer)
+    }
+}
+
+/// `Origin` values are serialised as an option, which is none for an opaque
+/// origin and otherwise the serialised origin, e.g. `https://servo.org:8000`.
+/// The `null` string of an opaque origin in the header is also accepted.
+impl<'de> Deserialize<'de> for De<Origin> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            None => Ok(De::new(Origin::NULL)),
+            Some(v) => decode(&v).map(De::new),
+        }
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Origin> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        if self.v.is_null() {
+            return serializer.serialize_none();
+        }
+        serializer.serialize_some(&encode::<_, S::Error>(self.v)?)
+    }
+}
+
+/// `RetryAfter` values are serialised as the string of their value, which is
+/// either a delay in seconds or an HTTP date. In human-readable formats, a
+/// delay is serialised as a number instead, e.g. `120`.
+impl<'de> Deserialize<'de> for De<RetryAfter> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct RetryAfterVisitor;
+
+        impl<'de> Visitor<'de> for RetryAfterVisitor {
+            type Value = RetryAfter;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a delay in seconds or an HTTP date")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<RetryAfter, E>
+                where E: de::Error,
+            {
+                Ok(RetryAfter::delay(Duration::from_secs(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<RetryAfter, E>
+                where E: de::Error,
+            {
+                decode(v)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(RetryAfterVisitor).map(De::new)
+        } else {
+            deserializer.deserialize_str(RetryAfterVisitor).map(De::new)
+        }
+    }
+}
+
+impl<'a> Serialize for Ser<'a, RetryAfter> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let v = encode::<_, S::Error>(self.v)?;
+        match v.parse::<u64>() {
+            Ok(delay) if serializer.is_human_readable() => serializer.serialize_u64(delay),
+            _ => serializer.serialize_str(&v),
+        }
+    }
+}
+
+/// `Age` values are serialised as a number of seconds.
+impl<'de> Deserialize<'de> for De<Age> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(|secs| De::new(Age::from_secs(secs)))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Age> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_u64(self.v.as_secs())
+    }
+}
+
+/// `SecWebsocketVersion` values are serialised as their version number,
+/// which can only be `13`.
+impl<'de> Deserialize<'de> for De<SecWebsocketVersion> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let version = u8::deserialize(deserializer)?;
+        decode(&version.to_string()).map(De::new)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, SecWebsocketVersion> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        let version = encode::<_, S::Error>(self.v)?;
+        serializer.serialize_u8(version.parse().map_err(ser::Error::custom)?)
+    }
+}
+
+/// `ContentLength` values are serialised as their length.
+impl<'de> Deserialize<'de> for De<ContentLength> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(|v| De::new(ContentLength(v)))
+    }
+}
+
+impl<'a> Serialize for Ser<'a, ContentLength> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_u64(self.v.0)
+    }
+}
+
+/// A convenience wrapper for any typed header, including the ones of the
+/// `headers` crate without their own `De` and `Ser` implementations and
+/// third-party ones, through their `Header` implementation.
+///
+/// This is serialised as the string of the header values, joined by commas,
+/// so it should not be used with headers whose values cannot be combined,
+/// such as `SetCookie`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedHeaderSerde<H>(pub H);
+
+impl<H> TypedHeaderSerde<H> {
+    /// Consumes this wrapper, returning the inner value.
+    #[inline(always)]
+    pub fn into_inner(self) -> H {
+        self.0
+    }
+}
+
+impl<H> Deref for TypedHeaderSerde<H> {
+    type Target = H;
+
+    fn deref(&self) -> &H {
+        &self.0
+    }
+}
+
+impl<H> DerefMut for TypedHeaderSerde<H> {
+    fn deref_mut(&mut self) -> &mut H {
+        &mut self.0
+    }
+}
+
+impl<'de, H> Deserialize<'de> for TypedHeaderSerde<H>
+    where H: Header,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        deserialize_header(deserializer).map(TypedHeaderSerde)
+    }
+}
+
+impl<H> Serialize for TypedHeaderSerde<H>
+    where H: Header,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serialize_header(&self.0, serializer)
+    }
+}
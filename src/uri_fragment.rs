@@ -0,0 +1,124 @@
+//! URI references with a fragment.
+
+use hyper::Uri;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a URI with a fragment is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidUriWithFragment {
+    reason: &'static str,
+}
+
+impl InvalidUriWithFragment {
+    fn new(reason: &'static str) -> Self {
+        InvalidUriWithFragment { reason }
+    }
+}
+
+impl fmt::Display for InvalidUriWithFragment {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid URI with fragment: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidUriWithFragment {}
+
+/// A `Uri` with the fragment that `http` drops when parsing, such as
+/// `https://example.com/doc#section-2`.
+///
+/// This is serialised as a string including the fragment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UriWithFragment {
+    /// The URI without its fragment.
+    pub uri: Uri,
+    /// The fragment, without its leading `#`.
+    pub fragment: Option<String>,
+}
+
+impl UriWithFragment {
+    /// Returns the given URI with the given fragment, checking that the
+    /// fragment only contains visible ASCII characters.
+    pub fn new(uri: Uri, fragment: Option<String>) -> Result<Self, InvalidUriWithFragment> {
+        let is_fragment = |f: &String| f.bytes().all(|b| b.is_ascii_graphic());
+        if fragment.as_ref().is_some_and(|f| !is_fragment(f)) {
+            return Err(InvalidUriWithFragment::new("invalid fragment"));
+        }
+        Ok(UriWithFragment { uri, fragment })
+    }
+
+    /// Consumes this URI, returning it without its fragment.
+    pub fn into_uri(self) -> Uri {
+        self.uri
+    }
+}
+
+impl From<Uri> for UriWithFragment {
+    fn from(uri: Uri) -> Self {
+        UriWithFragment { uri, fragment: None }
+    }
+}
+
+impl From<UriWithFragment> for Uri {
+    fn from(uri: UriWithFragment) -> Self {
+        uri.uri
+    }
+}
+
+impl FromStr for UriWithFragment {
+    type Err = InvalidUriWithFragment;
+
+    fn from_str(s: &str) -> Result<Self, InvalidUriWithFragment> {
+        let (uri, fragment) = match s.split_once('#') {
+            Some((uri, fragment)) => (uri, Some(fragment.to_owned())),
+            None => (s, None),
+        };
+        let uri = uri.parse().map_err(|_| InvalidUriWithFragment::new("invalid URI"))?;
+        UriWithFragment::new(uri, fragment)
+    }
+}
+
+impl fmt::Display for UriWithFragment {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.uri)?;
+        if let Some(ref fragment) = self.fragment {
+            write!(formatter, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for UriWithFragment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct UriWithFragmentVisitor;
+
+        impl<'de> Visitor<'de> for UriWithFragmentVisitor {
+            type Value = UriWithFragment;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a URI with an optional fragment")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<UriWithFragment, E>
+                where E: de::Error,
+            {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(UriWithFragmentVisitor)
+    }
+}
+
+impl Serialize for UriWithFragment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
@@ -0,0 +1,58 @@
+//! Values of the `url` crate.
+
+use crate::{De, InvalidUriWithFragment, Ser, UriWithFragment};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use url::Url;
+
+/// Converts a `Url` to a `Uri`, keeping its fragment, which `Uri` values
+/// cannot hold.
+///
+/// This fails for URLs which are not valid URIs for `http`, such as `data:`
+/// URLs. Use `into_uri` on the result to drop the fragment.
+pub fn uri_from_url(url: &Url) -> Result<UriWithFragment, InvalidUriWithFragment> {
+    url.as_str().parse()
+}
+
+/// Converts a `Uri` and its fragment to a `Url`.
+///
+/// This fails for URIs which are not absolute, such as the origin form
+/// `/path` of request targets. A plain `Uri` can be converted with
+/// `UriWithFragment::from`.
+pub fn url_from_uri(uri: &UriWithFragment) -> Result<Url, url::ParseError> {
+    Url::parse(&uri.to_string())
+}
+
+/// `Url` values are serialised as strings, including their fragment.
+impl<'de> Deserialize<'de> for De<Url> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct UrlVisitor;
+
+        impl<'de> Visitor<'de> for UrlVisitor {
+            type Value = De<Url>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a URL")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where E: de::Error,
+            {
+                Url::parse(v).map(De::new).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(UrlVisitor)
+    }
+}
+
+impl<'a> Serialize for Ser<'a, Url> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.serialize_str(self.v.as_str())
+    }
+}
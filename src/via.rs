@@ -0,0 +1,267 @@
+//! `Via` headers.
+//!
+//! See https://httpwg.org/specs/rfc9110.html#field.via.
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a `Via` header is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidVia {
+    reason: &'static str,
+}
+
+impl InvalidVia {
+    fn new(reason: &'static str) -> Self {
+        InvalidVia { reason }
+    }
+}
+
+impl fmt::Display for InvalidVia {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid Via header: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidVia {}
+
+/// A parsed `Via` header, listing the proxies a message went through.
+///
+/// This is serialised as a sequence of entries, each of them being
+/// serialised as a `[protocol, version, received_by, comment]` tuple, where
+/// `protocol` and `comment` may be null.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Via {
+    entries: Vec<ViaEntry>,
+}
+
+/// An entry of a `Via` header, describing a single proxy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ViaEntry {
+    /// The name of the received protocol, omitted when it is HTTP.
+    pub protocol: Option<String>,
+    /// The version of the received protocol, e.g. `1.1`.
+    pub version: String,
+    /// The host and port of the proxy, or a pseudonym.
+    pub received_by: String,
+    /// The comment identifying the software of the proxy, without the
+    /// enclosing parentheses.
+    pub comment: Option<String>,
+}
+
+impl ViaEntry {
+    fn validate(self) -> Result<Self, InvalidVia> {
+        if self.protocol.as_ref().is_some_and(|p| !is_token(p)) {
+            return Err(InvalidVia::new("invalid protocol name"));
+        }
+        if !is_token(&self.version) {
+            return Err(InvalidVia::new("invalid protocol version"));
+        }
+        if !is_received_by(&self.received_by) {
+            return Err(InvalidVia::new("invalid received-by"));
+        }
+        if self.comment.as_ref().is_some_and(|c| !is_comment_text(c)) {
+            return Err(InvalidVia::new("invalid comment"));
+        }
+        Ok(self)
+    }
+}
+
+impl Via {
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries, from the first proxy the message
+    /// went through to the last one.
+    pub fn iter(&self) -> impl Iterator<Item = &ViaEntry> {
+        self.entries.iter()
+    }
+
+    /// Adds an entry at the end of the list.
+    pub fn push(&mut self, entry: ViaEntry) -> Result<(), InvalidVia> {
+        self.entries.push(entry.validate()?);
+        Ok(())
+    }
+}
+
+/// Returns whether `s` is a `token` as defined by RFC 9110.
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(is_tchar)
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// Returns whether `s` is a host, optionally followed by a port, or a
+/// pseudonym.
+fn is_received_by(s: &str) -> bool {
+    // Brackets and colons are allowed for IPv6 addresses and ports.
+    !s.is_empty() && s.bytes().all(|b| is_tchar(b) || b"[]:".contains(&b))
+}
+
+/// Returns whether `s` can be put in parentheses as a comment, with its own
+/// unescaped parentheses balanced as nested comments.
+fn is_comment_text(s: &str) -> bool {
+    let mut depth = 0usize;
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            // A quoted pair, which may escape a parenthesis.
+            b'\\' => match bytes.next() {
+                Some(b'\t' | b' '..=b'~' | 0x80..=0xff) => {},
+                _ => return false,
+            },
+            b'(' => depth += 1,
+            b')' => {
+                depth = match depth.checked_sub(1) {
+                    Some(depth) => depth,
+                    None => return false,
+                };
+            },
+            b'\t' | b' '..=b'~' | 0x80..=0xff => {},
+            _ => return false,
+        }
+    }
+    depth == 0
+}
+
+impl FromStr for Via {
+    type Err = InvalidVia;
+
+    fn from_str(s: &str) -> Result<Self, InvalidVia> {
+        let is_ows = |c| c == ' ' || c == '\t';
+        let mut via = Via::default();
+        let mut rest = s.trim_start_matches(is_ows);
+        while !rest.is_empty() {
+            let end = rest.find(is_ows).ok_or(InvalidVia::new("missing received-by"))?;
+            let (protocol, version) = match rest[..end].split_once('/') {
+                Some((protocol, version)) => (Some(protocol.to_owned()), version),
+                None => (None, &rest[..end]),
+            };
+            rest = rest[end..].trim_start_matches(is_ows);
+            let end = rest.find(|c| is_ows(c) || c == ',').unwrap_or(rest.len());
+            let received_by = rest[..end].to_owned();
+            rest = rest[end..].trim_start_matches(is_ows);
+            let mut comment = None;
+            if rest.starts_with('(') {
+                let mut depth = 0;
+                let mut escaped = false;
+                let end = rest
+                    .char_indices()
+                    .find(|&(_, c)| {
+                        match c {
+                            _ if escaped => escaped = false,
+                            '\\' => escaped = true,
+                            '(' => depth += 1,
+                            ')' => depth -= 1,
+                            _ => {},
+                        }
+                        depth == 0
+                    })
+                    .map(|(i, _)| i)
+                    .ok_or(InvalidVia::new("unterminated comment"))?;
+                comment = Some(rest[1..end].to_owned());
+                rest = rest[end + 1..].trim_start_matches(is_ows);
+            }
+            via.push(ViaEntry {
+                protocol,
+                version: version.to_owned(),
+                received_by,
+                comment,
+            })?;
+            if !rest.is_empty() {
+                rest = rest.strip_prefix(',')
+                    .ok_or(InvalidVia::new("expected a comma"))?
+                    .trim_start_matches(|c| is_ows(c) || c == ',');
+            }
+        }
+        Ok(via)
+    }
+}
+
+impl fmt::Display for Via {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            if let Some(ref protocol) = entry.protocol {
+                write!(formatter, "{}/", protocol)?;
+            }
+            write!(formatter, "{} {}", entry.version, entry.received_by)?;
+            if let Some(ref comment) = entry.comment {
+                write!(formatter, " ({})", comment)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Via {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct ViaVisitor;
+
+        impl<'de> Visitor<'de> for ViaVisitor {
+            type Value = Via;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of Via entries")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Via, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut entries = Vec::with_capacity(capacity);
+                while let Some(entry) = visitor.next_element()? {
+                    entries.push(entry);
+                }
+                Ok(Via { entries })
+            }
+        }
+
+        deserializer.deserialize_seq(ViaVisitor)
+    }
+}
+
+impl Serialize for Via {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_seq(&self.entries)
+    }
+}
+
+impl<'de> Deserialize<'de> for ViaEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let (protocol, version, received_by, comment) = Deserialize::deserialize(deserializer)?;
+        ViaEntry { protocol, version, received_by, comment }
+            .validate()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ViaEntry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        (&self.protocol, &self.version, &self.received_by, &self.comment).serialize(serializer)
+    }
+}
@@ -0,0 +1,142 @@
+//! `Sec-WebSocket-Protocol` headers.
+//!
+//! See https://www.rfc-editor.org/rfc/rfc6455.html#section-11.3.4.
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error returned when a `Sec-WebSocket-Protocol` header is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidSecWebsocketProtocol {
+    reason: &'static str,
+}
+
+impl InvalidSecWebsocketProtocol {
+    fn new(reason: &'static str) -> Self {
+        InvalidSecWebsocketProtocol { reason }
+    }
+}
+
+impl fmt::Display for InvalidSecWebsocketProtocol {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "invalid Sec-WebSocket-Protocol header: {}", self.reason)
+    }
+}
+
+impl error::Error for InvalidSecWebsocketProtocol {}
+
+/// A parsed `Sec-WebSocket-Protocol` header, listing the subprotocols a
+/// client supports by order of preference, or the one a server selected.
+///
+/// This is serialised as a sequence of the subprotocol names, in order,
+/// e.g. `["chat", "superchat"]`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SecWebsocketProtocol {
+    protocols: Vec<String>,
+}
+
+impl SecWebsocketProtocol {
+    /// Returns the number of subprotocols.
+    pub fn len(&self) -> usize {
+        self.protocols.len()
+    }
+
+    /// Returns whether there are no subprotocols.
+    pub fn is_empty(&self) -> bool {
+        self.protocols.is_empty()
+    }
+
+    /// Returns whether `protocol` is listed. Subprotocol names are case
+    /// sensitive.
+    pub fn contains(&self, protocol: &str) -> bool {
+        self.protocols.iter().any(|p| p == protocol)
+    }
+
+    /// Returns an iterator over the subprotocol names, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.protocols.iter().map(|p| &**p)
+    }
+
+    /// Adds a subprotocol at the end of the list.
+    pub fn push(&mut self, protocol: String) -> Result<(), InvalidSecWebsocketProtocol> {
+        if !is_token(&protocol) {
+            return Err(InvalidSecWebsocketProtocol::new("invalid subprotocol name"));
+        }
+        self.protocols.push(protocol);
+        Ok(())
+    }
+}
+
+/// Returns whether `s` is a `token` as defined by RFC 9110.
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+    })
+}
+
+impl FromStr for SecWebsocketProtocol {
+    type Err = InvalidSecWebsocketProtocol;
+
+    /// Parses a comma-separated list of subprotocol names, ignoring empty
+    /// elements as required for lists.
+    fn from_str(s: &str) -> Result<Self, InvalidSecWebsocketProtocol> {
+        let mut protocols = SecWebsocketProtocol::default();
+        for protocol in s.split(',') {
+            let protocol = protocol.trim_matches(|c| c == ' ' || c == '\t');
+            if !protocol.is_empty() {
+                protocols.push(protocol.to_owned())?;
+            }
+        }
+        Ok(protocols)
+    }
+}
+
+impl fmt::Display for SecWebsocketProtocol {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.protocols.join(", "))
+    }
+}
+
+impl<'de> Deserialize<'de> for SecWebsocketProtocol {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>,
+    {
+        struct ProtocolsVisitor;
+
+        impl<'de> Visitor<'de> for ProtocolsVisitor {
+            type Value = SecWebsocketProtocol;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a sequence of WebSocket subprotocol names")
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<SecWebsocketProtocol, V::Error>
+                where V: SeqAccess<'de>,
+            {
+                // Clamp to not OOM on rogue values.
+                let capacity = cmp::min(visitor.size_hint().unwrap_or(0), 64);
+                let mut protocols = SecWebsocketProtocol {
+                    protocols: Vec::with_capacity(capacity),
+                };
+                while let Some(protocol) = visitor.next_element()? {
+                    protocols.push(protocol).map_err(de::Error::custom)?;
+                }
+                Ok(protocols)
+            }
+        }
+
+        deserializer.deserialize_seq(ProtocolsVisitor)
+    }
+}
+
+impl Serialize for SecWebsocketProtocol {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer,
+    {
+        serializer.collect_seq(&self.protocols)
+    }
+}
@@ -0,0 +1,91 @@
+extern crate hyper_serde;
+extern crate mime;
+extern crate serde_json;
+
+use hyper_serde::{Accept, AcceptLanguage, QualityItem};
+
+#[test]
+fn test_accept() {
+    let header = "text/html, application/xhtml+xml;Q=0.9 , image/webp,*/*;q=0.8";
+    let accept = header.parse::<Accept>().unwrap();
+
+    let items = accept.iter().collect::<Vec<_>>();
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[1].item, "application/xhtml+xml".parse::<mime::Mime>().unwrap());
+    assert_eq!(items[1].quality, 900);
+    assert_eq!(items[3].item, mime::STAR_STAR);
+    assert_eq!(items[3].quality, 800);
+    assert_eq!(accept.to_string(),
+               "text/html, application/xhtml+xml;q=0.9, image/webp, */*;q=0.8");
+
+    let preferred = accept.by_preference()
+        .iter()
+        .map(|item| item.item.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(preferred, ["text/html", "image/webp", "application/xhtml+xml", "*/*"]);
+
+    let json = serde_json::to_string(&accept).unwrap();
+    assert_eq!(json,
+               r#"[["text/html",1000],["application/xhtml+xml",900],["image/webp",1000],["*/*",800]]"#);
+    assert_eq!(serde_json::from_str::<Accept>(&json).unwrap(), accept);
+}
+
+#[test]
+fn test_accept_parameters() {
+    let accept = "text/html;level=1;q=0.125;ext=1, text/plain;q=0".parse::<Accept>().unwrap();
+    let items = accept.iter().collect::<Vec<_>>();
+    assert_eq!(items[0].item.get_param("level").unwrap(), "1");
+    assert_eq!(items[0].quality, 125);
+    assert_eq!(items[1].quality, 0);
+    assert_eq!(accept.to_string(), "text/html;level=1;q=0.125, text/plain;q=0");
+
+    let mut accept = Accept::default();
+    accept.push(QualityItem::new(mime::APPLICATION_JSON)).unwrap();
+    assert_eq!(accept.to_string(), "application/json");
+}
+
+#[test]
+fn test_accept_invalid() {
+    assert!("text/html;q=1.5".parse::<Accept>().is_err());
+    assert!("text/html;q=0.1234".parse::<Accept>().is_err());
+    assert!("text/html;q=".parse::<Accept>().is_err());
+    assert!("text".parse::<Accept>().is_err());
+    assert!("".parse::<Accept>().unwrap().is_empty());
+
+    let mut accept = Accept::default();
+    assert!(accept.push(QualityItem { item: mime::TEXT_HTML, quality: 1001 }).is_err());
+    assert!(serde_json::from_str::<Accept>(r#"[["text/html",1001]]"#).is_err());
+}
+
+#[test]
+fn test_accept_language() {
+    let header = "fr-CH, fr;q=0.9, en;q=0.8, de;q=0.7, *;q=0.5, zh-Hant-TW";
+    let accept = header.parse::<AcceptLanguage>().unwrap();
+    assert_eq!(accept.len(), 6);
+    assert_eq!(accept.to_string(), header);
+
+    let preferred = accept.by_preference()
+        .iter()
+        .map(|item| &*item.item)
+        .collect::<Vec<_>>();
+    assert_eq!(preferred, ["fr-CH", "zh-Hant-TW", "fr", "en", "de", "*"]);
+
+    let accept = "en-GB;q=0.8".parse::<AcceptLanguage>().unwrap();
+    let json = serde_json::to_string(&accept).unwrap();
+    assert_eq!(json, r#"[["en-GB",800]]"#);
+    assert_eq!(serde_json::from_str::<AcceptLanguage>(&json).unwrap(), accept);
+}
+
+#[test]
+fn test_accept_language_invalid() {
+    assert!("en_GB".parse::<AcceptLanguage>().is_err());
+    assert!("en-".parse::<AcceptLanguage>().is_err());
+    assert!("1en".parse::<AcceptLanguage>().is_err());
+    assert!("en-abcdefghi".parse::<AcceptLanguage>().is_err());
+    assert!("en;q=2".parse::<AcceptLanguage>().is_err());
+
+    let mut accept = AcceptLanguage::default();
+    assert!(accept.push(QualityItem::new("*-CH".to_owned())).is_err());
+    assert!(serde_json::from_str::<AcceptLanguage>(r#"[["en GB",1000]]"#).is_err());
+    assert!(serde_json::from_str::<AcceptLanguage>(r#"[["en",1001]]"#).is_err());
+}
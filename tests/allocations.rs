@@ -0,0 +1,83 @@
+extern crate headers;
+extern crate http;
+extern crate hyper_serde;
+extern crate mime;
+extern crate serde;
+extern crate serde_json;
+
+use http::header::{self, HeaderMap, HeaderValue};
+use headers::ContentType;
+use http::{StatusCode, Uri};
+use hyper_serde::{HeaderMapFormat, Ser, SerConfig, StatusWithReason};
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T: Serialize>(value: &T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    serde_json::to_writer(io::sink(), value).unwrap();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_grouping_allocations() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, HeaderValue::from_static("baguette"));
+    headers.insert(header::ACCEPT, HeaderValue::from_static("*/*"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("a=b"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("c=d"));
+    headers.append(header::VARY, HeaderValue::from_static("accept"));
+    headers.append(header::VARY, HeaderValue::from_static("origin"));
+
+    // Grouping the values of each header does not allocate.
+    assert_eq!(allocations(&Ser::new(&headers)), 0);
+
+    // Sorting header names allocates their list.
+    let sorted = SerConfig::new().sort_keys(true);
+    assert_eq!(allocations(&Ser::with_config(&headers, &sorted)), 1);
+
+    // The combined value of Vary is allocated too.
+    let fetch = SerConfig::new().header_format(HeaderMapFormat::Fetch);
+    assert_eq!(allocations(&Ser::with_config(&headers, &fetch)), 2);
+}
+
+#[test]
+fn test_formatting_allocations() {
+    let mime = "text/html; charset=utf-8".parse::<mime::Mime>().unwrap();
+    let content_type = ContentType::from(mime.clone());
+    let uri = "https://servo.org/path?query".parse::<Uri>().unwrap();
+
+    assert_eq!(allocations(&Ser::new(&mime)), 0);
+    assert_eq!(allocations(&Ser::new(&content_type)), 0);
+    assert_eq!(allocations(&Ser::new(&uri)), 0);
+    assert_eq!(allocations(&Ser::new_pretty(&StatusCode::NOT_FOUND)), 0);
+}
+
+#[test]
+fn test_canonical_reason_allocations() {
+    let before = ALLOCATIONS.with(Cell::get);
+    let status = serde_json::from_str::<StatusWithReason>(r#"[404, "Not Found"]"#);
+    assert_eq!(ALLOCATIONS.with(Cell::get) - before, 0);
+    assert!(status.unwrap().is_canonical());
+}
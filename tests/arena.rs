@@ -0,0 +1,65 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde;
+extern crate serde_json;
+
+use http::header;
+use hyper_serde::{Arena, InArena};
+use serde::de::DeserializeSeed;
+use std::cell::Cell;
+
+#[derive(Default)]
+struct LeakingArena {
+    allocations: Cell<usize>,
+}
+
+impl Arena for LeakingArena {
+    fn alloc_bytes(&self, bytes: &[u8]) -> &[u8] {
+        self.allocations.set(self.allocations.get() + 1);
+        Box::leak(bytes.to_vec().into_boxed_slice())
+    }
+}
+
+#[test]
+fn test_in_arena() {
+    let arena = LeakingArena::default();
+    let json = r#"{"Host": ["baguette"], "vary": ["accept", [111, 114]]}"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let headers = InArena::new(&arena).deserialize(&mut deserializer).unwrap();
+
+    assert_eq!(arena.allocations.get(), 5);
+    assert_eq!(headers.len(), 3);
+    assert_eq!(headers.get("host"), Some(&b"baguette"[..]));
+    assert_eq!(headers.iter().collect::<Vec<_>>(),
+               [(&b"Host"[..], &b"baguette"[..]),
+                (&b"vary"[..], &b"accept"[..]),
+                (&b"vary"[..], &b"or"[..])]);
+
+    let headers = headers.to_header_map().unwrap();
+    assert_eq!(headers[header::HOST], "baguette");
+    assert_eq!(headers.get_all(header::VARY).iter().count(), 2);
+}
+
+#[test]
+fn test_in_arena_single_value() {
+    let arena = LeakingArena::default();
+    let json = r#"{"Host": "baguette", "vary": ["accept"]}"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let headers = InArena::new(&arena).deserialize(&mut deserializer).unwrap();
+
+    assert_eq!(headers.iter().collect::<Vec<_>>(),
+               [(&b"Host"[..], &b"baguette"[..]), (&b"vary"[..], &b"accept"[..])]);
+
+    let mut deserializer = serde_json::Deserializer::from_str(r#"{"Host": 42}"#);
+    assert!(InArena::new(&arena).deserialize(&mut deserializer).is_err());
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn test_in_bump() {
+    let bump = bumpalo::Bump::new();
+    let mut deserializer = serde_json::Deserializer::from_str(r#"{"a": ["b"]}"#);
+    let headers = InArena::new(&bump).deserialize(&mut deserializer).unwrap();
+
+    assert_eq!(headers.get("A"), Some(&b"b"[..]));
+}
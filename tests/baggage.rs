@@ -0,0 +1,72 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::{Baggage, BaggageMember};
+
+#[test]
+fn test_baggage() {
+    let header = "userId=alice,serverNode=DF%2028, isProduction=false;sampled;ttl=60";
+    let baggage = header.parse::<Baggage>().unwrap();
+
+    assert_eq!(baggage.len(), 3);
+    assert_eq!(baggage.get("serverNode").unwrap().value, "DF 28");
+    assert_eq!(baggage.get("isProduction").unwrap().properties,
+               [("sampled".to_owned(), None), ("ttl".to_owned(), Some("60".to_owned()))]);
+    assert_eq!(baggage.to_string(),
+               "userId=alice,serverNode=DF%2028,isProduction=false;sampled;ttl=60");
+
+    let json = serde_json::to_string(&baggage).unwrap();
+    assert_eq!(json,
+               r#"[["userId","alice",[]],["serverNode","DF 28",[]],["isProduction","false",[["sampled",null],["ttl","60"]]]]"#);
+    assert_eq!(serde_json::from_str::<Baggage>(&json).unwrap(), baggage);
+}
+
+#[test]
+fn test_baggage_encoding() {
+    let mut baggage = Baggage::default();
+    baggage.push(BaggageMember::new("city".to_owned(), "Zürich, 100%".to_owned())).unwrap();
+
+    assert_eq!(baggage.to_string(), "city=Z%C3%BCrich%2C%20100%25");
+    assert_eq!(baggage.to_string().parse::<Baggage>().unwrap(), baggage);
+}
+
+#[test]
+fn test_baggage_invalid() {
+    assert!("user id=alice".parse::<Baggage>().is_err());
+    assert!("userId".parse::<Baggage>().is_err());
+    assert!("userId=a,b".parse::<Baggage>().is_err());
+    assert!("userId=%ff".parse::<Baggage>().is_err());
+    assert!("userId=alice;sample d".parse::<Baggage>().is_err());
+
+    let members = (0..65).map(|i| format!("k{}=v", i)).collect::<Vec<_>>();
+    assert!(members.join(",").parse::<Baggage>().is_err());
+    assert!(members[..64].join(",").parse::<Baggage>().is_ok());
+
+    let long = format!("k={}", "v".repeat(Baggage::MAX_LEN));
+    assert!(long.parse::<Baggage>().is_err());
+
+    let mut baggage = Baggage::default();
+    let member = BaggageMember::new("k".to_owned(), " ".repeat(3000));
+    assert!(baggage.push(member).is_err());
+    assert!(baggage.is_empty());
+
+    assert!(serde_json::from_str::<Baggage>(r#"[["user id","alice",[]]]"#).is_err());
+}
+
+#[test]
+fn test_max_len() {
+    let member = |key: &str, len| {
+        let mut member = BaggageMember::new(key.to_owned(), "v".repeat(len));
+        member.properties.push(("p".to_owned(), Some(" ".to_owned())));
+        member
+    };
+
+    // Each member is serialised with a `;p=%20` property.
+    let mut baggage = Baggage::default();
+    baggage.push(member("a", 4000)).unwrap();
+    assert!(baggage.push(member("b", 4176)).is_err());
+    baggage.push(member("b", 4175)).unwrap();
+    assert_eq!(baggage.to_string().len(), Baggage::MAX_LEN);
+    assert!(baggage.push(BaggageMember::new("c".to_owned(), String::new())).is_err());
+    assert_eq!(baggage.len(), 2);
+}
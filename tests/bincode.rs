@@ -0,0 +1,42 @@
+#![cfg(feature = "bincode")]
+
+extern crate bincode;
+extern crate cookie;
+extern crate http;
+extern crate hyper_serde;
+
+use cookie::Cookie;
+use http::header::{self, HeaderMap, HeaderValue};
+use hyper_serde::{De, Ser, Serde};
+
+#[test]
+fn test_bincode_wire_compatibility() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, HeaderValue::from_static("servo.org"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("a=b"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("c=d"));
+
+    let config = bincode::config::legacy();
+    let bytes = bincode::encode_to_vec(Ser::new(&headers), config).unwrap();
+    assert_eq!(bytes, bincode::serde::encode_to_vec(Ser::new(&headers), config).unwrap());
+    let (decoded, len) = bincode::decode_from_slice::<De<HeaderMap>, _>(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.into_inner(), headers);
+
+    let config = bincode::config::standard();
+    let bytes = bincode::serde::encode_to_vec(Ser::new(&headers), config).unwrap();
+    let (decoded, _) = bincode::borrow_decode_from_slice::<De<HeaderMap>, _>(&bytes, config)
+        .unwrap();
+    assert_eq!(decoded.into_inner(), headers);
+}
+
+#[test]
+fn test_bincode_serde() {
+    let config = bincode::config::standard();
+    let cookie = Serde(Cookie::new("id", "42"));
+    let bytes = bincode::encode_to_vec(&cookie, config).unwrap();
+    let (decoded, _) = bincode::decode_from_slice::<Serde<Cookie>, _>(&bytes, config).unwrap();
+    assert_eq!(decoded, cookie);
+
+    assert!(bincode::decode_from_slice::<Serde<Cookie>, _>(&bytes[..2], config).is_err());
+}
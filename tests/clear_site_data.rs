@@ -0,0 +1,37 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::ClearSiteData;
+
+#[test]
+fn test_clear_site_data() {
+    let data = r#""cache", "cookies", "prefetchCache""#.parse::<ClearSiteData>().unwrap();
+    assert_eq!(data, ClearSiteData {
+        cache: true,
+        cookies: true,
+        ..ClearSiteData::default()
+    });
+    assert_eq!(data.to_string(), r#""cache", "cookies""#);
+
+    let json = serde_json::to_string(&data).unwrap();
+    assert_eq!(json, r#"["cache","cookies"]"#);
+    assert_eq!(serde_json::from_str::<ClearSiteData>(&json).unwrap(), data);
+}
+
+#[test]
+fn test_clear_site_data_wildcard() {
+    let data = r#""*""#.parse::<ClearSiteData>().unwrap();
+    assert_eq!(data, ClearSiteData::all());
+    assert_eq!(data.to_string(), r#""*""#);
+    assert_eq!(serde_json::to_string(&data).unwrap(), r#"["*"]"#);
+
+    let json = r#"["cache","cookies","storage","executionContexts"]"#;
+    assert_eq!(serde_json::from_str::<ClearSiteData>(json).unwrap(), data);
+}
+
+#[test]
+fn test_clear_site_data_invalid() {
+    assert!("cache".parse::<ClearSiteData>().is_ok_and(|data| data.is_empty()));
+    assert!(r#""cache"#.parse::<ClearSiteData>().is_err());
+    assert!(serde_json::from_str::<ClearSiteData>(r#"["bogus"]"#).is_err());
+}
@@ -0,0 +1,58 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header::{HeaderMap, HeaderValue};
+use hyper_serde::{Brand, UserAgentHints};
+use hyper_serde::{SEC_CH_UA, SEC_CH_UA_MOBILE, SEC_CH_UA_PLATFORM};
+
+fn brand(brand: &str, version: &str) -> Brand {
+    Brand {
+        brand: brand.to_owned(),
+        version: version.to_owned(),
+    }
+}
+
+#[test]
+fn test_user_agent_hints() {
+    let mut headers = HeaderMap::new();
+    headers.insert(SEC_CH_UA.clone(),
+                   HeaderValue::from_static(r#""Servo";v="1", "Not\"A;Brand";v="99""#));
+    headers.insert(SEC_CH_UA_MOBILE.clone(), HeaderValue::from_static("?0"));
+    headers.insert(SEC_CH_UA_PLATFORM.clone(), HeaderValue::from_static("\"Linux\""));
+
+    let hints = UserAgentHints::from_headers(&headers).unwrap();
+    assert_eq!(hints.brands, [brand("Servo", "1"), brand("Not\"A;Brand", "99")]);
+    assert_eq!(hints.full_version_list, None);
+    assert_eq!(hints.mobile, Some(false));
+    assert_eq!(hints.platform.as_deref(), Some("Linux"));
+
+    let mut inserted = HeaderMap::new();
+    hints.insert_into(&mut inserted).unwrap();
+    assert_eq!(inserted, headers);
+
+    let json = serde_json::to_string(&hints).unwrap();
+    assert_eq!(json, r#"[[["Servo","1"],["Not\"A;Brand","99"]],null,false,"Linux",null]"#);
+    assert_eq!(serde_json::from_str::<UserAgentHints>(&json).unwrap(), hints);
+}
+
+#[test]
+fn test_user_agent_hints_invalid() {
+    let mut headers = HeaderMap::new();
+    headers.insert(SEC_CH_UA.clone(), HeaderValue::from_static("\"Servo\""));
+    let error = UserAgentHints::from_headers(&headers).unwrap_err();
+    assert_eq!(*error.name(), SEC_CH_UA);
+
+    headers.insert(SEC_CH_UA.clone(), HeaderValue::from_static("\"Servo\";v=\"1\""));
+    headers.insert(SEC_CH_UA_MOBILE.clone(), HeaderValue::from_static("0"));
+    let error = UserAgentHints::from_headers(&headers).unwrap_err();
+    assert_eq!(*error.name(), SEC_CH_UA_MOBILE);
+
+    let hints = UserAgentHints {
+        brands: vec![brand("Sérvo", "1")],
+        ..UserAgentHints::default()
+    };
+    assert!(hints.insert_into(&mut HeaderMap::new()).is_err());
+    assert!(serde_json::from_str::<UserAgentHints>(r#"[[["Sérvo","1"]],null,null,null,null]"#)
+        .is_err());
+}
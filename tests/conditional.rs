@@ -0,0 +1,53 @@
+extern crate headers;
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use headers::{ETag, HeaderMapExt, IfNoneMatch};
+use http::header::{self, HeaderMap, HeaderValue};
+use hyper_serde::ConditionalRequest;
+
+#[test]
+fn test_conditional_request() {
+    let mut response = HeaderMap::new();
+    response.insert(header::ETAG, HeaderValue::from_static("\"xyzzy\""));
+    response.insert(header::LAST_MODIFIED,
+                    HeaderValue::from_static("Sat, 29 Oct 1994 19:43:31 GMT"));
+    let conditional = ConditionalRequest::from_response(&response);
+
+    let mut request = HeaderMap::new();
+    conditional.apply(&mut request);
+    assert_eq!(request.len(), 2);
+    assert_eq!(request[header::IF_NONE_MATCH], "\"xyzzy\"");
+    assert_eq!(request[header::IF_MODIFIED_SINCE], "Sat, 29 Oct 1994 19:43:31 GMT");
+
+    let json = serde_json::to_string(&conditional).unwrap();
+    assert_eq!(serde_json::from_str::<ConditionalRequest>(&json).unwrap(), conditional);
+}
+
+#[test]
+fn test_conditional_request_custom() {
+    let etag = "\"xyzzy\"".parse::<ETag>().unwrap();
+    let conditional = ConditionalRequest {
+        etag: Some(etag),
+        if_none_match: Some(IfNoneMatch::any()),
+        ..ConditionalRequest::default()
+    };
+
+    let json = serde_json::to_string(&conditional).unwrap();
+    let headers = serde_json::from_str::<hyper_serde::De<HeaderMap>>(&json)
+        .unwrap()
+        .into_inner();
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers.typed_get::<IfNoneMatch>(), Some(IfNoneMatch::any()));
+    assert_eq!(serde_json::from_str::<ConditionalRequest>(&json).unwrap(), conditional);
+}
+
+#[test]
+fn test_conditional_request_invalid() {
+    let json = r#"{"etag": ["xyzzy"]}"#;
+    assert!(serde_json::from_str::<ConditionalRequest>(json).is_err());
+
+    let json = r#"{"host": ["servo.org"]}"#;
+    assert!(serde_json::from_str::<ConditionalRequest>(json).is_err());
+}
@@ -0,0 +1,311 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+extern crate serde_test;
+
+use http::header::{self, HeaderMap, HeaderValue};
+use http::Uri;
+use hyper_serde::{DeConfig, HeaderAction, HeaderMapFormat, HeaderPolicy};
+use hyper_serde::{De, Issue, Ser, SerConfig, SerIter};
+use serde_test::{assert_ser_tokens, Configure, Token};
+use std::sync::{Arc, Mutex};
+
+fn collect_issues() -> (Arc<Mutex<Vec<Issue>>>, impl Fn(&Issue) + Send + Sync) {
+    let issues = Arc::new(Mutex::new(vec![]));
+    let sink = issues.clone();
+    (issues, move |issue: &Issue| sink.lock().unwrap().push(issue.clone()))
+}
+
+fn from_json(json: &str, config: &DeConfig) -> serde_json::Result<HeaderMap> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    hyper_serde::deserialize_with_config(&mut deserializer, config)
+}
+
+#[test]
+fn test_header_policy_default() {
+    let json = r#"{"Host": ["baguette"], "Proxy-Authorization": ["secret"]}"#;
+    let headers = from_json(json, &DeConfig::new()).unwrap();
+
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[header::HOST], "baguette");
+    assert_eq!(headers[header::PROXY_AUTHORIZATION], "secret");
+}
+
+#[test]
+fn test_header_policy_drop() {
+    let config = DeConfig::new().header_policy(
+        HeaderPolicy::new().rule("proxy-*", HeaderAction::Drop));
+    let json = r#"{
+        "Host": ["baguette"],
+        "PROXY-AUTHORIZATION": ["secret"],
+        "proxy-connection": ["close"]
+    }"#;
+    let headers = from_json(json, &config).unwrap();
+
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[header::HOST], "baguette");
+}
+
+#[test]
+fn test_header_policy_reject() {
+    let config = DeConfig::new().header_policy(
+        HeaderPolicy::new().rule("X-Servo-*", HeaderAction::Reject));
+
+    assert!(from_json(r#"{"x-servo-id": ["1"]}"#, &config).is_err());
+    assert!(from_json(r#"{"X-SERVO-ID": ["1"]}"#, &config).is_err());
+    assert!(from_json(r#"{"x-servo": ["1"]}"#, &config).is_ok());
+}
+
+#[test]
+fn test_header_policy_allow_list() {
+    let policy = HeaderPolicy::new()
+        .rule("Content-Type", HeaderAction::Keep)
+        .rule("Content-*", HeaderAction::Reject)
+        .otherwise(HeaderAction::Drop);
+
+    assert_eq!(policy.action("content-type"), HeaderAction::Keep);
+    assert_eq!(policy.action("CONTENT-TYPE"), HeaderAction::Keep);
+    assert_eq!(policy.action("Content-Length"), HeaderAction::Reject);
+    assert_eq!(policy.action("host"), HeaderAction::Drop);
+}
+
+#[test]
+fn test_byte_budget() {
+    let config = DeConfig::new().byte_budget(12);
+
+    assert!(from_json(r#"{"host": ["baguette"]}"#, &config).is_ok());
+    assert!(from_json(r#"{"host": ["baguette", "!"]}"#, &config).is_err());
+    assert!(from_json(r#"{"host": ["baguette"], "accept": ["*/*"]}"#, &config).is_err());
+
+    // Deserialisation stops before reaching the syntax error.
+    let error = from_json(r#"{"host": ["baguette", "baguette"], !}"#, &config).unwrap_err();
+    assert_eq!(error.to_string(),
+               "exceeded the byte budget of 12 bytes at line 1 column 33");
+}
+
+#[test]
+fn test_sort_keys() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, HeaderValue::from_static("baguette"));
+    headers.insert(header::ACCEPT, HeaderValue::from_static("*/*"));
+
+    let config = SerConfig::new().sort_keys(true);
+    let tokens = &[Token::Map { len: Some(2) },
+                   Token::Str("accept"),
+                   Token::Seq { len: Some(1) },
+                   Token::Bytes(b"*/*"),
+                   Token::SeqEnd,
+                   Token::Str("host"),
+                   Token::Seq { len: Some(1) },
+                   Token::Bytes(b"baguette"),
+                   Token::SeqEnd,
+                   Token::MapEnd];
+
+    assert_ser_tokens(&Ser::with_config(&headers, &config), tokens);
+}
+
+#[test]
+fn test_fetch_format() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::VARY, HeaderValue::from_static("accept"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("a=b"));
+    headers.append(header::VARY, HeaderValue::from_static("origin"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("c=d"));
+    headers.insert(header::ACCEPT, HeaderValue::from_static("*/*"));
+
+    let config = SerConfig::new().header_format(HeaderMapFormat::Fetch);
+    let tokens = &[Token::Map { len: Some(4) },
+                   Token::Str("accept"),
+                   Token::Str("*/*"),
+                   Token::Str("set-cookie"),
+                   Token::Str("a=b"),
+                   Token::Str("set-cookie"),
+                   Token::Str("c=d"),
+                   Token::Str("vary"),
+                   Token::Str("accept, origin"),
+                   Token::MapEnd];
+
+    assert_ser_tokens(&Ser::with_config(&headers, &config).readable(), tokens);
+}
+
+#[test]
+fn test_ordered_format() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::SET_COOKIE, HeaderValue::from_static("a=b"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("c=d"));
+    headers.insert(header::VARY, HeaderValue::from_static("accept"));
+
+    let tokens = &[Token::Seq { len: Some(3) },
+                   Token::Tuple { len: 2 },
+                   Token::Str("set-cookie"),
+                   Token::Bytes(b"a=b"),
+                   Token::TupleEnd,
+                   Token::Tuple { len: 2 },
+                   Token::Str("set-cookie"),
+                   Token::Bytes(b"c=d"),
+                   Token::TupleEnd,
+                   Token::Tuple { len: 2 },
+                   Token::Str("vary"),
+                   Token::Bytes(b"accept"),
+                   Token::TupleEnd,
+                   Token::SeqEnd];
+    assert_ser_tokens(&Ser::new_ordered(&headers), tokens);
+
+    let config = SerConfig::new().header_format(HeaderMapFormat::Ordered).pretty(true);
+    let json = serde_json::to_string(&Ser::with_config(&headers, &config)).unwrap();
+    assert_eq!(json, r#"[["set-cookie","a=b"],["set-cookie","c=d"],["vary","accept"]]"#);
+
+    let config = DeConfig::new().header_format(HeaderMapFormat::Ordered);
+    assert_eq!(from_json(&json, &config).unwrap(), headers);
+    assert!(from_json(r#"{"vary": ["accept"]}"#, &config).is_err());
+    assert!(from_json(&json, &DeConfig::new().header_format(HeaderMapFormat::Fetch)).is_err());
+}
+
+#[test]
+fn test_ordered_format_interleaved() {
+    let items = [
+        (header::SET_COOKIE, HeaderValue::from_static("a=b")),
+        (header::VARY, HeaderValue::from_static("accept")),
+        (header::SET_COOKIE, HeaderValue::from_static("c=d")),
+    ];
+    let config = SerConfig::new().header_format(HeaderMapFormat::Ordered).pretty(true);
+    let view = SerIter::with_config(|| items.iter().map(|(name, v)| (name, v)), &config);
+
+    assert_eq!(serde_json::to_string(&view).unwrap(),
+               r#"[["set-cookie","a=b"],["vary","accept"],["set-cookie","c=d"]]"#);
+}
+
+#[test]
+fn test_base64_fallback() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, HeaderValue::from_static("baguette"));
+    headers.insert("x-latin1", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+    headers.insert("x-tagged", HeaderValue::from_static("base64:abc"));
+
+    let config = SerConfig::new().pretty(true).sort_keys(true).base64_fallback(true);
+    let json = serde_json::to_string(&Ser::with_config(&headers, &config)).unwrap();
+    assert_eq!(json,
+               r#"{"host":["baguette"],"x-latin1":["base64:Y2Fm6Q=="],"x-tagged":["base64:YmFzZTY0OmFiYw=="]}"#);
+
+    let config = DeConfig::new().base64_fallback(true);
+    assert_eq!(from_json(&json, &config).unwrap(), headers);
+    assert!(from_json(r#"{"x-tagged": ["base64:!"]}"#, &config).is_err());
+    assert_eq!(from_json(&json, &DeConfig::new()).unwrap()["x-latin1"], "base64:Y2Fm6Q==");
+
+    let config = SerConfig::new().header_format(HeaderMapFormat::Ordered).base64_fallback(true);
+    let json = serde_json::to_string(&Ser::with_config(&headers, &config.pretty(true))).unwrap();
+    assert!(json.contains(r#"["x-latin1","base64:Y2Fm6Q=="]"#));
+}
+
+#[test]
+fn test_audit_serialize() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, HeaderValue::from_static("baguette"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("a=b"));
+    headers.append(header::SET_COOKIE, HeaderValue::from_static("c=d"));
+    headers.append(header::VARY, HeaderValue::from_static("accept"));
+    headers.append(header::VARY, HeaderValue::from_static("origin"));
+
+    let (issues, audit) = collect_issues();
+    let config = SerConfig::new().audit(audit);
+    serde_json::to_string(&Ser::with_config(&headers, &config)).unwrap();
+    assert_eq!(*issues.lock().unwrap(),
+               [Issue::ValuesGrouped(header::SET_COOKIE)]);
+
+    issues.lock().unwrap().clear();
+    let iter = SerIter::with_config(|| headers.iter(), &config);
+    serde_json::to_string(&iter).unwrap();
+    assert_eq!(*issues.lock().unwrap(),
+               [Issue::ValuesGrouped(header::SET_COOKIE)]);
+}
+
+#[test]
+fn test_audit_deserialize() {
+    let (issues, audit) = collect_issues();
+    let config = DeConfig::new().audit(audit);
+    let json = r#"{"host": ["baguette"], "Content-Type": ["text/plain"]}"#;
+    from_json(json, &config).unwrap();
+
+    assert_eq!(*issues.lock().unwrap(),
+               [Issue::CasingNormalized("Content-Type".to_owned())]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_pretty_json() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, HeaderValue::from_static("baguette"));
+    headers.insert(header::COOKIE, HeaderValue::from_static("secret=1"));
+    headers.insert(header::ACCEPT, HeaderValue::from_static("*/*"));
+
+    assert_eq!(hyper_serde::pretty_json(&headers).unwrap(), r#"{
+  "accept": [
+    "*/*"
+  ],
+  "cookie": [
+    "[redacted]"
+  ],
+  "host": [
+    "baguette"
+  ]
+}"#);
+
+    let time = std::time::UNIX_EPOCH - std::time::Duration::from_secs(1);
+    assert!(hyper_serde::pretty_json(&time).is_err());
+}
+
+#[test]
+fn test_grpc_metadata() {
+    let mut headers = HeaderMap::new();
+    headers.insert("grpc-trace-bin", HeaderValue::from_static("AAEC"));
+    let config = SerConfig::new().grpc_metadata(true);
+
+    assert_ser_tokens(&Ser::with_config(&headers, &config),
+                      &[Token::Map { len: Some(1) },
+                        Token::Str("grpc-trace-bin"),
+                        Token::Seq { len: Some(1) },
+                        Token::Bytes(&[0, 1, 2]),
+                        Token::SeqEnd,
+                        Token::MapEnd]);
+
+    headers.insert(header::USER_AGENT, HeaderValue::from_static("grpc-rust"));
+    let json = serde_json::to_string(&Ser::with_config(&headers, &config)).unwrap();
+    let config = DeConfig::new().grpc_metadata(true);
+    assert_eq!(from_json(&json, &config).unwrap(), headers);
+
+    // Padded values are accepted, but re-encoded without padding.
+    let json = r#"{"key-bin": [[0, 1]]}"#;
+    assert_eq!(from_json(json, &config).unwrap()["key-bin"], "AAE");
+    let mut padded = HeaderMap::new();
+    padded.insert("key-bin", HeaderValue::from_static("AAE="));
+    let config = SerConfig::new().grpc_metadata(true);
+    assert_eq!(serde_json::to_string(&Ser::with_config(&padded, &config)).unwrap(),
+               r#"{"key-bin":[[0,1]]}"#);
+
+    let mut invalid = HeaderMap::new();
+    invalid.insert("key-bin", HeaderValue::from_static("!"));
+    assert!(serde_json::to_string(&Ser::with_config(&invalid, &config)).is_err());
+}
+
+#[test]
+fn test_uri_components() {
+    let config = SerConfig::new().uri_components(true);
+
+    let uri = Uri::from_static("https://servo.org/path?key=value");
+    let json = serde_json::to_string(&Ser::with_config(&uri, &config)).unwrap();
+    assert_eq!(json,
+               r#"{"scheme":"https","authority":"servo.org","path":"/path","query":"key=value"}"#);
+    assert_eq!(serde_json::from_str::<De<Uri>>(&json).unwrap().into_inner(), uri);
+
+    let uri = Uri::from_static("/path");
+    assert_ser_tokens(&Ser::with_config(&uri, &config).readable(),
+                      &[Token::Map { len: Some(1) },
+                        Token::Str("path"),
+                        Token::Str("/path"),
+                        Token::MapEnd]);
+    assert_ser_tokens(&Ser::with_config(&uri, &config).compact(), &[Token::Str("/path")]);
+
+    let uri = Uri::from_static("servo.org:443");
+    assert_eq!(serde_json::to_string(&Ser::with_config(&uri, &config)).unwrap(),
+               r#"{"authority":"servo.org:443"}"#);
+}
@@ -0,0 +1,57 @@
+#[cfg(all(feature = "http-info", feature = "http1"))]
+extern crate hyper;
+extern crate hyper_serde;
+extern crate serde_json;
+#[cfg(all(feature = "http-info", feature = "http1"))]
+extern crate tokio;
+
+use hyper_serde::ConnectionInfo;
+
+#[test]
+fn test_connection_info() {
+    let info = ConnectionInfo::new("[2001:db8::1]:443".parse().unwrap(),
+                                   "192.0.2.1:50000".parse().unwrap());
+    let json = serde_json::to_string(&info).unwrap();
+    assert_eq!(json, r#"["[2001:db8::1]:443","192.0.2.1:50000",null,false]"#);
+    assert_eq!(serde_json::from_str::<ConnectionInfo>(&json).unwrap(), info);
+
+    let info = ConnectionInfo { alpn: Some("h2".to_owned()), is_tls: true, ..info };
+    let json = serde_json::to_string(&info).unwrap();
+    assert_eq!(json, r#"["[2001:db8::1]:443","192.0.2.1:50000","h2",true]"#);
+    assert_eq!(serde_json::from_str::<ConnectionInfo>(&json).unwrap(), info);
+}
+
+#[test]
+fn test_invalid() {
+    let parse = serde_json::from_str::<ConnectionInfo>;
+
+    assert!(parse(r#"["example.com:443","192.0.2.1:50000",null,false]"#).is_err());
+    assert!(parse(r#"["192.0.2.2:443","192.0.2.1:50000","",true]"#).is_err());
+    assert!(parse(r#"["192.0.2.2:443","192.0.2.1:50000",null]"#).is_err());
+}
+
+#[cfg(all(feature = "http-info", feature = "http1"))]
+#[tokio::test]
+async fn test_from_http_info() {
+    use hyper::client::connect::HttpInfo;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request).unwrap();
+        stream.write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n").unwrap();
+    });
+
+    let uri = format!("http://{}/", addr).parse().unwrap();
+    let response = hyper::Client::new().get(uri).await.unwrap();
+    let info = ConnectionInfo::from(response.extensions().get::<HttpInfo>().unwrap());
+    assert_eq!(info.remote_addr, addr);
+    assert!(info.local_addr.ip().is_loopback());
+    assert_eq!((info.alpn, info.is_tls), (None, false));
+    server.join().unwrap();
+}
@@ -0,0 +1,52 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::ContentDisposition;
+
+#[test]
+fn test_content_disposition() {
+    let header = r#"attachment; filename="EURO rates"; filename*=UTF-8''%E2%82%AC%20rates"#;
+    let disposition = header.parse::<ContentDisposition>().unwrap();
+    assert_eq!(disposition.disposition, "attachment");
+    assert_eq!(disposition.filename.as_deref(), Some("EURO rates"));
+    assert_eq!(disposition.filename_ext.as_deref(), Some("€ rates"));
+    assert_eq!(disposition.file_name(), Some("€ rates"));
+    assert_eq!(disposition.to_string(), header);
+
+    let json = serde_json::to_string(&disposition).unwrap();
+    assert_eq!(json, r#"["attachment","EURO rates","€ rates"]"#);
+    assert_eq!(serde_json::from_str::<ContentDisposition>(&json).unwrap(), disposition);
+
+    let disposition = "INLINE".parse::<ContentDisposition>().unwrap();
+    assert_eq!(disposition.disposition, "inline");
+    assert_eq!(disposition.file_name(), None);
+    assert_eq!(serde_json::to_string(&disposition).unwrap(), r#"["inline",null,null]"#);
+}
+
+#[test]
+fn test_content_disposition_parameters() {
+    let header = r#"attachment;filename = "a \"quoted\" name.txt" ; size=12;"#;
+    let disposition = header.parse::<ContentDisposition>().unwrap();
+    assert_eq!(disposition.file_name(), Some(r#"a "quoted" name.txt"#));
+    assert_eq!(disposition.to_string(), r#"attachment; filename="a \"quoted\" name.txt""#);
+
+    let header = "attachment; FILENAME*=iso-8859-1'en'%A3%20rates";
+    let disposition = header.parse::<ContentDisposition>().unwrap();
+    assert_eq!(disposition.file_name(), Some("£ rates"));
+    assert_eq!(disposition.to_string(), "attachment; filename*=UTF-8''%C2%A3%20rates");
+}
+
+#[test]
+fn test_content_disposition_invalid() {
+    assert!("".parse::<ContentDisposition>().is_err());
+    assert!("attachment; filename".parse::<ContentDisposition>().is_err());
+    assert!(r#"attachment; filename="rates"#.parse::<ContentDisposition>().is_err());
+    assert!(r#"attachment; filename="a" b"#.parse::<ContentDisposition>().is_err());
+    assert!("attachment; filename*=rates".parse::<ContentDisposition>().is_err());
+    assert!("attachment; filename*=UTF-8''%E2%82".parse::<ContentDisposition>().is_err());
+    assert!("attachment; filename*=UTF-8''%E".parse::<ContentDisposition>().is_err());
+    assert!("attachment; filename*=KOI8-R''rates".parse::<ContentDisposition>().is_err());
+
+    assert!(serde_json::from_str::<ContentDisposition>(r#"["Inline",null,null]"#).is_err());
+    assert!(serde_json::from_str::<ContentDisposition>(r#"["inline","a\nb",null]"#).is_err());
+}
@@ -0,0 +1,118 @@
+extern crate cookie;
+extern crate hyper_serde;
+extern crate serde;
+extern crate serde_json;
+extern crate time;
+
+use cookie::{Cookie, CookieJar};
+use hyper_serde::{De, DeConfig, JarDelta, JarMerge, MergeInto, Ser, SerConfig};
+use serde::de::DeserializeSeed;
+use time::Tm;
+
+fn jar(json: &str) -> CookieJar {
+    serde_json::from_str::<De<CookieJar>>(json).unwrap().into_inner()
+}
+
+fn merge(jar: &mut CookieJar, strategy: JarMerge, json: &str) {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    MergeInto::new(jar, strategy).deserialize(&mut deserializer).unwrap();
+}
+
+fn values(jar: &CookieJar) -> Vec<String> {
+    let mut values = jar.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    values.sort();
+    values
+}
+
+#[test]
+fn test_cookie_jar() {
+    let jar = jar(r#"["a=1", "b=2; Path=/"]"#);
+
+    assert_eq!(values(&jar), ["a=1", "b=2; Path=/"]);
+    assert_eq!(jar.delta().count(), 0);
+    assert!(serde_json::from_str::<De<CookieJar>>(r#"["a"]"#).is_err());
+}
+
+#[test]
+fn test_cookie_jar_state() {
+    let mut jar = jar(r#"["a=1", "b=2", "c=3"]"#);
+    jar.add(Cookie::new("d", "4"));
+    jar.remove(Cookie::build("b").path("/"));
+
+    let config = SerConfig::new().jar_state(true);
+    let json = serde_json::to_string(&Ser::with_config(&jar, &config)).unwrap();
+    let state = serde_json::from_str::<(Vec<String>, Vec<String>, Vec<String>)>(&json).unwrap();
+    let mut original = state.0;
+    original.sort();
+    assert_eq!(original, ["a=1", "c=3"]);
+    assert_eq!(state.1, ["d=4"]);
+    assert_eq!(state.2.len(), 1);
+    assert!(state.2[0].starts_with("b=; Path=/; Max-Age=0"));
+
+    let config = DeConfig::new().jar_state(true);
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let restored = hyper_serde::deserialize_with_config::<CookieJar, _>(&mut deserializer,
+                                                                        &config).unwrap();
+    assert_eq!(values(&restored), values(&jar));
+    let mut delta = restored.delta().map(|c| c.name().to_owned()).collect::<Vec<_>>();
+    delta.sort();
+    assert_eq!(delta, ["b", "d"]);
+    assert!(restored.get("b").is_none());
+
+    // Without the option, only the live cookies are serialised.
+    let json = serde_json::to_string(&Ser::new(&jar)).unwrap();
+    assert_eq!(values(&self::jar(&json)), ["a=1", "c=3", "d=4"]);
+}
+
+#[test]
+fn test_jar_delta() {
+    let mut original = jar(r#"["a=1", "b=2"]"#);
+    let mut jar = original.clone();
+    jar.add(Cookie::new("c", "3"));
+    jar.remove(Cookie::from("a"));
+
+    let delta = JarDelta::new(&jar);
+    assert_eq!(delta.added(), [Cookie::new("c", "3")]);
+    assert_eq!(delta.removed().len(), 1);
+    assert!(JarDelta::new(&original).is_empty());
+
+    let json = serde_json::to_string(&delta).unwrap();
+    assert!(json.starts_with(r#"[["c=3"],["a=; Max-Age=0"#));
+    let delta = serde_json::from_str::<JarDelta>(&json).unwrap();
+    delta.apply(&mut original);
+    assert_eq!(values(&original), ["b=2", "c=3"]);
+    assert_eq!(original.delta().count(), 2);
+
+    assert!(serde_json::from_str::<JarDelta>(r#"[["c"], []]"#).is_err());
+}
+
+#[test]
+fn test_merge_replace_all() {
+    let mut jar = jar(r#"["a=1", "b=2"]"#);
+    merge(&mut jar, JarMerge::ReplaceAll, r#"["b=3", "c=4"]"#);
+
+    assert_eq!(values(&jar), ["b=3", "c=4"]);
+}
+
+#[test]
+fn test_merge_union() {
+    let mut jar = jar(r#"["a=1", "b=2"]"#);
+    merge(&mut jar, JarMerge::Union, r#"["b=3", "c=4"]"#);
+
+    assert_eq!(values(&jar), ["a=1", "b=3", "c=4"]);
+    assert_eq!(jar.delta().count(), 2);
+}
+
+#[test]
+fn test_merge_keep_newer() {
+    // Pretend the value of the cookies is their creation time.
+    let created = |cookie: &Cookie<'static>| {
+        cookie.value().parse().ok().map(|sec| time::at_utc(time::Timespec::new(sec, 0)))
+    };
+    let created: &dyn Fn(&Cookie<'static>) -> Option<Tm> = &created;
+
+    let mut jar = jar(r#"["a=10", "b=10", "c=10"]"#);
+    merge(&mut jar, JarMerge::KeepNewer(created), r#"["a=5", "b=20", "c=x"]"#);
+
+    assert_eq!(values(&jar), ["a=10", "b=20", "c=10"]);
+}
@@ -0,0 +1,51 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header::{HeaderMap, HeaderValue};
+use hyper_serde::{CrossOriginIsolation, EmbedderPolicy, OpenerPolicy, ResourcePolicy};
+use hyper_serde::{CROSS_ORIGIN_EMBEDDER_POLICY, CROSS_ORIGIN_OPENER_POLICY};
+use hyper_serde::CROSS_ORIGIN_RESOURCE_POLICY;
+
+#[test]
+fn test_cross_origin_isolation() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CROSS_ORIGIN_OPENER_POLICY.clone(),
+                   HeaderValue::from_static("same-origin; report-to=\"coop\""));
+    headers.insert(CROSS_ORIGIN_EMBEDDER_POLICY.clone(),
+                   HeaderValue::from_static("require-corp"));
+
+    let isolation = CrossOriginIsolation::from_headers(&headers).unwrap();
+    assert_eq!(isolation, CrossOriginIsolation {
+        opener: Some(OpenerPolicy::SameOrigin),
+        embedder: Some(EmbedderPolicy::RequireCorp),
+        resource: None,
+    });
+
+    let mut inserted = HeaderMap::new();
+    isolation.insert_into(&mut inserted);
+    assert_eq!(inserted[&CROSS_ORIGIN_OPENER_POLICY], "same-origin");
+    assert_eq!(inserted[&CROSS_ORIGIN_EMBEDDER_POLICY], "require-corp");
+    assert!(!inserted.contains_key(&CROSS_ORIGIN_RESOURCE_POLICY));
+
+    let json = serde_json::to_string(&isolation).unwrap();
+    assert_eq!(json, r#"["same-origin","require-corp",null]"#);
+    assert_eq!(serde_json::from_str::<CrossOriginIsolation>(&json).unwrap(), isolation);
+}
+
+#[test]
+fn test_cross_origin_isolation_invalid() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CROSS_ORIGIN_RESOURCE_POLICY.clone(), HeaderValue::from_static("same-site"));
+    assert_eq!(CrossOriginIsolation::from_headers(&headers).unwrap().resource,
+               Some(ResourcePolicy::SameSite));
+
+    headers.insert(CROSS_ORIGIN_EMBEDDER_POLICY.clone(),
+                   HeaderValue::from_static("\"require-corp\""));
+    let error = CrossOriginIsolation::from_headers(&headers).unwrap_err();
+    assert_eq!(error.name(), &CROSS_ORIGIN_EMBEDDER_POLICY);
+
+    let json = r#"["bogus",null,null]"#;
+    let error = serde_json::from_str::<CrossOriginIsolation>(json).unwrap_err();
+    assert!(error.to_string().starts_with("unknown variant `bogus`"));
+}
@@ -0,0 +1,63 @@
+extern crate hyper_serde;
+extern crate serde_json;
+extern crate serde_test;
+
+use hyper_serde::DataUrl;
+use serde_test::{Configure, Token, assert_tokens};
+
+#[test]
+fn test_data_url() {
+    let url = "data:text/plain;charset=utf-8,caf%C3%A9".parse::<DataUrl>().unwrap();
+    assert_eq!(url.mime, "text/plain;charset=utf-8");
+    assert_eq!(url.charset(), Some("utf-8"));
+    assert_eq!(url.body, "café".as_bytes());
+    assert_eq!(url.to_string(), "data:text/plain;charset=utf-8;base64,Y2Fmw6k=");
+    assert_eq!(url.to_string().parse::<DataUrl>().unwrap(), url);
+
+    let url = "DATA:image/png ; Base64,iVBO Rw==#top".parse::<DataUrl>().unwrap();
+    assert_eq!(url.mime, mime::IMAGE_PNG);
+    assert_eq!(url.charset(), None);
+    assert_eq!(url.body, b"\x89PNG");
+}
+
+#[test]
+fn test_percent_decoding() {
+    let url = "data:,100%25%20%+f%zz%4".parse::<DataUrl>().unwrap();
+    assert_eq!(url.body, b"100% %+f%zz%4");
+}
+
+#[test]
+fn test_default_mime() {
+    let url = "data:,Hello".parse::<DataUrl>().unwrap();
+    assert_eq!(url.mime, "text/plain;charset=US-ASCII");
+    assert_eq!(url.body, b"Hello");
+
+    let url = "data:;charset=utf-8,Hello".parse::<DataUrl>().unwrap();
+    assert_eq!(url.mime, "text/plain;charset=utf-8");
+
+    let url = "data:invalid,Hello".parse::<DataUrl>().unwrap();
+    assert_eq!(url.mime, "text/plain;charset=US-ASCII");
+}
+
+#[test]
+fn test_invalid() {
+    assert!("http://example.com/".parse::<DataUrl>().is_err());
+    assert!("data:text/plain".parse::<DataUrl>().is_err());
+    assert!("data:;base64,Y2Fmw6k=%20=".parse::<DataUrl>().is_err());
+}
+
+#[test]
+fn test_serde() {
+    let url = "data:text/plain,Hi".parse::<DataUrl>().unwrap();
+    assert_tokens(&url.clone().readable(), &[Token::Str("data:text/plain;base64,SGk=")]);
+    assert_tokens(&url.compact(),
+                  &[Token::Tuple { len: 2 },
+                    Token::Str("text/plain"),
+                    Token::Bytes(b"Hi"),
+                    Token::TupleEnd]);
+
+    let json = r#""data:,Hello""#;
+    let url = serde_json::from_str::<DataUrl>(json).unwrap();
+    assert_eq!(url.body, b"Hello");
+    assert!(serde_json::from_str::<DataUrl>(r#""about:blank""#).is_err());
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "time03")]
+
+extern crate hyper_serde;
+extern crate serde_test;
+extern crate time03;
+
+use hyper_serde::{De, Ser};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Configure};
+use serde_test::{Compact, Readable, Token};
+use time03::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+fn datetime() -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(1508570880).unwrap()
+}
+
+#[test]
+fn test_offset_date_time() {
+    let v = datetime();
+    let tokens = &[Token::Str("2017-10-21T07:28:00Z")];
+    assert_ser_tokens(&Ser::new(&v).readable(), tokens);
+    assert_de_tokens(&De::new(v).readable(), tokens);
+
+    let v = datetime().replace_nanosecond(500_000_000).unwrap()
+        .to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+    let tokens = &[Token::Str("2017-10-21T09:28:00.5+02:00")];
+    assert_ser_tokens(&Ser::new(&v).readable(), tokens);
+    assert_de_tokens(&De::new(v).readable(), tokens);
+
+    let tokens = &[Token::Tuple { len: 3 },
+                   Token::I64(1508570880),
+                   Token::U32(500_000_000),
+                   Token::I32(7200),
+                   Token::TupleEnd];
+    assert_ser_tokens(&Ser::new(&v).compact(), tokens);
+    assert_de_tokens(&De::new(v).compact(), tokens);
+}
+
+#[test]
+fn test_offset_date_time_invalid() {
+    assert_de_tokens_error::<Readable<De<OffsetDateTime>>>(
+        &[Token::Str("2017-10-21T07:28:00")],
+        "the 'offset hour' component could not be parsed");
+    assert_de_tokens_error::<Compact<De<OffsetDateTime>>>(
+        &[Token::Tuple { len: 3 },
+          Token::I64(i64::MAX),
+          Token::U32(0),
+          Token::I32(0),
+          Token::TupleEnd],
+        "timestamp was not in range");
+}
+
+#[test]
+fn test_primitive_date_time() {
+    let v = datetime();
+    let v = PrimitiveDateTime::new(v.date(), v.time());
+    let tokens = &[Token::Str("2017-10-21T07:28:00")];
+    assert_ser_tokens(&Ser::new(&v).readable(), tokens);
+    assert_de_tokens(&De::new(v).readable(), tokens);
+
+    let v = v.replace_millisecond(250).unwrap();
+    let tokens = &[Token::Str("2017-10-21T07:28:00.25")];
+    assert_ser_tokens(&Ser::new(&v).readable(), tokens);
+    assert_de_tokens(&De::new(v).readable(), tokens);
+
+    let tokens = &[Token::Tuple { len: 2 },
+                   Token::I64(1508570880),
+                   Token::U32(250_000_000),
+                   Token::TupleEnd];
+    assert_ser_tokens(&Ser::new(&v).compact(), tokens);
+    assert_de_tokens(&De::new(v).compact(), tokens);
+}
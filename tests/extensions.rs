@@ -0,0 +1,133 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::{request, Extensions, Request, Response};
+use hyper_serde::{De, DeConfig, ExtensionRegistry, RecordedExtensions, Ser, SerConfig};
+use std::convert::TryInto;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq)]
+struct RequestId(u32);
+
+#[derive(Debug, PartialEq)]
+struct Unregistered;
+
+fn registry() -> ExtensionRegistry {
+    ExtensionRegistry::new()
+        .register::<RequestId, _, _>(
+            "request-id",
+            |id| id.0.to_be_bytes().to_vec(),
+            |bytes| Some(RequestId(u32::from_be_bytes(bytes.try_into().ok()?))))
+}
+
+#[test]
+fn test_extensions_round_trip() {
+    let mut extensions = Extensions::new();
+    extensions.insert(RequestId(42));
+    extensions.insert(Unregistered);
+
+    let recorded = registry().extract(&extensions);
+    let json = serde_json::to_string(&recorded).unwrap();
+    assert_eq!(json, r#"{"request-id":[0,0,0,42]}"#);
+
+    let recorded = serde_json::from_str::<RecordedExtensions>(&json).unwrap();
+    let mut restored = Extensions::new();
+    registry().restore(&recorded, &mut restored).unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored.get::<RequestId>(), Some(&RequestId(42)));
+}
+
+#[test]
+fn test_extensions_unknown() {
+    let json = r#"{"request-id":[0,0,0,42],"trace":[1]}"#;
+    let recorded = serde_json::from_str::<RecordedExtensions>(json).unwrap();
+    assert_eq!(recorded.len(), 2);
+
+    let mut restored = Extensions::new();
+    registry().restore(&recorded, &mut restored).unwrap();
+    assert_eq!(restored.len(), 1);
+}
+
+#[test]
+fn test_extensions_invalid() {
+    let json = r#"{"request-id":[42]}"#;
+    let recorded = serde_json::from_str::<RecordedExtensions>(json).unwrap();
+
+    let error = registry().restore(&recorded, &mut Extensions::new()).unwrap_err();
+    assert_eq!(error.name(), "request-id");
+
+    let json = r#"{"request-id":[42],"request-id":[43]}"#;
+    assert!(serde_json::from_str::<RecordedExtensions>(json).is_err());
+}
+
+#[test]
+fn test_extensions_preserve_unknown() {
+    let json = r#"{"request-id":[0,0,0,42],"trace":[1]}"#;
+    let recorded = serde_json::from_str::<RecordedExtensions>(json).unwrap();
+
+    let registry = registry().preserve_unknown(true);
+    let mut restored = Extensions::new();
+    registry.restore(&recorded, &mut restored).unwrap();
+    assert_eq!(restored.len(), 2);
+    assert_eq!(restored.get::<RequestId>(), Some(&RequestId(42)));
+
+    let recorded = registry.extract(&restored);
+    assert_eq!(serde_json::to_string(&recorded).unwrap(), json);
+}
+
+#[test]
+fn test_parts_extensions() {
+    let registry = Arc::new(registry());
+    let (mut parts, ()) = Request::get("/").body(()).unwrap().into_parts();
+    parts.extensions.insert(RequestId(42));
+
+    let config = SerConfig::new().extensions(registry.clone());
+    let json = serde_json::to_string(&Ser::with_config(&parts, &config)).unwrap();
+    assert_eq!(json, r#"["GET","/","HTTP/1.1",{},{"request-id":[0,0,0,42]}]"#);
+
+    let config = DeConfig::new().extensions(registry);
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let parts = hyper_serde::deserialize_with_config::<request::Parts, _>(&mut deserializer,
+                                                                          &config).unwrap();
+    assert_eq!(parts.extensions.get::<RequestId>(), Some(&RequestId(42)));
+
+    // Without a registry, the extensions are not expected.
+    assert!(serde_json::from_str::<De<request::Parts>>(&json).is_err());
+
+    let (mut parts, ()) = Response::new(()).into_parts();
+    parts.extensions.insert(RequestId(7));
+    let config = SerConfig::new().extensions(Arc::new(ExtensionRegistry::new()));
+    let json = serde_json::to_string(&Ser::with_config(&parts, &config)).unwrap();
+    assert_eq!(json, r#"[200,"HTTP/1.1",{},{}]"#);
+}
+
+#[test]
+fn test_message_extensions() {
+    let registry = Arc::new(registry());
+    let mut request = Request::get("/").body("ping".to_owned()).unwrap();
+    request.extensions_mut().insert(RequestId(42));
+
+    let config = SerConfig::new().extensions(registry.clone());
+    let json = serde_json::to_string(&Ser::with_config(&request, &config)).unwrap();
+    assert_eq!(json, r#"["GET","/","HTTP/1.1",{},"ping",{"request-id":[0,0,0,42]}]"#);
+
+    let de_config = DeConfig::new().extensions(registry);
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let request = hyper_serde::deserialize_with_config::<Request<String>, _>(&mut deserializer,
+                                                                             &de_config)
+        .unwrap();
+    assert_eq!(request.extensions().get::<RequestId>(), Some(&RequestId(42)));
+    assert!(serde_json::from_str::<De<Request<String>>>(&json).is_err());
+
+    let mut response = Response::new("pong".to_owned());
+    response.extensions_mut().insert(RequestId(7));
+    let json = serde_json::to_string(&Ser::with_config(&response, &config)).unwrap();
+    assert_eq!(json, r#"[200,"HTTP/1.1",{},"pong",{"request-id":[0,0,0,7]}]"#);
+
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let response = hyper_serde::deserialize_with_config::<Response<String>, _>(&mut deserializer,
+                                                                               &de_config)
+        .unwrap();
+    assert_eq!(response.extensions().get::<RequestId>(), Some(&RequestId(7)));
+}
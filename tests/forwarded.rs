@@ -0,0 +1,60 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::{Forwarded, ForwardedElement};
+
+#[test]
+fn test_forwarded() {
+    let header = r#"for=192.0.2.43, For="[2001:db8:cafe::17]:4711";proto=https;by=203.0.113.60"#;
+    let forwarded = header.parse::<Forwarded>().unwrap();
+
+    let elements = forwarded.iter().collect::<Vec<_>>();
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[0].forwarded_for.as_deref(), Some("192.0.2.43"));
+    assert_eq!(elements[1].forwarded_for.as_deref(), Some("[2001:db8:cafe::17]:4711"));
+    assert_eq!(elements[1].by.as_deref(), Some("203.0.113.60"));
+    assert_eq!(elements[1].proto.as_deref(), Some("https"));
+    assert_eq!(forwarded.to_string(),
+               r#"for=192.0.2.43, for="[2001:db8:cafe::17]:4711";by=203.0.113.60;proto=https"#);
+    assert_eq!(forwarded.to_string().parse::<Forwarded>().unwrap(), forwarded);
+
+    let json = serde_json::to_string(&forwarded).unwrap();
+    assert_eq!(json,
+               r#"[["192.0.2.43",null,null,null],["[2001:db8:cafe::17]:4711","203.0.113.60",null,"https"]]"#);
+    assert_eq!(serde_json::from_str::<Forwarded>(&json).unwrap(), forwarded);
+}
+
+#[test]
+fn test_forwarded_parameters() {
+    let header = r#"for=_hidden; host="example.com"; ext="a, b";proto=http,,for=unknown"#;
+    let forwarded = header.parse::<Forwarded>().unwrap();
+    assert_eq!(forwarded.len(), 2);
+    assert_eq!(forwarded.iter().next().unwrap().host.as_deref(), Some("example.com"));
+    assert_eq!(forwarded.to_string(), "for=_hidden;host=example.com;proto=http, for=unknown");
+}
+
+#[test]
+fn test_x_forwarded_for() {
+    let forwarded = Forwarded::from_x_forwarded_for("203.0.113.195, 2001:db8::17 ,").unwrap();
+    assert_eq!(forwarded.to_string(), r#"for=203.0.113.195, for="[2001:db8::17]""#);
+    assert_eq!(forwarded.to_x_forwarded_for(), "203.0.113.195, 2001:db8::17");
+
+    let forwarded = r#"proto=https, for="[2001:db8::17]:80""#.parse::<Forwarded>().unwrap();
+    assert_eq!(forwarded.to_x_forwarded_for(), "[2001:db8::17]:80");
+}
+
+#[test]
+fn test_forwarded_invalid() {
+    assert!("for".parse::<Forwarded>().is_err());
+    assert!("for=a;for=b".parse::<Forwarded>().is_err());
+    assert!(r#"for="[::1]"#.parse::<Forwarded>().is_err());
+    assert!("for=[::1]".parse::<Forwarded>().is_err());
+    assert!("for=a b".parse::<Forwarded>().is_err());
+    assert!("f(r=a".parse::<Forwarded>().is_err());
+    assert!("".parse::<Forwarded>().unwrap().is_empty());
+
+    let mut forwarded = Forwarded::default();
+    assert!(forwarded.push(ForwardedElement::default()).is_err());
+    assert!(serde_json::from_str::<Forwarded>(r#"[["a\nb",null,null,null]]"#).is_err());
+    assert!(serde_json::from_str::<Forwarded>(r#"[[null,null,null,null]]"#).is_err());
+}
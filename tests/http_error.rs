@@ -0,0 +1,87 @@
+extern crate http;
+extern crate hyper;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header::HeaderValue;
+use http::{Method, Request, Uri};
+use hyper::body::{Body, HttpBody};
+use hyper_serde::{HttpErrorKind, HyperErrorKind, SerializableHttpError};
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(clone(std::ptr::null())) }
+}
+
+#[test]
+fn test_hyper_error() {
+    let (sender, mut body) = Body::channel();
+    sender.abort();
+    let waker = noop_waker();
+    let error = match Pin::new(&mut body).poll_data(&mut Context::from_waker(&waker)) {
+        Poll::Ready(Some(Err(error))) => error,
+        _ => panic!("expected an aborted body"),
+    };
+    let message = error.to_string();
+    let uri = Uri::from_static("https://example.com/upload");
+    let error = SerializableHttpError::from(error).with_uri(uri.clone());
+
+    assert_eq!(error, SerializableHttpError::Hyper {
+        kind: HyperErrorKind::BodyWriteAborted,
+        message: message.clone(),
+        uri: Some(uri),
+    });
+    let json = serde_json::to_string(&error).unwrap();
+    assert_eq!(json, format!(
+        r#"["hyper","body-write-aborted","{}","https://example.com/upload"]"#, message));
+    assert_eq!(serde_json::from_str::<SerializableHttpError>(&json).unwrap(), error);
+    assert_eq!(error.to_string(), format!("{} (https://example.com/upload)", message));
+}
+
+#[test]
+fn test_http_error() {
+    let error = Request::builder().method("GET\n").body(()).unwrap_err();
+    let message = error.to_string();
+    let error = SerializableHttpError::from(error);
+
+    assert_eq!(error, SerializableHttpError::Http {
+        kind: HttpErrorKind::Method,
+        message: message.clone(),
+        uri: None,
+    });
+    assert_eq!(error.message(), message);
+    assert_eq!(error.uri(), None);
+    let json = serde_json::to_string(&error).unwrap();
+    assert_eq!(json, format!(r#"["http","method","{}",null]"#, message));
+    assert_eq!(serde_json::from_str::<SerializableHttpError>(&json).unwrap(), error);
+}
+
+#[test]
+fn test_http_error_kinds() {
+    let kind = |error: SerializableHttpError| match error {
+        SerializableHttpError::Http { kind, .. } => kind,
+        SerializableHttpError::Hyper { .. } => panic!("expected an http error"),
+    };
+
+    assert_eq!(kind("\n".parse::<Uri>().unwrap_err().into()), HttpErrorKind::Uri);
+    assert_eq!(kind(Method::from_bytes(b"\n").unwrap_err().into()), HttpErrorKind::Method);
+    assert_eq!(kind(HeaderValue::from_bytes(b"\n").unwrap_err().into()),
+               HttpErrorKind::HeaderValue);
+    assert_eq!(kind(http::StatusCode::from_u16(0).unwrap_err().into()),
+               HttpErrorKind::StatusCode);
+}
+
+#[test]
+fn test_invalid() {
+    let parse = serde_json::from_str::<SerializableHttpError>;
+
+    assert!(parse(r#"["tokio","other","",null]"#).is_err());
+    assert!(parse(r#"["http","parse","",null]"#).is_err());
+    assert!(parse(r#"["hyper","parse",""]"#).is_err());
+}
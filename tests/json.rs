@@ -0,0 +1,30 @@
+#![cfg(feature = "serde_json")]
+
+extern crate http;
+extern crate hyper_serde;
+#[macro_use]
+extern crate serde_json;
+
+use http::header::{self, HeaderMap, HeaderValue};
+use http::{Method, StatusCode};
+
+#[test]
+fn test_value_round_trip() {
+    let mut headers = HeaderMap::new();
+    headers.append(header::ACCEPT, HeaderValue::from_static("text/html"));
+    headers.append(header::ACCEPT, HeaderValue::from_static("*/*"));
+
+    let value = hyper_serde::to_value(&headers).unwrap();
+    assert_eq!(value, json!({"accept": [b"text/html", b"*/*"]}));
+    assert_eq!(hyper_serde::from_value::<HeaderMap>(value).unwrap(), headers);
+
+    let value = hyper_serde::to_value(&StatusCode::NOT_FOUND).unwrap();
+    assert_eq!(value, json!(404));
+    assert_eq!(hyper_serde::from_value::<StatusCode>(value).unwrap(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_value_null() {
+    assert!(hyper_serde::from_value::<HeaderMap>(serde_json::Value::Null).unwrap().is_empty());
+    assert!(hyper_serde::from_value::<Method>(serde_json::Value::Null).is_err());
+}
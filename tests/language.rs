@@ -0,0 +1,27 @@
+#![cfg(feature = "language-tags")]
+
+extern crate hyper_serde;
+extern crate language_tags;
+extern crate serde_json;
+
+use hyper_serde::{AcceptLanguage, De, Ser};
+use language_tags::LanguageTag;
+
+#[test]
+fn test_language_tag() {
+    let tag = LanguageTag::parse("zh-Hant-TW").unwrap();
+    let json = serde_json::to_string(&Ser::new(&tag)).unwrap();
+    assert_eq!(json, r#""zh-Hant-TW""#);
+    assert_eq!(serde_json::from_str::<De<LanguageTag>>(&json).unwrap().into_inner(), tag);
+    assert!(serde_json::from_str::<De<LanguageTag>>(r#""en_GB""#).is_err());
+}
+
+#[test]
+fn test_accept_language_tags() {
+    let accept = "fr-CH, en-a;q=0.9, *;q=0.5, de;q=0.7".parse::<AcceptLanguage>().unwrap();
+    let tags = accept.language_tags()
+        .into_iter()
+        .map(|item| (item.item.to_string(), item.quality))
+        .collect::<Vec<_>>();
+    assert_eq!(tags, [("fr-CH".to_owned(), 1000), ("de".to_owned(), 700)]);
+}
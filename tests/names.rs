@@ -0,0 +1,23 @@
+extern crate http;
+extern crate hyper_serde;
+
+use http::header;
+use hyper_serde::{STANDARD_HEADERS, standard_header_name};
+
+#[test]
+fn test_standard_headers_sorted() {
+    assert!(STANDARD_HEADERS.windows(2).all(|w| w[0].as_str() < w[1].as_str()));
+}
+
+#[test]
+fn test_standard_header_name() {
+    for name in STANDARD_HEADERS {
+        assert_eq!(standard_header_name(name.as_str().as_bytes()).as_ref(),
+                   Some(name));
+    }
+    assert_eq!(standard_header_name(b"Content-Type"), Some(header::CONTENT_TYPE));
+    assert_eq!(standard_header_name(b"X-SERVO-ID"), None);
+    assert_eq!(standard_header_name(b""), None);
+    assert_eq!(standard_header_name(b"\xffhost"), None);
+    assert_eq!(standard_header_name(b"hos"), None);
+}
@@ -0,0 +1,124 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header::{self, HeaderValue};
+use http::{request, response, Method, Request, Response, StatusCode, Version};
+use hyper_serde::{De, DeConfig, HeaderMapFormat, Ser, SerConfig};
+
+#[test]
+fn test_request_parts() {
+    let request = Request::post("https://servo.org/form")
+        .version(Version::HTTP_2)
+        .header(header::DNT, HeaderValue::from_static("1"))
+        .body(())
+        .unwrap();
+    let (parts, ()) = request.into_parts();
+
+    let json = serde_json::to_string(&Ser::new(&parts)).unwrap();
+    assert_eq!(json, r#"["POST","https://servo.org/form","HTTP/2.0",{"dnt":[[49]]}]"#);
+
+    let de = serde_json::from_str::<De<request::Parts>>(&json).unwrap().into_inner();
+    assert_eq!(de.method, Method::POST);
+    assert_eq!(de.uri, parts.uri);
+    assert_eq!(de.version, Version::HTTP_2);
+    assert_eq!(de.headers, parts.headers);
+}
+
+#[test]
+fn test_response_parts() {
+    let response = Response::builder()
+        .status(StatusCode::NOT_MODIFIED)
+        .header(header::ETAG, HeaderValue::from_static("\"1\""))
+        .body(())
+        .unwrap();
+    let (parts, ()) = response.into_parts();
+
+    let json = serde_json::to_string(&Ser::new_pretty(&parts)).unwrap();
+    assert_eq!(json, r#"["304 Not Modified","HTTP/1.1",{"etag":["\"1\""]}]"#);
+
+    let de = serde_json::from_str::<De<response::Parts>>(&json).unwrap().into_inner();
+    assert_eq!(de.status, StatusCode::NOT_MODIFIED);
+    assert_eq!(de.version, Version::HTTP_11);
+    assert_eq!(de.headers, parts.headers);
+}
+
+#[test]
+fn test_request() {
+    let request = Request::put("/upload")
+        .header(header::DNT, HeaderValue::from_static("1"))
+        .body("hello".to_owned())
+        .unwrap();
+
+    let json = serde_json::to_string(&Ser::new(&request)).unwrap();
+    assert_eq!(json, r#"["PUT","/upload","HTTP/1.1",{"dnt":[[49]]},"hello"]"#);
+
+    let de = serde_json::from_str::<De<Request<String>>>(&json).unwrap().into_inner();
+    assert_eq!(de.method(), Method::PUT);
+    assert_eq!(de.uri(), "/upload");
+    assert_eq!(de.version(), Version::HTTP_11);
+    assert_eq!(de.headers(), request.headers());
+    assert_eq!(de.body(), "hello");
+}
+
+#[test]
+fn test_request_with_config() {
+    let request = Request::get("/")
+        .header(header::SET_COOKIE, HeaderValue::from_static("a=b"))
+        .header(header::SET_COOKIE, HeaderValue::from_static("c=d"))
+        .body(())
+        .unwrap();
+
+    let config = SerConfig::new().header_format(HeaderMapFormat::Ordered).pretty(true);
+    let json = serde_json::to_string(&Ser::with_config(&request, &config)).unwrap();
+    assert_eq!(json, r#"["GET","/","HTTP/1.1",[["set-cookie","a=b"],["set-cookie","c=d"]],null]"#);
+
+    let config = DeConfig::new().header_format(HeaderMapFormat::Ordered);
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let de: Request<()> = hyper_serde::deserialize_with_config(&mut deserializer, &config)
+        .unwrap();
+    assert_eq!(de.method(), Method::GET);
+    assert_eq!(de.uri(), "/");
+    assert_eq!(de.headers(), request.headers());
+    assert!(serde_json::from_str::<De<Request<()>>>(&json).is_err());
+}
+
+#[test]
+fn test_response() {
+    let response = Response::builder()
+        .status(StatusCode::CREATED)
+        .header(header::DNT, HeaderValue::from_static("1"))
+        .body(Some(42))
+        .unwrap();
+
+    let json = serde_json::to_string(&Ser::new(&response)).unwrap();
+    assert_eq!(json, r#"[201,"HTTP/1.1",{"dnt":[[49]]},42]"#);
+
+    let de = serde_json::from_str::<De<Response<Option<u32>>>>(&json).unwrap().into_inner();
+    assert_eq!(de.status(), StatusCode::CREATED);
+    assert_eq!(de.version(), Version::HTTP_11);
+    assert_eq!(de.headers(), response.headers());
+    assert_eq!(*de.body(), Some(42));
+}
+
+#[test]
+fn test_response_with_config() {
+    let response = Response::builder()
+        .header(header::SET_COOKIE, HeaderValue::from_static("a=b"))
+        .header(header::SET_COOKIE, HeaderValue::from_static("c=d"))
+        .body(Some(42))
+        .unwrap();
+
+    let config = SerConfig::new().header_format(HeaderMapFormat::Ordered).pretty(true);
+    let json = serde_json::to_string(&Ser::with_config(&response, &config)).unwrap();
+    assert_eq!(json, r#"["200 OK","HTTP/1.1",[["set-cookie","a=b"],["set-cookie","c=d"]],42]"#);
+
+    let config = DeConfig::new().header_format(HeaderMapFormat::Ordered);
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let de: Response<Option<u32>> =
+        hyper_serde::deserialize_with_config(&mut deserializer, &config).unwrap();
+    assert_eq!(de.status(), StatusCode::OK);
+    assert_eq!(de.headers(), response.headers());
+    assert_eq!(*de.body(), Some(42));
+    assert!(serde_json::from_str::<De<Response<Option<u32>>>>(&json).is_err());
+}
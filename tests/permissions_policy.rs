@@ -0,0 +1,38 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::{AllowlistEntry, PermissionsPolicy};
+
+#[test]
+fn test_permissions_policy() {
+    let header = r#"geolocation=(self "https://servo.org"), camera=(), fullscreen=*, usb=bogus"#;
+    let policy = header.parse::<PermissionsPolicy>().unwrap();
+
+    assert_eq!(policy.len(), 4);
+    assert_eq!(policy.get("geolocation").unwrap(),
+               [AllowlistEntry::SelfOrigin,
+                AllowlistEntry::Origin("https://servo.org".to_owned())]);
+    assert_eq!(policy.get("camera").unwrap(), []);
+    assert_eq!(policy.get("fullscreen").unwrap(), [AllowlistEntry::All]);
+    assert_eq!(policy.get("usb").unwrap(), []);
+    assert_eq!(policy.get("microphone"), None);
+    assert_eq!(policy.to_string(),
+               r#"geolocation=(self "https://servo.org"), camera=(), fullscreen=*, usb=()"#);
+
+    let json = serde_json::to_string(&policy).unwrap();
+    assert_eq!(json,
+               r#"{"geolocation":["self","https://servo.org"],"camera":[],"fullscreen":["*"],"usb":[]}"#);
+    assert_eq!(serde_json::from_str::<PermissionsPolicy>(&json).unwrap(), policy);
+}
+
+#[test]
+fn test_permissions_policy_invalid() {
+    assert!("geolocation=(self".parse::<PermissionsPolicy>().is_err());
+    assert!("Geolocation=*".parse::<PermissionsPolicy>().is_err());
+
+    let mut policy = PermissionsPolicy::default();
+    assert!(policy.insert("Camera".to_owned(), vec![]).is_err());
+    assert!(policy.insert("camera".to_owned(), vec![AllowlistEntry::Origin("é".to_owned())])
+        .is_err());
+    assert!(serde_json::from_str::<PermissionsPolicy>(r#"{"Camera":[]}"#).is_err());
+}
@@ -0,0 +1,48 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header::{self, HeaderMap, HeaderValue};
+use http::{Method, StatusCode};
+use hyper_serde::{PushPromise, PushedResponse};
+
+#[test]
+fn test_push_promise() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ACCEPT, HeaderValue::from_static("text/css"));
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/css"));
+    let push = PushPromise {
+        method: Method::GET,
+        uri: "https://servo.org/style.css".parse().unwrap(),
+        headers,
+        response: Some(PushedResponse {
+            status: StatusCode::OK,
+            headers: response_headers,
+            body: b"body{}".to_vec(),
+        }),
+    };
+
+    let json = serde_json::to_string(&push).unwrap();
+    assert_eq!(json,
+               r#"["GET","https://servo.org/style.css",{"accept":[[116,101,120,116,47,99,115,115]]},[200,{"content-type":[[116,101,120,116,47,99,115,115]]},[98,111,100,121,123,125]]]"#);
+
+    let push = serde_json::from_str::<PushPromise>(&json).unwrap();
+    assert_eq!(push.uri, "https://servo.org/style.css");
+    assert_eq!(push.headers[header::ACCEPT], "text/css");
+    let response = push.response.unwrap();
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, b"body{}");
+}
+
+#[test]
+fn test_push_promise_invalid() {
+    let json = r#"["POST","https://servo.org/",{},null]"#;
+    assert!(serde_json::from_str::<PushPromise>(json).is_err());
+
+    let json = r#"["GET","/style.css",{},null]"#;
+    assert!(serde_json::from_str::<PushPromise>(json).is_err());
+
+    let json = r#"["HEAD","https://servo.org/",{},null]"#;
+    assert!(serde_json::from_str::<PushPromise>(json).unwrap().response.is_none());
+}
@@ -0,0 +1,69 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header::{HeaderMap, HeaderValue};
+use hyper_serde::{RATELIMIT, RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET, RateLimit};
+
+const RATE_LIMIT: RateLimit = RateLimit {
+    limit: 100,
+    remaining: 50,
+    reset: 30,
+};
+
+#[test]
+fn test_rate_limit_fields() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(RateLimit::from_headers(&headers), Ok(None));
+
+    headers.insert(RATELIMIT_LIMIT.clone(), HeaderValue::from_static("100, 100;w=60"));
+    assert!(RateLimit::from_headers(&headers).is_err());
+
+    headers.insert(RATELIMIT_REMAINING.clone(), HeaderValue::from_static("50"));
+    headers.insert(RATELIMIT_RESET.clone(), HeaderValue::from_static("30"));
+    assert_eq!(RateLimit::from_headers(&headers), Ok(Some(RATE_LIMIT)));
+
+    let mut inserted = HeaderMap::new();
+    RATE_LIMIT.insert_into(&mut inserted);
+    assert_eq!(inserted[&RATELIMIT_LIMIT], "100");
+    assert_eq!(RateLimit::from_headers(&inserted), Ok(Some(RATE_LIMIT)));
+
+    headers.insert(RATELIMIT_RESET.clone(), HeaderValue::from_static("-1"));
+    assert!(RateLimit::from_headers(&headers).is_err());
+}
+
+#[test]
+fn test_rate_limit_combined() {
+    let mut headers = HeaderMap::new();
+    headers.insert(RATELIMIT.clone(),
+                   HeaderValue::from_static("limit=100, remaining=50, reset=30"));
+    assert_eq!(RateLimit::from_headers(&headers), Ok(Some(RATE_LIMIT)));
+    assert_eq!(RATE_LIMIT.to_string(), "limit=100, remaining=50, reset=30");
+
+    assert!("limit=100, remaining=50".parse::<RateLimit>().is_err());
+    assert!("limit=100, remaining=50, reset=?1".parse::<RateLimit>().is_err());
+    assert!("limit=100,, remaining=50, reset=30".parse::<RateLimit>().is_err());
+}
+
+#[test]
+fn test_rate_limit_clamped() {
+    let max = RateLimit { limit: 999_999_999_999_999, remaining: 0, reset: 0 };
+    let above = RateLimit { limit: 1_000_000_000_000_000, ..max };
+    let wrapping = RateLimit { limit: u64::MAX, ..max };
+    assert_eq!(max.to_string(), "limit=999999999999999, remaining=0, reset=0");
+    assert_eq!(above.to_string(), max.to_string());
+    assert_eq!(wrapping.to_string(), max.to_string());
+    assert_eq!(wrapping.to_string().parse::<RateLimit>().unwrap(), max);
+
+    let mut headers = HeaderMap::new();
+    wrapping.insert_into(&mut headers);
+    assert_eq!(headers[&RATELIMIT_LIMIT], "999999999999999");
+    assert_eq!(RateLimit::from_headers(&headers), Ok(Some(max)));
+}
+
+#[test]
+fn test_rate_limit_serde() {
+    let json = serde_json::to_string(&RATE_LIMIT).unwrap();
+    assert_eq!(json, "[100,50,30]");
+    assert_eq!(serde_json::from_str::<RateLimit>(&json).unwrap(), RATE_LIMIT);
+}
@@ -0,0 +1,30 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::header;
+use hyper_serde::RawHeaders;
+
+#[test]
+fn test_raw_headers() {
+    let json = r#"{"Host":[[98,97,103,117,101,116,116,101]],"Bad Name":[[10]]}"#;
+    let raw = serde_json::from_str::<RawHeaders>(json).unwrap();
+
+    assert_eq!(raw.len(), 2);
+    assert_eq!(raw.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+               ["Host", "Bad Name"]);
+    assert_eq!(serde_json::to_string(&raw).unwrap(), json);
+    assert!(raw.parse().is_err());
+}
+
+#[test]
+fn test_raw_headers_parse() {
+    let json = r#"{"Host": ["baguette"], "Vary": ["accept", "origin"]}"#;
+    let headers = serde_json::from_str::<RawHeaders>(json).unwrap()
+        .parse()
+        .unwrap();
+
+    assert_eq!(headers.len(), 3);
+    assert_eq!(headers[header::HOST], "baguette");
+    assert_eq!(headers.get_all(header::VARY).iter().count(), 2);
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "reqwest")]
+
+extern crate cookie;
+extern crate http;
+extern crate hyper_serde;
+extern crate reqwest;
+extern crate serde_json;
+
+use cookie::{Cookie, SameSite};
+use http::header::{self, HeaderValue};
+use http::{response, Response, StatusCode};
+use hyper_serde::{De, Ser};
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::Url;
+
+fn recorded_response() -> reqwest::Response {
+    let response = Response::builder()
+        .status(StatusCode::CREATED)
+        .header(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"))
+        .header(header::SET_COOKIE,
+                HeaderValue::from_static("id=42; Path=/; Max-Age=60; Secure; SameSite=Strict"))
+        .header(header::SET_COOKIE, HeaderValue::from_static("lang=fr; HttpOnly"))
+        .body("hello")
+        .unwrap();
+    reqwest::Response::from(response)
+}
+
+#[test]
+fn test_reqwest_response_parts() {
+    let response = recorded_response();
+    let parts = hyper_serde::reqwest_response_parts(&response);
+    assert_eq!(parts.status, StatusCode::CREATED);
+    assert_eq!(&parts.headers, response.headers());
+
+    let json = serde_json::to_string(&Ser::new(&parts)).unwrap();
+    let parts = serde_json::from_str::<De<response::Parts>>(&json).unwrap().into_inner();
+    let replayed = hyper_serde::into_reqwest_response(parts, "hello");
+    assert_eq!(replayed.status(), StatusCode::CREATED);
+    assert_eq!(replayed.headers(), response.headers());
+}
+
+#[test]
+fn test_reqwest_response_cookies() {
+    let cookies = hyper_serde::reqwest_response_cookies(&recorded_response());
+    assert_eq!(cookies.len(), 2);
+    assert_eq!(cookies[0].name_value(), ("id", "42"));
+    assert_eq!(cookies[0].path(), Some("/"));
+    assert_eq!(cookies[0].max_age(), Some(cookie::time::Duration::seconds(60)));
+    assert_eq!(cookies[0].secure(), Some(true));
+    assert_eq!(cookies[0].same_site(), Some(SameSite::Strict));
+    assert_eq!(cookies[1].name_value(), ("lang", "fr"));
+    assert_eq!(cookies[1].http_only(), Some(true));
+
+    let json = serde_json::to_string(&Ser::new(&cookies[0])).unwrap();
+    let cookie = serde_json::from_str::<De<Cookie>>(&json).unwrap().into_inner();
+    assert_eq!(cookie.to_string(), cookies[0].to_string());
+}
+
+#[test]
+fn test_add_to_reqwest_jar() {
+    let jar = Jar::default();
+    let url = Url::parse("https://servo.org/").unwrap();
+    hyper_serde::add_to_reqwest_jar(&jar, &Cookie::new("id", "42"), &url);
+    assert_eq!(jar.cookies(&url).unwrap(), "id=42");
+}
@@ -1,3 +1,4 @@
+extern crate bytes;
 extern crate cookie;
 extern crate headers;
 extern crate http;
@@ -7,13 +8,27 @@ extern crate mime;
 extern crate serde;
 extern crate time;
 
-use cookie::Cookie;
-use http::header::HeaderMap;
-use headers::ContentType;
-use hyper::{Method, StatusCode, Uri};
+use bytes::Bytes;
+use cookie::time::Duration;
+use cookie::{Cookie, CookieJar, Expiration, SameSite};
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use http::{request, response, Request, Response};
+use http::uri::{self, Authority, PathAndQuery, Scheme};
+use headers::authorization::{Basic, Bearer};
+use headers::{AcceptRanges, AccessControlAllowCredentials, AccessControlAllowHeaders,
+              AccessControlAllowMethods, AccessControlAllowOrigin, AccessControlExposeHeaders,
+              AccessControlMaxAge, AccessControlRequestHeaders, AccessControlRequestMethod, Age,
+              Allow, Authorization, CacheControl, Connection, ContentEncoding, ContentLength,
+              ContentLocation, ContentRange, ContentType, Date, ETag, Expect, Expires, Host,
+              IfMatch, IfModifiedSince, IfNoneMatch, IfUnmodifiedSince, LastModified, Location,
+              Origin, Pragma, ProxyAuthorization, Range, Referer, RetryAfter, SecWebsocketAccept,
+              SecWebsocketKey, SecWebsocketVersion, Server, SetCookie, StrictTransportSecurity, Te,
+              TransferEncoding, Upgrade, UserAgent, Vary};
+use hyper::{Method, StatusCode, Uri, Version};
 use hyper_serde::{De, Ser, Serde};
 use mime::Mime;
 use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
 use time::Tm;
 
 fn is_supported<T>()
@@ -25,12 +40,78 @@ fn is_supported<T>()
 
 #[test]
 fn supported() {
+    is_supported::<Bytes>();
     is_supported::<Cookie>();
+    is_supported::<CookieJar>();
+    is_supported::<Expiration>();
+    is_supported::<SameSite>();
+    is_supported::<ContentLength>();
+    is_supported::<ETag>();
+    is_supported::<IfMatch>();
+    is_supported::<IfNoneMatch>();
+    is_supported::<Date>();
+    is_supported::<LastModified>();
+    is_supported::<CacheControl>();
+    is_supported::<UserAgent>();
+    is_supported::<Authorization<Basic>>();
+    is_supported::<Authorization<Bearer>>();
+    is_supported::<headers::Cookie>();
+    is_supported::<SetCookie>();
+    is_supported::<Host>();
+    is_supported::<Location>();
+    is_supported::<Range>();
+    is_supported::<ContentRange>();
+    is_supported::<ContentEncoding>();
+    is_supported::<TransferEncoding>();
+    is_supported::<Allow>();
+    is_supported::<AccessControlAllowCredentials>();
+    is_supported::<AccessControlAllowHeaders>();
+    is_supported::<AccessControlAllowMethods>();
+    is_supported::<AccessControlAllowOrigin>();
+    is_supported::<AccessControlExposeHeaders>();
+    is_supported::<AccessControlMaxAge>();
+    is_supported::<AccessControlRequestHeaders>();
+    is_supported::<AccessControlRequestMethod>();
+    is_supported::<StrictTransportSecurity>();
+    is_supported::<Origin>();
+    is_supported::<Referer>();
+    is_supported::<RetryAfter>();
+    is_supported::<Vary>();
+    is_supported::<Connection>();
+    is_supported::<Upgrade>();
+    is_supported::<Age>();
+    is_supported::<Expires>();
+    is_supported::<Pragma>();
+    is_supported::<Te>();
+    is_supported::<Expect>();
+    is_supported::<AcceptRanges>();
+    is_supported::<IfModifiedSince>();
+    is_supported::<IfUnmodifiedSince>();
+    is_supported::<ProxyAuthorization<Basic>>();
+    is_supported::<ProxyAuthorization<Bearer>>();
+    is_supported::<Server>();
+    is_supported::<SecWebsocketAccept>();
+    is_supported::<SecWebsocketKey>();
+    is_supported::<SecWebsocketVersion>();
+    is_supported::<ContentLocation>();
     is_supported::<ContentType>();
     is_supported::<HeaderMap>();
+    is_supported::<HeaderName>();
+    is_supported::<HeaderValue>();
     is_supported::<Method>();
+    is_supported::<Request<Vec<u8>>>();
+    is_supported::<request::Parts>();
+    is_supported::<Response<Vec<u8>>>();
+    is_supported::<response::Parts>();
     is_supported::<Mime>();
     is_supported::<StatusCode>();
+    is_supported::<SystemTime>();
     is_supported::<Tm>();
+    is_supported::<Duration>();
     is_supported::<Uri>();
+    is_supported::<uri::Parts>();
+    is_supported::<PathAndQuery>();
+    is_supported::<Scheme>();
+    is_supported::<Authority>();
+    is_supported::<Version>();
 }
@@ -0,0 +1,58 @@
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use http::{Method, StatusCode, Uri, Version};
+use hyper_serde::{RequestLine, StatusLine};
+
+#[test]
+fn test_request_line() {
+    let line = "GET /path?query HTTP/1.1".parse::<RequestLine>().unwrap();
+    assert_eq!(line.method, Method::GET);
+    assert_eq!(line.uri, "/path?query");
+    assert_eq!(line.version, Version::HTTP_11);
+    assert_eq!(line.to_string(), "GET /path?query HTTP/1.1");
+
+    let json = serde_json::to_string(&line).unwrap();
+    assert_eq!(json, r#""GET /path?query HTTP/1.1""#);
+    assert_eq!(serde_json::from_str::<RequestLine>(&json).unwrap(), line);
+
+    let uri = Uri::from_static("servo.org:443");
+    let line = RequestLine::from((Method::CONNECT, uri, Version::HTTP_10));
+    assert_eq!(line.to_string(), "CONNECT servo.org:443 HTTP/1.0");
+}
+
+#[test]
+fn test_request_line_invalid() {
+    assert!("GET /path".parse::<RequestLine>().is_err());
+    assert!("GET /path HTTP/1.1 extra".parse::<RequestLine>().is_err());
+    assert!("GET  /path HTTP/1.1".parse::<RequestLine>().is_err());
+    assert!("GET /path HTTP/4.0".parse::<RequestLine>().is_err());
+    assert!(serde_json::from_str::<RequestLine>(r#""G(T / HTTP/1.1""#).is_err());
+}
+
+#[test]
+fn test_status_line() {
+    let line = "HTTP/1.1 404 Not Found".parse::<StatusLine>().unwrap();
+    assert_eq!(line, StatusLine {
+        version: Version::HTTP_11,
+        status: StatusCode::NOT_FOUND,
+        reason: "Not Found".to_owned(),
+    });
+    assert_eq!(line.to_string(), "HTTP/1.1 404 Not Found");
+
+    let json = serde_json::to_string(&line).unwrap();
+    assert_eq!(json, r#""HTTP/1.1 404 Not Found""#);
+    assert_eq!(serde_json::from_str::<StatusLine>(&json).unwrap(), line);
+
+    let line = "HTTP/1.0 200".parse::<StatusLine>().unwrap();
+    assert_eq!(<(_, _, _)>::from(line), (Version::HTTP_10, StatusCode::OK, String::new()));
+}
+
+#[test]
+fn test_status_line_invalid() {
+    assert!("HTTP/1.1".parse::<StatusLine>().is_err());
+    assert!("HTTP/1.1 0200 OK".parse::<StatusLine>().is_err());
+    assert!("HTTP/1.1 200 O\rK".parse::<StatusLine>().is_err());
+    assert!("HTTP/1.2 200 OK".parse::<StatusLine>().is_err());
+}
@@ -0,0 +1,101 @@
+extern crate hyper_serde;
+extern crate serde_json;
+extern crate serde_test;
+
+use hyper_serde::{TraceParent, TraceState};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+const TRACE_PARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+#[test]
+fn test_trace_parent() {
+    let parent = TRACE_PARENT.parse::<TraceParent>().unwrap();
+
+    assert_eq!(parent.version, 0);
+    assert_eq!(parent.span_id, [0xb7, 0xad, 0x6b, 0x71, 0x69, 0x20, 0x33, 0x31]);
+    assert!(parent.is_sampled());
+    assert_eq!(parent.to_string(), TRACE_PARENT);
+
+    assert_tokens(&parent.readable(),
+                  &[Token::Struct { name: "TraceParent", len: 4 },
+                    Token::Str("version"),
+                    Token::U8(0),
+                    Token::Str("trace_id"),
+                    Token::Str("0af7651916cd43dd8448eb211c80319c"),
+                    Token::Str("span_id"),
+                    Token::Str("b7ad6b7169203331"),
+                    Token::Str("flags"),
+                    Token::U8(1),
+                    Token::StructEnd]);
+    assert_tokens(&parent.compact(),
+                  &[Token::Struct { name: "TraceParent", len: 4 },
+                    Token::Str("version"),
+                    Token::U8(0),
+                    Token::Str("trace_id"),
+                    Token::Bytes(b"\x0a\xf7\x65\x19\x16\xcd\x43\xdd\x84\x48\xeb\x21\x1c\x80\x31\x9c"),
+                    Token::Str("span_id"),
+                    Token::Bytes(b"\xb7\xad\x6b\x71\x69\x20\x33\x31"),
+                    Token::Str("flags"),
+                    Token::U8(1),
+                    Token::StructEnd]);
+}
+
+#[test]
+fn test_trace_parent_invalid() {
+    for header in &["00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331",
+                    "00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01",
+                    "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+                    "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+                    "ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+                    "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-00"] {
+        assert!(header.parse::<TraceParent>().is_err(), "{}", header);
+    }
+
+    // Future versions may have more fields.
+    let header = "01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-00";
+    assert!(header.parse::<TraceParent>().is_ok());
+
+    assert_de_tokens_error::<serde_test::Readable<TraceParent>>(
+        &[Token::Struct { name: "TraceParent", len: 4 },
+          Token::Str("version"),
+          Token::U8(0),
+          Token::Str("trace_id"),
+          Token::Str("00000000000000000000000000000000"),
+          Token::Str("span_id"),
+          Token::Str("b7ad6b7169203331"),
+          Token::Str("flags"),
+          Token::U8(1),
+          Token::StructEnd],
+        "invalid trace context: trace-id is all zeroes");
+}
+
+#[test]
+fn test_trace_state() {
+    let mut state = "rojo=00f067aa0ba902b7, congo=t61rcWkgMzE".parse::<TraceState>().unwrap();
+
+    assert_eq!(state.len(), 2);
+    assert_eq!(state.get("congo"), Some("t61rcWkgMzE"));
+    assert_eq!(state.to_string(), "rojo=00f067aa0ba902b7,congo=t61rcWkgMzE");
+
+    state.insert("congo".to_owned(), "ucfJifl5GOE".to_owned()).unwrap();
+    assert_eq!(state.to_string(), "congo=ucfJifl5GOE,rojo=00f067aa0ba902b7");
+
+    let json = serde_json::to_string(&state).unwrap();
+    assert_eq!(json, r#"{"congo":"ucfJifl5GOE","rojo":"00f067aa0ba902b7"}"#);
+    assert_eq!(serde_json::from_str::<TraceState>(&json).unwrap(), state);
+}
+
+#[test]
+fn test_trace_state_invalid() {
+    assert!("Rojo=1".parse::<TraceState>().is_err());
+    assert!("rojo=1,rojo=2".parse::<TraceState>().is_err());
+    assert!("rojo=a=b".parse::<TraceState>().is_err());
+    assert!("rojo".parse::<TraceState>().is_err());
+    assert!("fw529a3039@dt=1".parse::<TraceState>().is_ok());
+
+    let members = (0..33).map(|i| format!("k{}=v", i)).collect::<Vec<_>>();
+    assert!(members.join(",").parse::<TraceState>().is_err());
+    assert!(members[..32].join(",").parse::<TraceState>().is_ok());
+
+    assert!(serde_json::from_str::<TraceState>(r#"{"rojo":"a,b"}"#).is_err());
+}
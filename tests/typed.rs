@@ -0,0 +1,605 @@
+extern crate headers;
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+extern crate serde_test;
+
+use headers::{AcceptRanges, AccessControlAllowCredentials, AccessControlAllowHeaders};
+use headers::{AccessControlAllowMethods, AccessControlAllowOrigin, AccessControlExposeHeaders};
+use headers::{AccessControlMaxAge, AccessControlRequestHeaders, AccessControlRequestMethod, Age};
+use headers::{Allow, CacheControl, Connection, ContentEncoding, ContentLength, ContentLocation};
+use headers::{ContentRange, Date, ETag, Expect, Expires, Header, Host, IfMatch, IfModifiedSince};
+use headers::{IfNoneMatch, IfUnmodifiedSince, LastModified, Location, Origin, Pragma};
+use headers::{ProxyAuthorization, Range, Referer, ReferrerPolicy, RetryAfter, SecWebsocketAccept};
+use headers::{SecWebsocketKey, SecWebsocketVersion, Server, SetCookie, StrictTransportSecurity};
+use headers::{Te, TransferEncoding, Upgrade, UserAgent, Vary};
+use headers::authorization::{Authorization, Basic, Bearer};
+use http::{HeaderName, HeaderValue, Method};
+use hyper_serde::{De, Ser, SerConfig, TypedHeaderSerde};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Configure};
+use serde_test::{Readable, Token};
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn test_content_length() {
+    let content_length = ContentLength(1024);
+    let tokens = &[Token::U64(1024)];
+
+    assert_ser_tokens(&Ser::new(&content_length), tokens);
+    assert_de_tokens(&De::new(content_length), tokens);
+}
+
+#[test]
+fn test_etag() {
+    for &tag in &["\"xyzzy\"", "W/\"xyzzy\""] {
+        let etag = tag.parse::<ETag>().unwrap();
+        assert_ser_tokens(&Ser::new(&etag), &[Token::Str(tag)]);
+        assert_de_tokens(&De::new(etag), &[Token::Str(tag)]);
+    }
+    assert_de_tokens_error::<De<ETag>>(&[Token::Str("xyzzy")], r#"invalid etag header "xyzzy""#);
+}
+
+#[test]
+fn test_if_match() {
+    let tokens = &[Token::Str("*")];
+    assert_ser_tokens(&Ser::new(&IfMatch::any()), tokens);
+    assert_de_tokens(&De::new(IfMatch::any()), tokens);
+
+    let if_match = IfMatch::from("\"xyzzy\"".parse::<ETag>().unwrap());
+    let tokens = &[Token::Str("\"xyzzy\"")];
+    assert_ser_tokens(&Ser::new(&if_match), tokens);
+    assert_de_tokens(&De::new(if_match), tokens);
+}
+
+#[test]
+fn test_if_none_match() {
+    let tokens = &[Token::Str("*")];
+    assert_ser_tokens(&Ser::new(&IfNoneMatch::any()), tokens);
+    assert_de_tokens(&De::new(IfNoneMatch::any()), tokens);
+
+    let json = r#""\"a\", W/\"b\"""#;
+    let if_none_match = serde_json::from_str::<De<IfNoneMatch>>(json).unwrap().into_inner();
+    assert!(!if_none_match.precondition_passes(&"W/\"b\"".parse().unwrap()));
+    assert!(if_none_match.precondition_passes(&"\"c\"".parse().unwrap()));
+    assert_eq!(serde_json::to_string(&Ser::new(&if_none_match)).unwrap(), json);
+}
+
+#[test]
+fn test_date() {
+    let time = UNIX_EPOCH + Duration::from_secs(784111777);
+    let date = Date::from(time);
+    let tokens = &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")];
+    assert_ser_tokens(&Ser::new(&date).readable(), tokens);
+    assert_de_tokens(&De::new(date).readable(), tokens);
+
+    let last_modified = LastModified::from(time);
+    let tokens = &[Token::U64(784111777)];
+    assert_ser_tokens(&Ser::new(&last_modified).compact(), tokens);
+    assert_de_tokens(&De::new(last_modified).compact(), tokens);
+}
+
+#[test]
+fn test_cache_control() {
+    let cache_control = CacheControl::new().with_no_cache().with_max_age(Duration::from_secs(100));
+    let tokens = &[Token::Str("no-cache, max-age=100")];
+    assert_ser_tokens(&Ser::new(&cache_control).readable(), tokens);
+    assert_de_tokens(&De::new(cache_control.clone()).readable(), tokens);
+
+    let config = SerConfig::new().directive_maps(true);
+    let json = serde_json::to_string(&Ser::with_config(&cache_control, &config)).unwrap();
+    assert_eq!(json, r#"{"no-cache":true,"max-age":100}"#);
+    let de = serde_json::from_str::<De<CacheControl>>(&json).unwrap();
+    assert_eq!(de.into_inner(), cache_control);
+
+    let json = r#"{"public":true,"no-store":false,"s-maxage":"60"}"#;
+    let de = serde_json::from_str::<De<CacheControl>>(json).unwrap();
+    let cache_control = CacheControl::new().with_public().with_s_max_age(Duration::from_secs(60));
+    assert_eq!(de.into_inner(), cache_control);
+
+    assert_de_tokens_error::<Readable<De<CacheControl>>>(
+        &[Token::Str("max-age=soon")],
+        r#"invalid cache-control header "max-age=soon""#);
+}
+
+#[test]
+fn test_user_agent() {
+    let user_agent = UserAgent::from_static("Servo/1.0 Firefox/111.0");
+    let tokens = &[Token::Str("Servo/1.0 Firefox/111.0")];
+
+    assert_ser_tokens(&Ser::new(&user_agent), tokens);
+    assert_de_tokens(&De::new(user_agent), tokens);
+    assert_de_tokens_error::<De<UserAgent>>(
+        &[Token::Str("Servo\n")],
+        r#"invalid user-agent header "Servo\n""#);
+}
+
+#[test]
+fn test_authorization() {
+    let basic = Authorization::basic("Aladdin", "open sesame");
+    let tokens = &[Token::Str("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==")];
+    assert_ser_tokens(&Ser::new(&basic), tokens);
+    assert_de_tokens(&De::new(basic.clone()), tokens);
+
+    let bearer = Authorization::bearer("mF_9.B5f-4.1JqM").unwrap();
+    let tokens = &[Token::Str("Bearer mF_9.B5f-4.1JqM")];
+    assert_ser_tokens(&Ser::new(&bearer), tokens);
+    assert_de_tokens(&De::new(bearer.clone()), tokens);
+
+    let config = SerConfig::new().redact(true);
+    assert_ser_tokens(&Ser::with_config(&basic, &config), &[Token::Str("[redacted]")]);
+    assert_ser_tokens(&Ser::with_config(&bearer, &config), &[Token::Str("[redacted]")]);
+
+    assert_de_tokens_error::<De<Authorization<Basic>>>(
+        &[Token::Str("Bearer mF_9.B5f-4.1JqM")],
+        r#"invalid authorization header "Bearer mF_9.B5f-4.1JqM""#);
+    assert!(serde_json::from_str::<De<Authorization<Bearer>>>(r#""Basic Zm9v""#).is_err());
+}
+
+#[test]
+fn test_cookie() {
+    let json = r#"[["lang","en-US"],["SID","31d4d96e407aad42"],["lang","fr"]]"#;
+    let cookie = serde_json::from_str::<De<headers::Cookie>>(json).unwrap().into_inner();
+    assert_eq!(cookie.len(), 3);
+    assert_eq!(cookie.get("SID"), Some("31d4d96e407aad42"));
+    assert_eq!(serde_json::to_string(&Ser::new(&cookie)).unwrap(), json);
+
+    let config = SerConfig::new().redact(true);
+    assert_eq!(serde_json::to_string(&Ser::with_config(&cookie, &config)).unwrap(),
+               r#"[["lang","[redacted]"],["SID","[redacted]"],["lang","[redacted]"]]"#);
+
+    let error = serde_json::from_str::<De<headers::Cookie>>(r#"[["a;b","c"]]"#).unwrap_err();
+    assert_eq!(error.to_string(), r#"invalid cookie pair ("a;b", "c")"#);
+}
+
+#[test]
+fn test_set_cookie() {
+    let json = r#"["lang=en-US; Path=/","SID=31d4d96e407aad42; Secure; HttpOnly"]"#;
+    let set_cookie = serde_json::from_str::<De<SetCookie>>(json).unwrap().into_inner();
+    assert_eq!(serde_json::to_string(&Ser::new(&set_cookie)).unwrap(), json);
+
+    let config = SerConfig::new().redact(true);
+    assert_eq!(serde_json::to_string(&Ser::with_config(&set_cookie, &config)).unwrap(),
+               r#"["[redacted]","[redacted]"]"#);
+
+    assert!(serde_json::from_str::<De<SetCookie>>("[]").is_err());
+    assert!(serde_json::from_str::<De<SetCookie>>(r#"["a=b\r\n"]"#).is_err());
+}
+
+#[test]
+fn test_host() {
+    let host = Host::from(http::uri::Authority::from_static("servo.org:8080"));
+    let tokens = &[Token::Tuple { len: 2 },
+                   Token::Str("servo.org"),
+                   Token::Some,
+                   Token::U16(8080),
+                   Token::TupleEnd];
+    assert_ser_tokens(&Ser::new(&host), tokens);
+    assert_de_tokens(&De::new(host), tokens);
+
+    let host = Host::from(http::uri::Authority::from_static("[::1]"));
+    let tokens = &[Token::Tuple { len: 2 }, Token::Str("[::1]"), Token::None, Token::TupleEnd];
+    assert_ser_tokens(&Ser::new(&host), tokens);
+    assert_de_tokens(&De::new(host), tokens);
+
+    assert_de_tokens_error::<De<Host>>(
+        &[Token::Tuple { len: 2 }, Token::Str("user@servo.org"), Token::None, Token::TupleEnd],
+        r#"invalid host "user@servo.org""#);
+}
+
+#[test]
+fn test_location() {
+    for &location in &["https://servo.org/download#nightly", "/about", "../index.html", "?q=1"] {
+        let json = serde_json::to_string(location).unwrap();
+        let de = serde_json::from_str::<De<Location>>(&json).unwrap();
+        assert_eq!(serde_json::to_string(&Ser::new(&de.into_inner())).unwrap(), json);
+    }
+    assert_de_tokens_error::<De<Location>>(
+        &[Token::Str("https://servo.org/a b")],
+        r#"invalid location header "https://servo.org/a b": not a URI reference"#);
+}
+
+#[test]
+fn test_range() {
+    let range = Range::bytes(0..500).unwrap();
+    assert_ser_tokens(&Ser::new(&range), &[Token::Str("bytes=0-499")]);
+    assert_de_tokens(&De::new(range), &[Token::Str("bytes=0-499")]);
+
+    let json = r#""bytes=0-499, 1000-, -500""#;
+    let range = serde_json::from_str::<De<Range>>(json).unwrap().into_inner();
+    assert_eq!(range.iter().count(), 3);
+    assert_eq!(serde_json::to_string(&Ser::new(&range)).unwrap(), json);
+
+    assert_de_tokens_error::<De<Range>>(
+        &[Token::Str("lines=1-2")],
+        r#"invalid range header "lines=1-2""#);
+}
+
+#[test]
+fn test_content_range() {
+    let content_range = ContentRange::bytes(0..500, 1234).unwrap();
+    let tokens = &[Token::Str("bytes 0-499/1234")];
+    assert_ser_tokens(&Ser::new(&content_range), tokens);
+    assert_de_tokens(&De::new(content_range), tokens);
+
+    let content_range = ContentRange::unsatisfied_bytes(1234);
+    let tokens = &[Token::Str("bytes */1234")];
+    assert_ser_tokens(&Ser::new(&content_range), tokens);
+    assert_de_tokens(&De::new(content_range), tokens);
+}
+
+#[test]
+fn test_content_encoding() {
+    let json = r#"["deflate","gzip"]"#;
+    let content_encoding = serde_json::from_str::<De<ContentEncoding>>(json).unwrap().into_inner();
+    assert!(content_encoding.contains("gzip"));
+    assert_eq!(serde_json::to_string(&Ser::new(&content_encoding)).unwrap(), json);
+
+    let tokens = &[Token::Seq { len: None }, Token::Str("gzip"), Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&ContentEncoding::gzip()), tokens);
+
+    assert_de_tokens_error::<De<ContentEncoding>>(
+        &[Token::Seq { len: Some(1) }, Token::Str("gzip, br"), Token::SeqEnd],
+        r#"invalid content-encoding header element "gzip, br""#);
+}
+
+#[test]
+fn test_transfer_encoding() {
+    let json = r#"["gzip","chunked"]"#;
+    let transfer_encoding = serde_json::from_str::<De<TransferEncoding>>(json).unwrap();
+    let transfer_encoding = transfer_encoding.into_inner();
+    assert!(transfer_encoding.is_chunked());
+    assert_eq!(serde_json::to_string(&Ser::new(&transfer_encoding)).unwrap(), json);
+}
+
+#[test]
+fn test_allow() {
+    let allow = vec![Method::GET, Method::HEAD].into_iter().collect::<Allow>();
+    let tokens = &[Token::Seq { len: Some(2) },
+                   Token::Str("GET"),
+                   Token::Str("HEAD"),
+                   Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&allow), tokens);
+    assert_de_tokens(&De::new(allow), tokens);
+}
+
+#[test]
+fn test_access_control_allow_origin() {
+    for &origin in &["*", "null", "https://servo.org"] {
+        let json = serde_json::to_string(origin).unwrap();
+        let de = serde_json::from_str::<De<AccessControlAllowOrigin>>(&json).unwrap();
+        assert_eq!(serde_json::to_string(&Ser::new(&de.into_inner())).unwrap(), json);
+    }
+    assert_ser_tokens(&Ser::new(&AccessControlAllowOrigin::ANY), &[Token::Str("*")]);
+    assert_de_tokens(&De::new(AccessControlAllowOrigin::NULL), &[Token::Str("null")]);
+}
+
+#[test]
+fn test_access_control_lists() {
+    let methods = vec![Method::GET, Method::PUT].into_iter();
+    let methods = methods.collect::<AccessControlAllowMethods>();
+    let tokens = &[Token::Seq { len: Some(2) },
+                   Token::Str("GET"),
+                   Token::Str("PUT"),
+                   Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&methods), tokens);
+    assert_de_tokens(&De::new(methods), tokens);
+
+    let names = || vec![http::header::ACCEPT, http::header::CONTENT_TYPE].into_iter();
+    let tokens = &[Token::Seq { len: Some(2) },
+                   Token::Str("accept"),
+                   Token::Str("content-type"),
+                   Token::SeqEnd];
+    let allow_headers = names().collect::<AccessControlAllowHeaders>();
+    assert_ser_tokens(&Ser::new(&allow_headers), tokens);
+    assert_de_tokens(&De::new(allow_headers), tokens);
+    let expose_headers = names().collect::<AccessControlExposeHeaders>();
+    assert_ser_tokens(&Ser::new(&expose_headers), tokens);
+    let request_headers = names().collect::<AccessControlRequestHeaders>();
+    assert_ser_tokens(&Ser::new(&request_headers), tokens);
+
+    let json = r#"["accept","content-type"]"#;
+    let de = serde_json::from_str::<De<AccessControlExposeHeaders>>(json).unwrap();
+    assert_eq!(de.into_inner().iter().collect::<Vec<_>>(), names().collect::<Vec<_>>());
+    let de = serde_json::from_str::<De<AccessControlRequestHeaders>>(json).unwrap();
+    assert_eq!(de.into_inner().iter().collect::<Vec<_>>(), names().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_access_control_scalars() {
+    let credentials = AccessControlAllowCredentials;
+    assert_ser_tokens(&Ser::new(&credentials), &[Token::Unit]);
+    assert_de_tokens(&De::new(credentials), &[Token::Unit]);
+
+    let max_age = AccessControlMaxAge::from(Duration::from_secs(600));
+    assert_ser_tokens(&Ser::new(&max_age), &[Token::U64(600)]);
+    assert_de_tokens(&De::new(max_age), &[Token::U64(600)]);
+
+    let request_method = AccessControlRequestMethod::from(Method::DELETE);
+    assert_ser_tokens(&Ser::new(&request_method), &[Token::Str("DELETE")]);
+    assert_de_tokens(&De::new(request_method), &[Token::Str("DELETE")]);
+}
+
+#[test]
+fn test_strict_transport_security() {
+    let hsts = StrictTransportSecurity::including_subdomains(Duration::from_secs(31536000));
+    let tokens = &[Token::Str("max-age=31536000; includeSubdomains")];
+    assert_ser_tokens(&Ser::new(&hsts).readable(), tokens);
+    assert_de_tokens(&De::new(hsts.clone()).readable(), tokens);
+
+    let config = SerConfig::new().directive_maps(true);
+    let json = serde_json::to_string(&Ser::with_config(&hsts, &config)).unwrap();
+    assert_eq!(json, r#"{"max-age":31536000,"includeSubdomains":true}"#);
+    let de = serde_json::from_str::<De<StrictTransportSecurity>>(&json).unwrap();
+    assert_eq!(de.into_inner(), hsts);
+
+    let json = r#"{"max-age":600,"preload":true}"#;
+    let de = serde_json::from_str::<De<StrictTransportSecurity>>(json).unwrap();
+    let hsts = StrictTransportSecurity::excluding_subdomains(Duration::from_secs(600));
+    assert_eq!(de.into_inner(), hsts);
+
+    assert!(serde_json::from_str::<De<StrictTransportSecurity>>(r#"{"preload":true}"#).is_err());
+}
+
+#[test]
+fn test_origin() {
+    let origin = Origin::try_from_parts("https", "servo.org", 8000).unwrap();
+    let tokens = &[Token::Some, Token::Str("https://servo.org:8000")];
+    assert_ser_tokens(&Ser::new(&origin), tokens);
+    assert_de_tokens(&De::new(origin), tokens);
+
+    assert_ser_tokens(&Ser::new(&Origin::NULL), &[Token::None]);
+    assert_de_tokens(&De::new(Origin::NULL), &[Token::None]);
+    assert_de_tokens(&De::new(Origin::NULL), &[Token::Some, Token::Str("null")]);
+
+    assert_de_tokens_error::<De<Origin>>(
+        &[Token::Some, Token::Str("https://servo.org/about")],
+        r#"invalid origin header "https://servo.org/about""#);
+}
+
+#[test]
+fn test_referer() {
+    for &referer in &["https://servo.org/download", "/People.html#tim"] {
+        let json = serde_json::to_string(referer).unwrap();
+        let de = serde_json::from_str::<De<Referer>>(&json).unwrap();
+        assert_eq!(serde_json::to_string(&Ser::new(&de.into_inner())).unwrap(), json);
+    }
+    assert_de_tokens_error::<De<Referer>>(
+        &[Token::Str("https://servo.org/a b")],
+        r#"invalid referer header "https://servo.org/a b": not a URI reference"#);
+}
+
+#[test]
+fn test_retry_after() {
+    let delay = RetryAfter::delay(Duration::from_secs(120));
+    assert_ser_tokens(&Ser::new(&delay).readable(), &[Token::U64(120)]);
+    assert_de_tokens(&De::new(delay.clone()).readable(), &[Token::U64(120)]);
+    assert_de_tokens(&De::new(delay.clone()).readable(), &[Token::Str("120")]);
+    assert_ser_tokens(&Ser::new(&delay).compact(), &[Token::Str("120")]);
+    assert_de_tokens(&De::new(delay).compact(), &[Token::Str("120")]);
+
+    let date = RetryAfter::date(UNIX_EPOCH + Duration::from_secs(784887151));
+    let tokens = &[Token::Str("Tue, 15 Nov 1994 08:12:31 GMT")];
+    assert_ser_tokens(&Ser::new(&date).readable(), tokens);
+    assert_de_tokens(&De::new(date.clone()).readable(), tokens);
+    assert_ser_tokens(&Ser::new(&date).compact(), tokens);
+    assert_de_tokens(&De::new(date).compact(), tokens);
+
+    assert_de_tokens_error::<Readable<De<RetryAfter>>>(
+        &[Token::Str("soon")],
+        r#"invalid retry-after header "soon""#);
+}
+
+#[test]
+fn test_vary() {
+    let json = r#"["accept-encoding","accept-language"]"#;
+    let vary = serde_json::from_str::<De<Vary>>(json).unwrap().into_inner();
+    assert!(!vary.is_any());
+    assert_eq!(vary.iter_strs().collect::<Vec<_>>(), ["accept-encoding", "accept-language"]);
+    assert_eq!(serde_json::to_string(&Ser::new(&vary)).unwrap(), json);
+
+    let tokens = &[Token::Seq { len: None }, Token::Str("*"), Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&Vary::any()), tokens);
+    assert_de_tokens(&De::new(Vary::any()), &[Token::Seq { len: Some(1) },
+                                              Token::Str("*"),
+                                              Token::SeqEnd]);
+}
+
+#[test]
+fn test_connection() {
+    let json = r#"["keep-alive","Upgrade"]"#;
+    let connection = serde_json::from_str::<De<Connection>>(json).unwrap().into_inner();
+    assert!(connection.contains("keep-alive"));
+    assert!(connection.contains("upgrade"));
+    assert_eq!(serde_json::to_string(&Ser::new(&connection)).unwrap(), json);
+
+    let tokens = &[Token::Seq { len: None }, Token::Str("close"), Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&Connection::close()), tokens);
+}
+
+#[test]
+fn test_upgrade() {
+    let tokens = &[Token::Seq { len: None }, Token::Str("websocket"), Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&Upgrade::websocket()), tokens);
+    assert_de_tokens(&De::new(Upgrade::websocket()), &[Token::Seq { len: Some(1) },
+                                                       Token::Str("websocket"),
+                                                       Token::SeqEnd]);
+
+    let json = r#"["HTTP/2.0","SHTTP/1.3","IRC/6.9"]"#;
+    let upgrade = serde_json::from_str::<De<Upgrade>>(json).unwrap().into_inner();
+    assert_eq!(serde_json::to_string(&Ser::new(&upgrade)).unwrap(), json);
+}
+
+#[test]
+fn test_expires() {
+    let expires = Expires::from(UNIX_EPOCH + Duration::from_secs(784111777));
+    let tokens = &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")];
+    assert_ser_tokens(&Ser::new(&expires).readable(), tokens);
+    assert_de_tokens(&De::new(expires).readable(), tokens);
+    assert_ser_tokens(&Ser::new(&expires).compact(), &[Token::U64(784111777)]);
+}
+
+#[test]
+fn test_age() {
+    let age = Age::from_secs(3600);
+    assert_ser_tokens(&Ser::new(&age), &[Token::U64(3600)]);
+    assert_de_tokens(&De::new(age), &[Token::U64(3600)]);
+}
+
+#[test]
+fn test_pragma() {
+    let pragma = Pragma::no_cache();
+    assert_ser_tokens(&Ser::new(&pragma), &[Token::Str("no-cache")]);
+    assert_de_tokens(&De::new(pragma), &[Token::Str("no-cache")]);
+
+    let pragma = serde_json::from_str::<De<Pragma>>(r#""x-custom""#).unwrap().into_inner();
+    assert!(!pragma.is_no_cache());
+}
+
+#[test]
+fn test_te() {
+    let tokens = &[Token::Seq { len: None }, Token::Str("trailers"), Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&Te::trailers()), tokens);
+    assert_de_tokens(&De::new(Te::trailers()), &[Token::Seq { len: Some(1) },
+                                                 Token::Str("trailers"),
+                                                 Token::SeqEnd]);
+
+    let json = r#"["trailers","deflate;q=0.5"]"#;
+    let te = serde_json::from_str::<De<Te>>(json).unwrap().into_inner();
+    assert_eq!(serde_json::to_string(&Ser::new(&te)).unwrap(), json);
+}
+
+#[test]
+fn test_expect() {
+    assert_ser_tokens(&Ser::new(&Expect::CONTINUE), &[Token::Unit]);
+    assert_de_tokens(&De::new(Expect::CONTINUE), &[Token::Unit]);
+}
+
+#[test]
+fn test_accept_ranges() {
+    let tokens = &[Token::Seq { len: None }, Token::Str("bytes"), Token::SeqEnd];
+    assert_ser_tokens(&Ser::new(&AcceptRanges::bytes()), tokens);
+    assert_de_tokens(&De::new(AcceptRanges::bytes()), &[Token::Seq { len: Some(1) },
+                                                        Token::Str("bytes"),
+                                                        Token::SeqEnd]);
+
+    let json = r#"["none"]"#;
+    let accept_ranges = serde_json::from_str::<De<AcceptRanges>>(json).unwrap().into_inner();
+    assert_ne!(accept_ranges, AcceptRanges::bytes());
+    assert_eq!(serde_json::to_string(&Ser::new(&accept_ranges)).unwrap(), json);
+}
+
+#[test]
+fn test_conditional_dates() {
+    let time = UNIX_EPOCH + Duration::from_secs(784111777);
+    let tokens = &[Token::Str("Sun, 06 Nov 1994 08:49:37 GMT")];
+
+    let if_modified_since = IfModifiedSince::from(time);
+    assert_ser_tokens(&Ser::new(&if_modified_since).readable(), tokens);
+    assert_de_tokens(&De::new(if_modified_since).readable(), tokens);
+
+    let if_unmodified_since = IfUnmodifiedSince::from(time);
+    assert_ser_tokens(&Ser::new(&if_unmodified_since).readable(), tokens);
+    assert_de_tokens(&De::new(if_unmodified_since).readable(), tokens);
+    assert_ser_tokens(&Ser::new(&if_unmodified_since).compact(), &[Token::U64(784111777)]);
+}
+
+#[test]
+fn test_proxy_authorization() {
+    let basic = ProxyAuthorization(Authorization::basic("Aladdin", "open sesame").0);
+    let tokens = &[Token::Str("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==")];
+    assert_ser_tokens(&Ser::new(&basic), tokens);
+    assert_de_tokens(&De::new(basic.clone()), tokens);
+
+    let bearer = ProxyAuthorization(Authorization::bearer("mF_9.B5f-4.1JqM").unwrap().0);
+    let tokens = &[Token::Str("Bearer mF_9.B5f-4.1JqM")];
+    assert_ser_tokens(&Ser::new(&bearer), tokens);
+    assert_de_tokens(&De::new(bearer.clone()), tokens);
+
+    let config = SerConfig::new().redact(true);
+    assert_ser_tokens(&Ser::with_config(&basic, &config), &[Token::Str("[redacted]")]);
+    assert_ser_tokens(&Ser::with_config(&bearer, &config), &[Token::Str("[redacted]")]);
+
+    assert_de_tokens_error::<De<ProxyAuthorization<Basic>>>(
+        &[Token::Str("Bearer mF_9.B5f-4.1JqM")],
+        r#"invalid proxy-authorization header "Bearer mF_9.B5f-4.1JqM""#);
+}
+
+#[test]
+fn test_server() {
+    let server = Server::from_static("Apache/2.4.1 (Unix)");
+    assert_ser_tokens(&Ser::new(&server), &[Token::Str("Apache/2.4.1 (Unix)")]);
+    assert_de_tokens(&De::new(server), &[Token::Str("Apache/2.4.1 (Unix)")]);
+}
+
+#[test]
+fn test_sec_websocket() {
+    let json = r#""dGhlIHNhbXBsZSBub25jZQ==""#;
+    let key = serde_json::from_str::<De<SecWebsocketKey>>(json).unwrap().into_inner();
+    let tokens = &[Token::Str("dGhlIHNhbXBsZSBub25jZQ==")];
+    assert_ser_tokens(&Ser::new(&key), tokens);
+    assert_de_tokens(&De::new(key.clone()), tokens);
+
+    let accept = SecWebsocketAccept::from(key);
+    let tokens = &[Token::Str("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")];
+    assert_ser_tokens(&Ser::new(&accept), tokens);
+    assert_de_tokens(&De::new(accept), tokens);
+
+    assert_ser_tokens(&Ser::new(&SecWebsocketVersion::V13), &[Token::U8(13)]);
+    assert_de_tokens(&De::new(SecWebsocketVersion::V13), &[Token::U8(13)]);
+    assert_de_tokens_error::<De<SecWebsocketVersion>>(
+        &[Token::U8(8)],
+        r#"invalid sec-websocket-version header "8""#);
+}
+
+#[test]
+fn test_content_location() {
+    for &location in &["https://servo.org/index.en.html", "/index.fr.html", "index.de.html"] {
+        let json = serde_json::to_string(location).unwrap();
+        let de = serde_json::from_str::<De<ContentLocation>>(&json).unwrap();
+        assert_eq!(serde_json::to_string(&Ser::new(&de.into_inner())).unwrap(), json);
+    }
+    assert_de_tokens_error::<De<ContentLocation>>(
+        &[Token::Str("/index html")],
+        r#"invalid content-location header "/index html": not a URI reference"#);
+}
+
+#[derive(Debug, PartialEq)]
+struct Dnt(bool);
+
+static DNT: HeaderName = HeaderName::from_static("dnt");
+
+impl Header for Dnt {
+    fn name() -> &'static HeaderName {
+        &DNT
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+        where I: Iterator<Item = &'i HeaderValue>,
+    {
+        match values.next().map(HeaderValue::as_bytes) {
+            Some(b"0") => Ok(Dnt(false)),
+            Some(b"1") => Ok(Dnt(true)),
+            _ => Err(headers::Error::invalid()),
+        }
+    }
+
+    fn encode<E>(&self, values: &mut E)
+        where E: Extend<HeaderValue>,
+    {
+        values.extend(Some(HeaderValue::from_static(if self.0 { "1" } else { "0" })));
+    }
+}
+
+#[test]
+fn test_typed_header_serde() {
+    let policy = TypedHeaderSerde(ReferrerPolicy::NO_REFERRER);
+    assert_ser_tokens(&policy, &[Token::Str("no-referrer")]);
+    assert_de_tokens(&policy, &[Token::Str("no-referrer")]);
+
+    let dnt = TypedHeaderSerde(Dnt(true));
+    assert_ser_tokens(&dnt, &[Token::Str("1")]);
+    assert_de_tokens(&dnt, &[Token::Str("1")]);
+    assert_de_tokens_error::<TypedHeaderSerde<Dnt>>(
+        &[Token::Str("yes")],
+        r#"invalid dnt header "yes""#);
+}
@@ -0,0 +1,41 @@
+extern crate hyper;
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper::Uri;
+use hyper_serde::UriWithFragment;
+
+#[test]
+fn test_uri_with_fragment() {
+    let uri = "https://example.com/doc?q=1#section-2".parse::<UriWithFragment>().unwrap();
+    assert_eq!(uri.uri, "https://example.com/doc?q=1");
+    assert_eq!(uri.fragment.as_deref(), Some("section-2"));
+    assert_eq!(uri.to_string(), "https://example.com/doc?q=1#section-2");
+
+    let json = serde_json::to_string(&uri).unwrap();
+    assert_eq!(json, r#""https://example.com/doc?q=1#section-2""#);
+    assert_eq!(serde_json::from_str::<UriWithFragment>(&json).unwrap(), uri);
+
+    let uri = "/path#".parse::<UriWithFragment>().unwrap();
+    assert_eq!(uri.fragment.as_deref(), Some(""));
+    assert_eq!(uri.to_string(), "/path#");
+}
+
+#[test]
+fn test_conversions() {
+    let uri = UriWithFragment::from(Uri::from_static("/index.html"));
+    assert_eq!(uri.fragment, None);
+    assert_eq!(uri.to_string(), "/index.html");
+    assert_eq!(Uri::from(uri), "/index.html");
+
+    let uri = UriWithFragment::new(Uri::from_static("/"), Some("top".to_owned())).unwrap();
+    assert_eq!(uri.into_uri(), "/");
+}
+
+#[test]
+fn test_invalid() {
+    assert!(UriWithFragment::new(Uri::from_static("/"), Some("a b".to_owned())).is_err());
+    assert!("/#caf\u{e9}".parse::<UriWithFragment>().is_err());
+    assert!("not a uri#top".parse::<UriWithFragment>().is_err());
+    assert!(serde_json::from_str::<UriWithFragment>(r#""/#\n""#).is_err());
+}
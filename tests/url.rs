@@ -0,0 +1,33 @@
+#![cfg(feature = "url")]
+
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+extern crate url;
+
+use http::Uri;
+use hyper_serde::{De, Ser, UriWithFragment};
+use url::Url;
+
+#[test]
+fn test_url() {
+    let url = Url::parse("https://servo.org/path?q=1#section").unwrap();
+    let json = serde_json::to_string(&Ser::new(&url)).unwrap();
+    assert_eq!(json, r#""https://servo.org/path?q=1#section""#);
+    assert_eq!(serde_json::from_str::<De<Url>>(&json).unwrap().into_inner(), url);
+    assert!(serde_json::from_str::<De<Url>>(r#""/path""#).is_err());
+}
+
+#[test]
+fn test_url_conversions() {
+    let url = Url::parse("https://servo.org/path?q=1#section").unwrap();
+    let uri = hyper_serde::uri_from_url(&url).unwrap();
+    assert_eq!(uri.uri, "https://servo.org/path?q=1");
+    assert_eq!(uri.fragment.as_deref(), Some("section"));
+    assert_eq!(hyper_serde::url_from_uri(&uri).unwrap(), url);
+
+    let uri = UriWithFragment::from(Uri::from_static("/path"));
+    assert!(hyper_serde::url_from_uri(&uri).is_err());
+    let url = Url::parse("data:text/plain,hello").unwrap();
+    assert!(hyper_serde::uri_from_url(&url).is_err());
+}
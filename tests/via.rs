@@ -0,0 +1,63 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::{Via, ViaEntry};
+
+#[test]
+fn test_via() {
+    let header = "1.0 fred, 1.1 p.example.net:8080 (Apache/1.1 (Unix), mod_ssl), HTTP/2 [::1]";
+    let via = header.parse::<Via>().unwrap();
+
+    let entries = via.iter().collect::<Vec<_>>();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].received_by, "fred");
+    assert_eq!(entries[1].comment.as_deref(), Some("Apache/1.1 (Unix), mod_ssl"));
+    assert_eq!(entries[2].protocol.as_deref(), Some("HTTP"));
+    assert_eq!(entries[2].version, "2");
+    assert_eq!(via.to_string(), header);
+
+    let json = serde_json::to_string(&via).unwrap();
+    assert_eq!(json,
+               r#"[[null,"1.0","fred",null],[null,"1.1","p.example.net:8080","Apache/1.1 (Unix), mod_ssl"],["HTTP","2","[::1]",null]]"#);
+    assert_eq!(serde_json::from_str::<Via>(&json).unwrap(), via);
+}
+
+#[test]
+fn test_via_comment_quoted_pairs() {
+    for header in &[r"1.1 proxy (a \(b\))", r"1.1 proxy (a \(b)", r"1.1 proxy (a (b (c)) \))"] {
+        let via = header.parse::<Via>().unwrap();
+        assert_eq!(via.to_string(), *header);
+    }
+    let via = r"1.1 proxy (a \(b)".parse::<Via>().unwrap();
+    assert_eq!(via.iter().next().unwrap().comment.as_deref(), Some(r"a \(b"));
+
+    let mut via = Via::default();
+    let entry = |comment: &str| ViaEntry {
+        protocol: None,
+        version: "1.1".to_owned(),
+        received_by: "fred".to_owned(),
+        comment: Some(comment.to_owned()),
+    };
+    assert!(via.push(entry(r"a \)")).is_ok());
+    assert!(via.push(entry(r"a \")).is_err());
+    assert!(via.push(entry("a \\\n")).is_err());
+}
+
+#[test]
+fn test_via_invalid() {
+    assert!("1.1".parse::<Via>().is_err());
+    assert!("1.1 fred (Apache".parse::<Via>().is_err());
+    assert!("1.1 fred (Apache) x".parse::<Via>().is_err());
+    assert!("".parse::<Via>().unwrap().is_empty());
+
+    let mut via = Via::default();
+    let entry = ViaEntry {
+        protocol: None,
+        version: "1.1".to_owned(),
+        received_by: "fred".to_owned(),
+        comment: Some("unbalanced (".to_owned()),
+    };
+    assert!(via.push(entry).is_err());
+
+    assert!(serde_json::from_str::<Via>(r#"[[null,"1.1","fr ed",null]]"#).is_err());
+}
@@ -0,0 +1,50 @@
+#![cfg(target_arch = "wasm32")]
+
+extern crate cookie;
+extern crate http;
+extern crate hyper_serde;
+extern crate serde_json;
+extern crate wasm_bindgen_test;
+
+use cookie::Cookie;
+use http::header::{self, HeaderMap, HeaderValue};
+use http::{Method, StatusCode, Uri};
+use hyper_serde::{De, Ser};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/html"));
+    let json = serde_json::to_string(&Ser::new(&headers)).unwrap();
+    assert_eq!(serde_json::from_str::<De<HeaderMap>>(&json).unwrap().into_inner(), headers);
+}
+
+#[wasm_bindgen_test]
+fn test_request_line() {
+    let json = serde_json::to_string(&Ser::new(&Method::POST)).unwrap();
+    assert_eq!(serde_json::from_str::<De<Method>>(&json).unwrap().into_inner(), Method::POST);
+
+    let uri = "https://servo.org/?q=1".parse::<Uri>().unwrap();
+    let json = serde_json::to_string(&Ser::new(&uri)).unwrap();
+    assert_eq!(serde_json::from_str::<De<Uri>>(&json).unwrap().into_inner(), uri);
+
+    let json = serde_json::to_string(&Ser::new(&StatusCode::OK)).unwrap();
+    assert_eq!(serde_json::from_str::<De<StatusCode>>(&json).unwrap().into_inner(),
+               StatusCode::OK);
+}
+
+#[wasm_bindgen_test]
+fn test_dates() {
+    // Formatting dates must not need the clock of the system, which is not
+    // available on this target.
+    let time = UNIX_EPOCH + Duration::from_secs(784111777);
+    let json = serde_json::to_string(&Ser::new(&time)).unwrap();
+    assert_eq!(json, r#""Sun, 06 Nov 1994 08:49:37 GMT""#);
+    assert_eq!(serde_json::from_str::<De<SystemTime>>(&json).unwrap().into_inner(), time);
+
+    let cookie = Cookie::parse("id=42; Expires=Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    let json = serde_json::to_string(&Ser::new(&cookie)).unwrap();
+    assert_eq!(serde_json::from_str::<De<Cookie>>(&json).unwrap().into_inner(), cookie);
+}
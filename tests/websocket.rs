@@ -0,0 +1,31 @@
+extern crate hyper_serde;
+extern crate serde_json;
+
+use hyper_serde::SecWebsocketProtocol;
+
+#[test]
+fn test_sec_websocket_protocol() {
+    let header = "chat, superchat,,v2.bookings.example";
+    let protocols = header.parse::<SecWebsocketProtocol>().unwrap();
+    assert_eq!(protocols.len(), 3);
+    assert!(protocols.contains("superchat"));
+    assert!(!protocols.contains("Chat"));
+    assert_eq!(protocols.to_string(), "chat, superchat, v2.bookings.example");
+
+    let json = serde_json::to_string(&protocols).unwrap();
+    assert_eq!(json, r#"["chat","superchat","v2.bookings.example"]"#);
+    assert_eq!(serde_json::from_str::<SecWebsocketProtocol>(&json).unwrap(), protocols);
+}
+
+#[test]
+fn test_sec_websocket_protocol_invalid() {
+    assert!("chat room".parse::<SecWebsocketProtocol>().is_err());
+    assert!("chat, [v2]".parse::<SecWebsocketProtocol>().is_err());
+    assert!("".parse::<SecWebsocketProtocol>().unwrap().is_empty());
+
+    let mut protocols = SecWebsocketProtocol::default();
+    assert!(protocols.push("".to_owned()).is_err());
+    assert!(protocols.is_empty());
+
+    assert!(serde_json::from_str::<SecWebsocketProtocol>(r#"["chat","a,b"]"#).is_err());
+}